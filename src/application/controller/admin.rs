@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, put, serde::json::Json, State};
+
+use crate::{application::service::{book_service::BookService, user_service::UserService}, domain::value_object::{book::{ReqCatalogQuery, ResBookList}, user::{ReqRole, ResUser, ResUserList}}, infrastructure::{db::repositories::{book_repository::BookRepositoryImplSql, user_repository::UserRepositoryImplSql}, rocket_http::{middleware::role_guard::{AdminUser, ModeratorUser}, response_type::custom_response::Response}}};
+
+#[get("/users")]
+pub async fn users(
+    _admin: AdminUser,
+    user_service: &State<Arc<UserService<UserRepositoryImplSql>>>
+)
+-> Response<Json<ResUserList>>
+{
+    user_service.index().await
+}
+
+#[put("/users/<id>/role", data = "<req_role>")]
+pub async fn update_role(
+    id: u32,
+    req_role: Json<ReqRole>,
+    _admin: AdminUser,
+    user_service: &State<Arc<UserService<UserRepositoryImplSql>>>
+)
+-> Response<Json<ResUser>>
+{
+    user_service.update_role(id as i32, req_role).await
+}
+
+#[get("/books?<query..>")]
+pub async fn catalog(
+    query: ReqCatalogQuery,
+    _admin: AdminUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Json<ResBookList>>
+{
+    book_service.catalog(query).await
+}
+
+#[delete("/books/<id>")]
+pub async fn moderate_book(
+    id: u32,
+    _moderator: ModeratorUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<String>
+{
+    book_service.moderate_delete(id as i32).await
+}
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
-use rocket::{get, post, serde::json::Json, State};
+use rocket::{get, patch, post, serde::json::Json, State};
 
-use crate::{domain::value_object::auth::{ReqSignIn, ReqSignUp, ResMe, ResSignIn}, infrastructure::{db::repositories::auth_repository::AuthRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}, };
+use crate::{domain::value_object::auth::{ReqMePatch, ReqRefresh, ReqSignIn, ReqSignUp, ResMe, ResSignIn}, infrastructure::{db::repositories::auth_repository::AuthRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}, };
 use crate::application::service::auth_service::AuthService;
 
 
@@ -18,11 +18,11 @@ pub async fn sign_in(
 
 #[post("/sign-up", data= "<req_sign_up>")]
 pub async fn sign_up(
-    req_sign_up: Json<ReqSignUp>,
+    req_sign_up: Validated<ReqSignUp>,
     auth_service: &State<Arc<AuthService<AuthRepositoryImplSql>>>,
 ) -> Response<Json<String>>
 {
-    auth_service.sign_up(req_sign_up).await
+    auth_service.sign_up(req_sign_up.into_inner()).await
 }
 
 #[get("/me")]
@@ -33,4 +33,35 @@ pub async fn me(
 -> Response<Json<ResMe>>
 {
     auth_service.me(user).await
+}
+
+#[patch("/me", data = "<req_profile>")]
+pub async fn update_me(
+    user: AuthenticatedUser,
+    req_profile: Validated<ReqMePatch>,
+    auth_service: &State<Arc<AuthService<AuthRepositoryImplSql>>>
+)
+-> Response<Json<ResMe>>
+{
+    auth_service.update_me(user, req_profile.into_inner()).await
+}
+
+#[post("/refresh", data = "<req_refresh>")]
+pub async fn refresh(
+    req_refresh: Json<ReqRefresh>,
+    auth_service: &State<Arc<AuthService<AuthRepositoryImplSql>>>,
+)
+-> Response<Json<ResSignIn>>
+{
+    auth_service.refresh(req_refresh).await
+}
+
+#[post("/sign-out", data = "<req_refresh>")]
+pub async fn sign_out(
+    req_refresh: Json<ReqRefresh>,
+    auth_service: &State<Arc<AuthService<AuthRepositoryImplSql>>>,
+)
+-> Response<String>
+{
+    auth_service.sign_out(req_refresh).await
 }
\ No newline at end of file
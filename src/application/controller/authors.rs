@@ -1,58 +1,86 @@
 use std::sync::Arc;
 
-use rocket::{delete, get, post, put, serde::json::Json, State};
+use rocket::{delete, get, patch, post, put, serde::json::Json, State};
 
-use crate::{application::service::author_service::AuthorService, domain::value_object::{author::{ReqAuthor, ResAuthor, ResAuthorList}, book::ResBookList}, infrastructure::{db::repositories::author_repository::AuthorRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}};
+use crate::{application::service::author_service::AuthorService, domain::value_object::{author::{ReqAuthor, ReqAuthorDelete, ReqAuthorPatch, ReqAuthorQuery, ResAuthor, ResAuthorList}, book::ResBookList}, infrastructure::{db::repositories::author_repository::AuthorRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions, validated::Validated}, response_type::custom_response::{Response, Tagged}}}};
 
-#[get("/")]
+#[get("/?<query..>")]
 pub async fn index(
     user: AuthenticatedUser,
+    query: ReqAuthorQuery,
     author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
 )
 -> Response<Json<ResAuthorList>>
 {
-    author_service.index(user).await
+    author_service.index(user, query).await
 }
 
 #[post("/", data = "<req_author>")]
 pub async fn create(
     user: AuthenticatedUser,
     author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>,
-    req_author: Json<ReqAuthor>
+    req_author: Validated<ReqAuthor>
 ) 
 -> Response<Json<ResAuthor>> {
-    author_service.create(user, req_author).await
+    author_service.create(user, req_author.into_inner()).await
 }
 
 #[get("/<id>")]
 pub async fn show(
     user: AuthenticatedUser,
     id: u32,
+    preconditions: Preconditions,
     author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
-) -> Response<Json<ResAuthor>> {
-    author_service.show(user, id as i32).await
+) -> Response<Tagged<Json<ResAuthor>>> {
+    author_service.show(user, id as i32, preconditions).await
 }
 
 #[put("/<id>", data = "<req_author>")]
 pub async fn update(
     id: u32,
-    req_author : Json<ReqAuthor>,
+    req_author : Validated<ReqAuthor>,
     user: AuthenticatedUser,
+    preconditions: Preconditions,
     author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
 ) 
--> Response<Json<ResAuthor>> {
-    author_service.update(user, id as i32, req_author).await
+-> Response<Tagged<Json<ResAuthor>>> {
+    author_service.update(user, id as i32, req_author.into_inner(), preconditions).await
+}
+
+#[patch("/<id>", data = "<req_author>")]
+pub async fn patch(
+    id: u32,
+    req_author: Validated<ReqAuthorPatch>,
+    user: AuthenticatedUser,
+    preconditions: Preconditions,
+    author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResAuthor>>> {
+    author_service.patch(user, id as i32, req_author.into_inner(), preconditions).await
 }
 
-#[delete("/<id>")]
+#[delete("/<id>?<query..>")]
 pub async fn delete(
     id: u32,
+    query: ReqAuthorDelete,
     user: AuthenticatedUser,
+    preconditions: Preconditions,
     author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
 )
 -> Response<String> 
 {
-        author_service.delete(user, id as i32).await
+        author_service.delete(user, id as i32, query, preconditions).await
+}
+
+#[post("/<id>/restore")]
+pub async fn restore(
+    id: u32,
+    user: AuthenticatedUser,
+    author_service: &State<Arc<AuthorService<AuthorRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResAuthor>>>
+{
+    author_service.restore(user, id as i32).await
 }
 
 #[get("/<author_id>/books")]
@@ -1,57 +1,156 @@
 use std::sync::Arc;
 
-use rocket::{delete, get, post, put, serde::json::Json, State};
+use rocket::{delete, form::Form, get, http::ContentType, patch, post, put, serde::json::Json, State};
 
-use crate::{application::service::book_service::BookService, domain::value_object::book::{ReqBook, ResBook, ResBookList}, infrastructure::{db::repositories::book_repository::BookRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}};
+use crate::{application::service::book_service::BookService, domain::value_object::{batch::ResBatch, book::{ReqBook, ReqBookBatch, ReqBookPatch, ReqBookQuery, ResBook, ResBookList}, cover::{ReqCoverQuery, ReqCoverUpload}, metadata::{BookMetadata, ReqBookDraft, ReqBookLookup}}, infrastructure::{db::repositories::book_repository::BookRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions, validated::Validated}, response_type::custom_response::{Cached, Response, Tagged}}}};
 
-#[get("/")]
+#[get("/?<query..>")]
 pub async fn index(
     user: AuthenticatedUser,
+    query: ReqBookQuery,
     book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
 ) 
 -> Response<Json<ResBookList>> {
-    book_service.index(user).await
+    book_service.index(user, query).await
 }
 
 #[post("/", data = "<req_book>")]
 pub async fn create(
     user: AuthenticatedUser,
     book_service: &State<Arc<BookService<BookRepositoryImplSql>>>,
-    req_book: Json<ReqBook>
+    req_book: Validated<ReqBook>
 ) -> Response<Json<ResBook>> {
-    book_service.create(user, req_book).await
+    book_service.create(user, req_book.into_inner()).await
+}
+
+// title, publication date, cover and author come from the metadata provider unless given
+#[post("/?enrich=true", data = "<draft>")]
+pub async fn create_enriched(
+    user: AuthenticatedUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>,
+    draft: Validated<ReqBookDraft>
+) -> Response<Json<ResBook>> {
+    book_service.create_enriched(user, draft.into_inner()).await
+}
+
+#[post("/lookup", data = "<req_lookup>")]
+pub async fn lookup(
+    _user: AuthenticatedUser,
+    req_lookup: Validated<ReqBookLookup>,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Json<BookMetadata>>
+{
+    book_service.lookup(req_lookup.into_inner()).await
+}
+
+#[post("/batch", data = "<req_batch>")]
+pub async fn batch(
+    user: AuthenticatedUser,
+    req_batch: Validated<ReqBookBatch>,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Json<ResBatch>>
+{
+    book_service.batch(user, req_batch.into_inner()).await
 }
 
 #[get("/<id>")]
 pub async fn show(
     id: u32,
     user: AuthenticatedUser,
+    preconditions: Preconditions,
     book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
 ) 
--> Response<Json<ResBook>> 
+-> Response<Tagged<Json<ResBook>>> 
 {
-    book_service.show(user, id as i32).await
+    book_service.show(user, id as i32, preconditions).await
+}
+
+#[get("/by-isbn/<isbn>")]
+pub async fn by_isbn(
+    isbn: String,
+    user: AuthenticatedUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResBook>>>
+{
+    book_service.by_isbn(user, isbn).await
 }
 
 #[put("/<id>", data = "<req_book>")]
 pub async fn update(
     id: u32,
-    req_book: Json<ReqBook>,
+    req_book: Validated<ReqBook>,
     user: AuthenticatedUser,
+    preconditions: Preconditions,
     book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
 ) 
--> Response<Json<ResBook>> 
+-> Response<Tagged<Json<ResBook>>> 
+{
+    book_service.update(user, id as i32, req_book.into_inner(), preconditions).await
+}
+
+#[patch("/<id>", data = "<req_book>")]
+pub async fn patch(
+    id: u32,
+    req_book: Validated<ReqBookPatch>,
+    user: AuthenticatedUser,
+    preconditions: Preconditions,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResBook>>>
 {
-    book_service.update(user, id as i32, req_book).await
+    book_service.patch(user, id as i32, req_book.into_inner(), preconditions).await
 }
 
 #[delete("/<id>")]
 pub async fn delete(
     id: u32,
     user: AuthenticatedUser,
+    preconditions: Preconditions,
     book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
 ) 
 -> Response<String> 
 {
-    book_service.delete(user, id as i32).await
+    book_service.delete(user, id as i32, preconditions).await
+}
+
+#[post("/<id>/restore")]
+pub async fn restore(
+    id: u32,
+    user: AuthenticatedUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResBook>>>
+{
+    book_service.restore(user, id as i32).await
+}
+
+
+// multipart with a `file` field holding a JPEG, PNG or WebP image
+#[put("/<id>/cover", data = "<upload>")]
+pub async fn upload_cover(
+    id: u32,
+    upload: Form<ReqCoverUpload<'_>>,
+    user: AuthenticatedUser,
+    preconditions: Preconditions,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Tagged<Json<ResBook>>>
+{
+    book_service.upload_cover(user, id as i32, upload.into_inner(), preconditions).await
+}
+
+#[get("/<id>/cover?<query..>")]
+pub async fn cover(
+    id: u32,
+    query: ReqCoverQuery,
+    user: AuthenticatedUser,
+    preconditions: Preconditions,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Cached<Tagged<(ContentType, Vec<u8>)>>>
+{
+    book_service.cover(user, id as i32, query.size, preconditions).await
 }
\ No newline at end of file
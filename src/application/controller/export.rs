@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use rocket::{futures::stream::BoxStream, get, response::stream::TextStream, State};
+
+use crate::{application::service::book_service::BookService, domain::value_object::export::ExportFormat, infrastructure::{db::repositories::book_repository::BookRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::{Download, Response}}}};
+
+#[get("/?<format>")]
+pub async fn export(
+    format: ExportFormat,
+    user: AuthenticatedUser,
+    book_service: &State<Arc<BookService<BookRepositoryImplSql>>>
+)
+-> Response<Download<TextStream<BoxStream<'static, String>>>>
+{
+    book_service.export(user, format)
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use rocket::{form::Form, http::ContentType, post, serde::json::Json, tokio::io::AsyncReadExt, State};
+
+use crate::{application::service::import_service::ImportService, domain::value_object::import::{ImportFormat, ReqImportUpload, ResImport}, infrastructure::{db::repositories::import_repository::ImportRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::Response}}}};
+
+// the format named in the form, or else the one of the file
+fn import_format(upload: &ReqImportUpload<'_>) -> Result<ImportFormat, AppError> {
+    if let Some(format) = upload.format {
+        return Ok(format);
+    }
+
+    match upload.file.content_type() {
+        Some(ct) if ct.is_csv() || *ct == ContentType::new("application", "vnd.ms-excel") => Ok(ImportFormat::Csv),
+        Some(ct) if ct.is_json() => Ok(ImportFormat::Json),
+        _ => Err(AppError::invalid_field("format", "must be csv or json when the file is not text/csv or application/json")),
+    }
+}
+
+#[post("/?<dry_run>", data = "<upload>")]
+pub async fn import(
+    dry_run: Option<bool>,
+    user: AuthenticatedUser,
+    upload: Form<ReqImportUpload<'_>>,
+    import_service: &State<Arc<ImportService<ImportRepositoryImplSql>>>
+)
+-> Response<Json<ResImport>>
+{
+    let format = import_format(&upload)?;
+
+    let mut text = String::new();
+    upload.file.open().await
+        .map_err(|e| AppError::Internal(format!("reading the upload: {}", e)))?
+        .read_to_string(&mut text)
+        .await
+        .map_err(|_| AppError::invalid_field("file", "must be UTF-8 text"))?;
+
+    import_service.import(user, format, &text, dry_run.unwrap_or(false)).await
+}
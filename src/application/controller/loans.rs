@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use rocket::{get, post, serde::json::Json, State};
+
+use crate::{application::service::loan_service::LoanService, domain::value_object::loan::{ReqLoan, ReqLoanQuery, ReqLoanReturn, ResLoan, ResLoanList}, infrastructure::{db::repositories::loan_repository::LoanRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/?<query..>")]
+pub async fn index(
+    user: AuthenticatedUser,
+    query: ReqLoanQuery,
+    loan_service: &State<Arc<LoanService<LoanRepositoryImplSql>>>
+)
+-> Response<Json<ResLoanList>>
+{
+    loan_service.index(user, query).await
+}
+
+#[post("/<id>/return", data = "<req_return>")]
+pub async fn return_loan(
+    id: u32,
+    req_return: Validated<ReqLoanReturn>,
+    user: AuthenticatedUser,
+    loan_service: &State<Arc<LoanService<LoanRepositoryImplSql>>>
+)
+-> Response<Json<ResLoan>> {
+    loan_service.return_loan(user, id as i32, req_return.into_inner()).await
+}
+
+// ranked below `/books/by-isbn/<isbn>`, which has the same shape
+#[get("/<book_id>/loans", rank = 2)]
+pub async fn history(
+    book_id: u32,
+    user: AuthenticatedUser,
+    loan_service: &State<Arc<LoanService<LoanRepositoryImplSql>>>
+) -> Response<Json<ResLoanList>> {
+    loan_service.history(user, book_id as i32).await
+}
+
+#[post("/<book_id>/loans", data = "<req_loan>")]
+pub async fn lend(
+    book_id: u32,
+    req_loan: Validated<ReqLoan>,
+    user: AuthenticatedUser,
+    loan_service: &State<Arc<LoanService<LoanRepositoryImplSql>>>
+)
+-> Response<Json<ResLoan>> {
+    loan_service.lend(user, book_id as i32, req_loan.into_inner()).await
+}
@@ -1,3 +1,15 @@
 pub mod auth;
 pub mod authors;
-pub mod books;
\ No newline at end of file
+pub mod books;
+pub mod admin;
+pub mod search;
+pub mod trash;
+pub mod import;
+pub mod export;
+pub mod series;
+pub mod reading;
+pub mod reviews;
+pub mod shelves;
+pub mod tags;
+pub mod loans;
+pub mod stats;
\ No newline at end of file
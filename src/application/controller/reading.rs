@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use rocket::{get, post, put, serde::json::Json, State};
+
+use crate::{application::service::reading_service::ReadingService, domain::value_object::reading::{ReqReadingSession, ReqReadingStatus, ResCurrentReading, ResReadingState, ResSessionLogged}, infrastructure::{db::repositories::reading_repository::ReadingRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[put("/<id>/status", data = "<req_status>")]
+pub async fn set_status(
+    id: u32,
+    req_status: Validated<ReqReadingStatus>,
+    user: AuthenticatedUser,
+    reading_service: &State<Arc<ReadingService<ReadingRepositoryImplSql>>>
+)
+-> Response<Json<ResReadingState>> {
+    reading_service.set_status(user, id as i32, req_status.into_inner()).await
+}
+
+#[post("/<id>/sessions", data = "<req_session>")]
+pub async fn log_session(
+    id: u32,
+    req_session: Validated<ReqReadingSession>,
+    user: AuthenticatedUser,
+    reading_service: &State<Arc<ReadingService<ReadingRepositoryImplSql>>>
+)
+-> Response<Json<ResSessionLogged>> {
+    reading_service.log_session(user, id as i32, req_session.into_inner()).await
+}
+
+#[get("/current")]
+pub async fn current(
+    user: AuthenticatedUser,
+    reading_service: &State<Arc<ReadingService<ReadingRepositoryImplSql>>>
+) -> Response<Json<ResCurrentReading>> {
+    reading_service.current(user).await
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, post, put, serde::json::Json, State};
+
+use crate::{application::service::review_service::ReviewService, domain::value_object::review::{ReqReview, ReqReviewQuery, ResReview, ResReviewList}, infrastructure::{db::repositories::review_repository::ReviewRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/<book_id>/reviews?<query..>")]
+pub async fn index(
+    book_id: u32,
+    user: AuthenticatedUser,
+    query: ReqReviewQuery,
+    review_service: &State<Arc<ReviewService<ReviewRepositoryImplSql>>>
+)
+-> Response<Json<ResReviewList>>
+{
+    review_service.index(user, book_id as i32, query).await
+}
+
+#[post("/<book_id>/reviews", data = "<req_review>")]
+pub async fn create(
+    book_id: u32,
+    user: AuthenticatedUser,
+    review_service: &State<Arc<ReviewService<ReviewRepositoryImplSql>>>,
+    req_review: Validated<ReqReview>
+)
+-> Response<Json<ResReview>> {
+    review_service.create(user, book_id as i32, req_review.into_inner()).await
+}
+
+#[get("/<book_id>/reviews/<id>")]
+pub async fn show(
+    book_id: u32,
+    id: u32,
+    user: AuthenticatedUser,
+    review_service: &State<Arc<ReviewService<ReviewRepositoryImplSql>>>
+) -> Response<Json<ResReview>> {
+    review_service.show(user, book_id as i32, id as i32).await
+}
+
+#[put("/<book_id>/reviews/<id>", data = "<req_review>")]
+pub async fn update(
+    book_id: u32,
+    id: u32,
+    req_review: Validated<ReqReview>,
+    user: AuthenticatedUser,
+    review_service: &State<Arc<ReviewService<ReviewRepositoryImplSql>>>
+)
+-> Response<Json<ResReview>> {
+    review_service.update(user, book_id as i32, id as i32, req_review.into_inner()).await
+}
+
+#[delete("/<book_id>/reviews/<id>")]
+pub async fn delete(
+    book_id: u32,
+    id: u32,
+    user: AuthenticatedUser,
+    review_service: &State<Arc<ReviewService<ReviewRepositoryImplSql>>>
+) -> Response<String> {
+    review_service.delete(user, book_id as i32, id as i32).await
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use rocket::{get, serde::json::Json, State};
+
+use crate::{application::service::search_service::SearchService, domain::value_object::search::ResSearchList, infrastructure::{db::repositories::search_repository::SearchRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}};
+
+#[get("/?<q>&<limit>")]
+pub async fn search(
+    q: String,
+    limit: Option<u64>,
+    user: AuthenticatedUser,
+    search_service: &State<Arc<SearchService<SearchRepositoryImplSql>>>
+)
+-> Response<Json<ResSearchList>>
+{
+    search_service.search(user, q, limit).await
+}
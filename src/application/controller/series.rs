@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, patch, post, put, serde::json::Json, State};
+
+use crate::{application::service::series_service::SeriesService, domain::value_object::series::{ReqSeries, ReqSeriesPatch, ReqSeriesQuery, ResSeries, ResSeriesBooks, ResSeriesList}, infrastructure::{db::repositories::series_repository::SeriesRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/?<query..>")]
+pub async fn index(
+    user: AuthenticatedUser,
+    query: ReqSeriesQuery,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+)
+-> Response<Json<ResSeriesList>>
+{
+    series_service.index(user, query).await
+}
+
+#[post("/", data = "<req_series>")]
+pub async fn create(
+    user: AuthenticatedUser,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>,
+    req_series: Validated<ReqSeries>
+)
+-> Response<Json<ResSeries>> {
+    series_service.create(user, req_series.into_inner()).await
+}
+
+#[get("/<id>")]
+pub async fn show(
+    user: AuthenticatedUser,
+    id: u32,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+) -> Response<Json<ResSeries>> {
+    series_service.show(user, id as i32).await
+}
+
+#[put("/<id>", data = "<req_series>")]
+pub async fn update(
+    id: u32,
+    req_series: Validated<ReqSeries>,
+    user: AuthenticatedUser,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+)
+-> Response<Json<ResSeries>> {
+    series_service.update(user, id as i32, req_series.into_inner()).await
+}
+
+#[patch("/<id>", data = "<req_series>")]
+pub async fn patch(
+    id: u32,
+    req_series: Validated<ReqSeriesPatch>,
+    user: AuthenticatedUser,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+)
+-> Response<Json<ResSeries>> {
+    series_service.patch(user, id as i32, req_series.into_inner()).await
+}
+
+#[delete("/<id>")]
+pub async fn delete(
+    id: u32,
+    user: AuthenticatedUser,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+)
+-> Response<String>
+{
+    series_service.delete(user, id as i32).await
+}
+
+#[get("/<id>/books")]
+pub async fn books(
+    id: u32,
+    user: AuthenticatedUser,
+    series_service: &State<Arc<SeriesService<SeriesRepositoryImplSql>>>
+)
+-> Response<Json<ResSeriesBooks>>
+{
+    series_service.books(user, id as i32).await
+}
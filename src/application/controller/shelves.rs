@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, post, put, serde::json::Json, State};
+
+use crate::{application::service::shelf_service::ShelfService, domain::value_object::shelf::{ReqShelf, ResShelf, ResShelfList}, infrastructure::{db::repositories::shelf_repository::ShelfRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/")]
+pub async fn index(
+    user: AuthenticatedUser,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+)
+-> Response<Json<ResShelfList>>
+{
+    shelf_service.index(user).await
+}
+
+#[post("/", data = "<req_shelf>")]
+pub async fn create(
+    user: AuthenticatedUser,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>,
+    req_shelf: Validated<ReqShelf>
+)
+-> Response<Json<ResShelf>> {
+    shelf_service.create(user, req_shelf.into_inner()).await
+}
+
+#[get("/<id>")]
+pub async fn show(
+    user: AuthenticatedUser,
+    id: u32,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+) -> Response<Json<ResShelf>> {
+    shelf_service.show(user, id as i32).await
+}
+
+#[put("/<id>", data = "<req_shelf>")]
+pub async fn update(
+    id: u32,
+    req_shelf: Validated<ReqShelf>,
+    user: AuthenticatedUser,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+)
+-> Response<Json<ResShelf>> {
+    shelf_service.update(user, id as i32, req_shelf.into_inner()).await
+}
+
+#[delete("/<id>")]
+pub async fn delete(
+    user: AuthenticatedUser,
+    id: u32,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+) -> Response<String> {
+    shelf_service.delete(user, id as i32).await
+}
+
+#[put("/<id>/books/<book_id>")]
+pub async fn add_book(
+    user: AuthenticatedUser,
+    id: u32,
+    book_id: u32,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+) -> Response<String> {
+    shelf_service.add_book(user, id as i32, book_id as i32).await
+}
+
+#[delete("/<id>/books/<book_id>")]
+pub async fn remove_book(
+    user: AuthenticatedUser,
+    id: u32,
+    book_id: u32,
+    shelf_service: &State<Arc<ShelfService<ShelfRepositoryImplSql>>>
+) -> Response<String> {
+    shelf_service.remove_book(user, id as i32, book_id as i32).await
+}
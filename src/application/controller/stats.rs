@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, put, serde::json::Json, State};
+
+use crate::{application::service::stats_service::StatsService, domain::value_object::stats::{ReqReadingGoal, ReqStatsQuery, ResReadingGoal, ResStats}, infrastructure::{db::repositories::stats_repository::StatsRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/?<query..>")]
+pub async fn stats(
+    user: AuthenticatedUser,
+    query: ReqStatsQuery,
+    stats_service: &State<Arc<StatsService<StatsRepositoryImplSql>>>
+)
+-> Response<Json<ResStats>>
+{
+    stats_service.stats(user, query).await
+}
+
+#[put("/goals/<year>", data = "<req_goal>")]
+pub async fn set_goal(
+    year: i32,
+    req_goal: Validated<ReqReadingGoal>,
+    user: AuthenticatedUser,
+    stats_service: &State<Arc<StatsService<StatsRepositoryImplSql>>>
+)
+-> Response<Json<ResReadingGoal>> {
+    stats_service.set_goal(user, year, req_goal.into_inner()).await
+}
+
+#[delete("/goals/<year>")]
+pub async fn delete_goal(
+    year: i32,
+    user: AuthenticatedUser,
+    stats_service: &State<Arc<StatsService<StatsRepositoryImplSql>>>
+) -> Response<String> {
+    stats_service.delete_goal(user, year).await
+}
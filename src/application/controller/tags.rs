@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use rocket::{delete, get, put, serde::json::Json, State};
+
+use crate::{application::service::tag_service::TagService, domain::value_object::tag::{ReqBookTags, ReqTag, ReqTagQuery, ResBookTags, ResTag, ResTagList}, infrastructure::{db::repositories::tag_repository::TagRepositoryImplSql, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, validated::Validated}, response_type::custom_response::Response}}};
+
+#[get("/?<query..>")]
+pub async fn index(
+    user: AuthenticatedUser,
+    query: ReqTagQuery,
+    tag_service: &State<Arc<TagService<TagRepositoryImplSql>>>
+)
+-> Response<Json<ResTagList>>
+{
+    tag_service.index(user, query).await
+}
+
+#[put("/<id>", data = "<req_tag>")]
+pub async fn rename(
+    id: u32,
+    req_tag: Validated<ReqTag>,
+    user: AuthenticatedUser,
+    tag_service: &State<Arc<TagService<TagRepositoryImplSql>>>
+)
+-> Response<Json<ResTag>> {
+    tag_service.rename(user, id as i32, req_tag.into_inner()).await
+}
+
+#[delete("/<id>")]
+pub async fn delete(
+    user: AuthenticatedUser,
+    id: u32,
+    tag_service: &State<Arc<TagService<TagRepositoryImplSql>>>
+) -> Response<String> {
+    tag_service.delete(user, id as i32).await
+}
+
+#[put("/<book_id>/tags", data = "<req_tags>")]
+pub async fn set_book_tags(
+    book_id: u32,
+    req_tags: Validated<ReqBookTags>,
+    user: AuthenticatedUser,
+    tag_service: &State<Arc<TagService<TagRepositoryImplSql>>>
+)
+-> Response<Json<ResBookTags>> {
+    tag_service.set_book_tags(user, book_id as i32, req_tags.into_inner()).await
+}
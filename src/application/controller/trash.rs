@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use rocket::{get, serde::json::Json, State};
+
+use crate::{application::service::trash_service::TrashService, domain::value_object::trash::ResTrash, infrastructure::{db::repositories::trash_repository::TrashRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}};
+
+#[get("/")]
+pub async fn index(
+    user: AuthenticatedUser,
+    trash_service: &State<Arc<TrashService<TrashRepositoryImplSql>>>
+)
+-> Response<Json<ResTrash>>
+{
+    trash_service.index(user).await
+}
@@ -0,0 +1,35 @@
+use std::{sync::Arc, time::Duration};
+
+use rocket::{fairing::AdHoc, tokio::{self, time}};
+
+use crate::{application::service::trash_service::TrashService, config::trash_config::TrashConfig, infrastructure::db::repositories::trash_repository::TrashRepositoryImplSql};
+
+// periodically purge the trash, until the server shuts down
+pub fn trash_purge() -> AdHoc {
+    AdHoc::on_liftoff("Trash purge", |rocket| Box::pin(async move {
+        let Some(trash_service) = rocket.state::<Arc<TrashService<TrashRepositoryImplSql>>>() else {
+            rocket::error!("trash purge disabled: trash service is not managed");
+            return;
+        };
+        let trash_service = Arc::clone(trash_service);
+        let mut shutdown = rocket.shutdown();
+        let mut interval = time::interval(Duration::from_secs(TrashConfig::default().purge_interval.max(1)));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = &mut shutdown => break,
+                }
+
+                match trash_service.purge_expired().await {
+                    Ok(count) if count.books + count.authors > 0 => {
+                        rocket::info!("trash purge removed {} books and {} authors", count.books, count.authors);
+                    }
+                    Ok(_) => {}
+                    Err(e) => rocket::error!("trash purge failed: {}", e),
+                }
+            }
+        });
+    }))
+}
@@ -1,2 +1,3 @@
 pub mod controller;
+pub mod job;
 pub mod service;
\ No newline at end of file
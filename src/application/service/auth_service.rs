@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use rocket::serde::json::Json;
 
-use crate::{domain::{repositories::auth_repository::AuthRepository, value_object::auth::{ReqSignIn, ReqSignUp, ResMe, ResSignIn}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+use crate::{domain::{repositories::auth_repository::AuthRepository, value_object::auth::{ReqMePatch, ReqRefresh, ReqSignIn, ReqSignUp, ResMe, ResSignIn}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
 
 pub struct AuthService<T> 
 where 
@@ -40,5 +40,17 @@ where
     pub async fn me(&self, user: AuthenticatedUser) -> Response<Json<ResMe>>{
         self.auth_service.me(user).await
     }
+
+    pub async fn update_me(&self, user: AuthenticatedUser, profile: Json<ReqMePatch>) -> Response<Json<ResMe>> {
+        self.auth_service.update_me(user, profile).await
+    }
+
+    pub async fn refresh(&self, refresh_data: Json<ReqRefresh>) -> Response<Json<ResSignIn>> {
+        self.auth_service.refresh(refresh_data).await
+    }
+
+    pub async fn sign_out(&self, refresh_data: Json<ReqRefresh>) -> Response<String> {
+        self.auth_service.sign_out(refresh_data).await
+    }
 }
 
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use rocket::serde::json::Json;
 
-use crate::{domain::{repositories::author_repository::AuthorRepository, value_object::{author::{ReqAuthor, ResAuthor, ResAuthorList}, book::ResBookList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+use crate::{domain::{repositories::author_repository::AuthorRepository, value_object::{author::{ReqAuthor, ReqAuthorDelete, ReqAuthorPatch, ReqAuthorQuery, ResAuthor, ResAuthorList}, book::ResBookList}}, infrastructure::rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::custom_response::{Response, Tagged}}};
 
 pub struct AuthorService<T>
 where 
@@ -23,21 +23,29 @@ where
     }
 
      // get all author
-     pub async fn index(&self,user: AuthenticatedUser) -> Response<Json<ResAuthorList>> {
-        self.author_service.index(user).await
+     pub async fn index(&self,user: AuthenticatedUser, query: ReqAuthorQuery) -> Response<Json<ResAuthorList>> {
+        self.author_service.index(user, query).await
      }
 
      // et athor by id
-     pub async fn show(&self,user: AuthenticatedUser, id: i32) -> Response<Json<ResAuthor>> {
-        self.author_service.show(user, id).await
+     pub async fn show(&self,user: AuthenticatedUser, id: i32, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>> {
+        self.author_service.show(user, id, preconditions).await
      }
  
-     pub async fn update(&self,user: AuthenticatedUser, id: i32, req_author: Json<ReqAuthor>) -> Response<Json<ResAuthor>> {
-        self.author_service.update(user, id, req_author).await
+     pub async fn update(&self,user: AuthenticatedUser, id: i32, req_author: Json<ReqAuthor>, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>> {
+        self.author_service.update(user, id, req_author, preconditions).await
      }
  
-     pub async fn delete(&self,user: AuthenticatedUser, id: i32) -> Response<String> {
-        self.author_service.delete(user, id).await
+     pub async fn patch(&self, user: AuthenticatedUser, id: i32, req_author: Json<ReqAuthorPatch>, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>> {
+        self.author_service.patch(user, id, req_author, preconditions).await
+     }
+
+     pub async fn restore(&self, user: AuthenticatedUser, id: i32) -> Response<Tagged<Json<ResAuthor>>> {
+        self.author_service.restore(user, id).await
+     }
+
+     pub async fn delete(&self,user: AuthenticatedUser, id: i32, query: ReqAuthorDelete, preconditions: Preconditions) -> Response<String> {
+        self.author_service.delete(user, id, query, preconditions).await
      }
  
      pub async fn create(&self, user: AuthenticatedUser, req_author: Json<ReqAuthor>) -> Response<Json<ResAuthor>>{
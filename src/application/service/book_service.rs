@@ -1,14 +1,19 @@
 use std::sync::Arc;
 
-use rocket::serde::json::Json;
+use rocket::{futures::stream::BoxStream, http::{ContentType, Status}, response::stream::TextStream, serde::json::Json, tokio::io::AsyncReadExt};
+use sha2::{Digest, Sha256};
 
-use crate::{domain::{repositories::book_repository::BookRepository, value_object::book::{ReqBook, ResBook, ResBookList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+use crate::{config::cover_config::CoverConfig, domain::{repositories::{blob_store::BlobStore, book_repository::BookRepository, metadata_provider::MetadataProvider, thumbnailer::Thumbnailer}, value_object::{batch::ResBatch, book::{ReqBook, ReqBookBatch, ReqBookPatch, ReqBookQuery, ReqCatalogQuery, ResBook, ResBookList}, cover::{Blob, CoverRef, CoverSize, ImageType, ReqCoverUpload}, export::ExportFormat, isbn::Isbn, metadata::{BookMetadata, ReqBookDraft, ReqBookLookup}}}, infrastructure::{format::export::{content_type, render}, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::{app_error::AppError, custom_response::{Cached, Download, Response, SuccessResponse, Tagged}}}}};
 
 pub struct BookService<T>
 where
     T: BookRepository + Send + Sync,
 {
-    book_service: Arc<T>
+    book_service: Arc<T>,
+    metadata: Arc<dyn MetadataProvider + Send + Sync>,
+    blobs: Arc<dyn BlobStore + Send + Sync>,
+    thumbnailer: Arc<dyn Thumbnailer + Send + Sync>,
+    covers: CoverConfig,
 }
 
 impl<T> BookService<T>
@@ -16,29 +21,193 @@ where
     T : BookRepository + Send + Sync,
 {
     
-    pub fn new(book_service: Arc<T>) -> Self {
+    pub fn new(
+        book_service: Arc<T>,
+        metadata: Arc<dyn MetadataProvider + Send + Sync>,
+        blobs: Arc<dyn BlobStore + Send + Sync>,
+        thumbnailer: Arc<dyn Thumbnailer + Send + Sync>,
+        covers: CoverConfig,
+    ) -> Self {
         Self {
-            book_service
+            book_service,
+            metadata,
+            blobs,
+            thumbnailer,
+            covers
         }
     }
 
-    pub async fn index(&self, user: AuthenticatedUser) -> Response<Json<ResBookList>>{
-        self.book_service.index(user).await
+    // validated ISBNs only
+    async fn metadata_of(&self, isbn: &str) -> Result<Option<BookMetadata>, AppError> {
+        let isbn = Isbn::parse(isbn).map_err(|e| AppError::invalid_field("isbn", e.message()))?;
+        self.metadata.lookup(&isbn).await
     }
 
-    pub async fn show(&self,user: AuthenticatedUser, id: i32) -> Response<Json<ResBook>>{
-        self.book_service.show(user, id).await
+    pub async fn index(&self, user: AuthenticatedUser, query: ReqBookQuery) -> Response<Json<ResBookList>>{
+        self.book_service.index(user, query).await
     }
 
-    pub async fn update(&self, user: AuthenticatedUser, id: i32, req_book: Json<ReqBook>) -> Response<Json<ResBook>>{
-        self.book_service.update(user, id, req_book).await
+    pub async fn show(&self,user: AuthenticatedUser, id: i32, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>{
+        self.book_service.show(user, id, preconditions).await
     }
 
-    pub async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String>{
-        self.book_service.delete(user, id).await
+    pub async fn by_isbn(&self, user: AuthenticatedUser, isbn: String) -> Response<Tagged<Json<ResBook>>>{
+        self.book_service.by_isbn(user, isbn).await
+    }
+
+    pub async fn update(&self, user: AuthenticatedUser, id: i32, req_book: Json<ReqBook>, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>{
+        self.book_service.update(user, id, req_book, preconditions).await
+    }
+
+    pub async fn patch(&self, user: AuthenticatedUser, id: i32, req_book: Json<ReqBookPatch>, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>{
+        self.book_service.patch(user, id, req_book, preconditions).await
+    }
+
+    pub async fn delete(&self, user: AuthenticatedUser, id: i32, preconditions: Preconditions) -> Response<String>{
+        self.book_service.delete(user, id, preconditions).await
+    }
+
+    pub async fn restore(&self, user: AuthenticatedUser, id: i32) -> Response<Tagged<Json<ResBook>>> {
+        self.book_service.restore(user, id).await
     }
 
     pub async fn create(&self, user: AuthenticatedUser, req_book: Json<ReqBook>) -> Response<Json<ResBook>> {
         self.book_service.create(user, req_book).await
     }
+
+    pub async fn lookup(&self, req_lookup: Json<ReqBookLookup>) -> Response<Json<BookMetadata>> {
+        let metadata = self
+            .metadata_of(&req_lookup.isbn)
+            .await?
+            .ok_or_else(|| AppError::NotFound("No metadata found for this ISBN".to_string()))?;
+
+        Ok(SuccessResponse((Status::Ok, Json(metadata))))
+    }
+
+    // an unknown ISBN only fails when the draft leaves fields empty
+    pub async fn create_enriched(&self, user: AuthenticatedUser, draft: Json<ReqBookDraft>) -> Response<Json<ResBook>> {
+        let draft = draft.into_inner();
+        let metadata = match self.metadata_of(&draft.isbn).await? {
+            Some(metadata) => metadata,
+            None => BookMetadata {
+                isbn10: None,
+                isbn13: draft.isbn.to_owned(),
+                title: None,
+                published_on: None,
+                cover: None,
+                authors: Vec::new(),
+            },
+        };
+
+        self.book_service.create_enriched(user, draft, metadata).await
+    }
+
+    // the original and its thumbnails are stored before the book points at them
+    pub async fn upload_cover(&self, user: AuthenticatedUser, id: i32, upload: ReqCoverUpload<'_>, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>> {
+        if upload.file.len() > self.covers.max_bytes {
+            return Err(AppError::PayloadTooLarge(format!(
+                "The cover must not be larger than {} bytes",
+                self.covers.max_bytes
+            )));
+        }
+
+        let read_error = |e: std::io::Error| AppError::Internal(format!("reading the upload: {}", e));
+        let mut image = Vec::new();
+        upload.file.open().await.map_err(read_error)?.read_to_end(&mut image).await.map_err(read_error)?;
+
+        let image_type = ImageType::sniff(&image).ok_or_else(|| {
+            AppError::UnsupportedMediaType("The cover must be a JPEG, PNG or WebP image".to_string())
+        })?;
+
+        // 404 before anything is stored
+        let previous = self.book_service.cover(user.clone(), id).await?;
+
+        let cover = CoverRef {
+            hash: hex::encode(Sha256::digest(&image)),
+            content_type: image_type.mime().to_string(),
+        };
+        for size in CoverSize::THUMBNAILS {
+            let max_side = size.max_side().expect("thumbnails have a size");
+            let bytes = self.thumbnailer.thumbnail(&image, max_side).await?;
+            self.blobs.put(&cover.key(id, size), Blob { content_type: cover.content_type.to_owned(), bytes }).await?;
+        }
+        self.blobs
+            .put(&cover.key(id, CoverSize::Original), Blob { content_type: cover.content_type.to_owned(), bytes: image })
+            .await?;
+
+        let result = self.book_service.set_cover(user, id, cover.to_owned(), preconditions).await;
+
+        // whichever cover the book does not point at is dropped
+        let unused = match &result {
+            Ok(_) => previous.filter(|p| p.hash != cover.hash),
+            Err(_) if previous.as_ref() != Some(&cover) => Some(cover),
+            Err(_) => None,
+        };
+        if let Some(unused) = unused {
+            for size in CoverSize::ALL {
+                if let Err(e) = self.blobs.delete(&unused.key(id, size)).await {
+                    e.log();
+                }
+            }
+        }
+
+        result
+    }
+
+    pub async fn cover(&self, user: AuthenticatedUser, id: i32, size: CoverSize, preconditions: Preconditions) -> Response<Cached<Tagged<(ContentType, Vec<u8>)>>> {
+        let cover = self
+            .book_service
+            .cover(user, id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Cover"))?;
+
+        // covers are private, only the user's own cache keeps them
+        let cache_control = format!("private, max-age={}", self.covers.max_age);
+        let etag = cover.etag(size);
+
+        if preconditions.not_modified(&etag) {
+            return Ok(SuccessResponse((
+                Status::NotModified,
+                Cached { cache_control, body: Tagged { etag, body: None } },
+            )));
+        }
+
+        let blob = self
+            .blobs
+            .get(&cover.key(id, size))
+            .await?
+            .ok_or_else(|| AppError::not_found("Cover"))?;
+        let content_type = ContentType::parse_flexible(&blob.content_type).unwrap_or(ContentType::Binary);
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Cached { cache_control, body: Tagged { etag, body: Some((content_type, blob.bytes)) } },
+        )))
+    }
+
+    pub async fn batch(&self, user: AuthenticatedUser, req_batch: Json<ReqBookBatch>) -> Response<Json<ResBatch>> {
+        self.book_service.batch(user, req_batch).await
+    }
+
+    // streamed, the library is never held in memory at once
+    pub fn export(&self, user: AuthenticatedUser, format: ExportFormat) -> Response<Download<TextStream<BoxStream<'static, String>>>> {
+        let rows = self.book_service.export(user);
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Download {
+                filename: format!("library.{}", format.extension()),
+                content_type: content_type(format),
+                body: TextStream(render(format, rows)),
+            }
+        )))
+    }
+
+    pub async fn catalog(&self, query: ReqCatalogQuery) -> Response<Json<ResBookList>> {
+        self.book_service.catalog(query).await
+    }
+
+    pub async fn moderate_delete(&self, id: i32) -> Response<String> {
+        self.book_service.moderate_delete(id).await
+    }
 }
\ No newline at end of file
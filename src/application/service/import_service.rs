@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::import_repository::ImportRepository, value_object::import::{ImportFormat, ResImport}}, infrastructure::{format::import::read_rows, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}}};
+
+pub struct ImportService<T>
+where
+    T: ImportRepository + Send + Sync,
+{
+    import_service: Arc<T>
+}
+
+impl<T> ImportService<T>
+where
+    T: ImportRepository + Send + Sync,
+{
+    pub fn new(import_service: Arc<T>) -> Self {
+        Self {
+            import_service
+        }
+    }
+
+    pub async fn import(&self, user: AuthenticatedUser, format: ImportFormat, text: &str, dry_run: bool) -> Response<Json<ResImport>> {
+        let rows = read_rows(format, text)?;
+        self.import_service.import(user, rows, dry_run).await
+    }
+}
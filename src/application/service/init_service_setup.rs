@@ -3,9 +3,9 @@ use std::sync::Arc;
 use rocket::fairing::AdHoc;
 use sea_orm::DatabaseConnection;
 
-use crate::infrastructure::db::repositories::{auth_repository::AuthRepositoryImplSql, author_repository::AuthorRepositoryImplSql, book_repository::BookRepositoryImplSql};
+use crate::{config::{blob_config::BlobConfig, cover_config::CoverConfig, metadata_config::MetadataConfig}, infrastructure::{blob::blob_store, db::repositories::{auth_repository::AuthRepositoryImplSql, author_repository::AuthorRepositoryImplSql, book_repository::BookRepositoryImplSql, import_repository::ImportRepositoryImplSql, loan_repository::LoanRepositoryImplSql, reading_repository::ReadingRepositoryImplSql, review_repository::ReviewRepositoryImplSql, search_repository::SearchRepositoryImplSql, series_repository::SeriesRepositoryImplSql, shelf_repository::ShelfRepositoryImplSql, stats_repository::StatsRepositoryImplSql, tag_repository::TagRepositoryImplSql, trash_repository::TrashRepositoryImplSql, user_repository::UserRepositoryImplSql}, metadata::metadata_provider, thumbnail::CommandThumbnailer}};
 
-use super::{auth_service::AuthService, author_service::AuthorService, book_service::BookService};
+use super::{auth_service::AuthService, author_service::AuthorService, book_service::BookService, import_service::ImportService, loan_service::LoanService, reading_service::ReadingService, review_service::ReviewService, search_service::SearchService, series_service::SeriesService, shelf_service::ShelfService, stats_service::StatsService, tag_service::TagService, trash_service::TrashService, user_service::UserService};
 
 
 pub fn init_service_setup(db: Arc<DatabaseConnection>) -> AdHoc {
@@ -20,7 +20,17 @@ pub fn init_service_setup(db: Arc<DatabaseConnection>) -> AdHoc {
         let book_repository = BookRepositoryImplSql {
             db_pool: Arc::clone(&db)
         };
-        let user_service = Arc::new(BookService::new(Arc::new(book_repository)));
+        let metadata = metadata_provider(&MetadataConfig::default());
+        let covers = CoverConfig::default();
+        let thumbnailer = Arc::new(CommandThumbnailer::new(&covers.thumbnail_command));
+        let blobs = blob_store(&BlobConfig::default());
+        let user_service = Arc::new(BookService::new(
+            Arc::new(book_repository),
+            metadata,
+            Arc::clone(&blobs),
+            thumbnailer,
+            covers,
+        ));
 
         let author_repository = AuthorRepositoryImplSql {
             db_pool: Arc::clone(&db)
@@ -28,10 +38,76 @@ pub fn init_service_setup(db: Arc<DatabaseConnection>) -> AdHoc {
 
         let author_service = Arc::new(AuthorService::new(Arc::new(author_repository)));
 
+        let user_repository = UserRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let admin_user_service = Arc::new(UserService::new(Arc::new(user_repository)));
+
+        let search_repository = SearchRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let search_service = Arc::new(SearchService::new(Arc::new(search_repository)));
+
+        let trash_repository = TrashRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let trash_service = Arc::new(TrashService::new(Arc::new(trash_repository), blobs));
+
+        let import_repository = ImportRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let import_service = Arc::new(ImportService::new(Arc::new(import_repository)));
+
+        let series_repository = SeriesRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let series_service = Arc::new(SeriesService::new(Arc::new(series_repository)));
+
+        let reading_repository = ReadingRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let reading_service = Arc::new(ReadingService::new(Arc::new(reading_repository)));
+
+        let review_repository = ReviewRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let review_service = Arc::new(ReviewService::new(Arc::new(review_repository)));
+
+        let shelf_repository = ShelfRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let shelf_service = Arc::new(ShelfService::new(Arc::new(shelf_repository)));
+
+        let tag_repository = TagRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let tag_service = Arc::new(TagService::new(Arc::new(tag_repository)));
+
+        let loan_repository = LoanRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let loan_service = Arc::new(LoanService::new(Arc::new(loan_repository)));
+
+        let stats_repository = StatsRepositoryImplSql {
+            db_pool: Arc::clone(&db)
+        };
+        let stats_service = Arc::new(StatsService::new(Arc::new(stats_repository)));
+
         // attach to rocket
         rocket.manage(Arc::clone(&db))
             .manage(auth_service)
             .manage(user_service)
             .manage(author_service)
+            .manage(admin_user_service)
+            .manage(search_service)
+            .manage(trash_service)
+            .manage(import_service)
+            .manage(series_service)
+            .manage(reading_service)
+            .manage(review_service)
+            .manage(shelf_service)
+            .manage(tag_service)
+            .manage(loan_service)
+            .manage(stats_service)
     })
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::loan_repository::LoanRepository, value_object::loan::{ReqLoan, ReqLoanQuery, ReqLoanReturn, ResLoan, ResLoanList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct LoanService<T>
+where
+    T: LoanRepository + Send + Sync,
+{
+    loan_service: Arc<T>
+}
+
+impl<T> LoanService<T>
+where
+    T: LoanRepository + Send + Sync
+{
+    pub fn new(loan_service: Arc<T>) -> Self {
+        Self {
+            loan_service
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser, query: ReqLoanQuery) -> Response<Json<ResLoanList>> {
+        self.loan_service.index(user, query).await
+    }
+
+    pub async fn history(&self, user: AuthenticatedUser, book_id: i32) -> Response<Json<ResLoanList>> {
+        self.loan_service.history(user, book_id).await
+    }
+
+    pub async fn lend(&self, user: AuthenticatedUser, book_id: i32, req_loan: Json<ReqLoan>) -> Response<Json<ResLoan>> {
+        self.loan_service.lend(user, book_id, req_loan).await
+    }
+
+    pub async fn return_loan(&self, user: AuthenticatedUser, id: i32, req_return: Json<ReqLoanReturn>) -> Response<Json<ResLoan>> {
+        self.loan_service.return_loan(user, id, req_return).await
+    }
+}
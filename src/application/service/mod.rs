@@ -1,4 +1,15 @@
 pub mod auth_service;
 pub mod init_service_setup;
 pub mod book_service;
-pub mod author_service;
\ No newline at end of file
+pub mod author_service;
+pub mod user_service;
+pub mod search_service;
+pub mod trash_service;
+pub mod import_service;
+pub mod series_service;
+pub mod reading_service;
+pub mod review_service;
+pub mod shelf_service;
+pub mod tag_service;
+pub mod loan_service;
+pub mod stats_service;
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::reading_repository::ReadingRepository, value_object::reading::{ReqReadingSession, ReqReadingStatus, ResCurrentReading, ResReadingState, ResSessionLogged}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct ReadingService<T>
+where
+    T: ReadingRepository + Send + Sync,
+{
+    reading_service: Arc<T>
+}
+
+impl<T> ReadingService<T>
+where
+    T: ReadingRepository + Send + Sync
+{
+    pub fn new(reading_service: Arc<T>) -> Self {
+        Self {
+            reading_service
+        }
+    }
+
+    pub async fn set_status(&self, user: AuthenticatedUser, book_id: i32, req_status: Json<ReqReadingStatus>) -> Response<Json<ResReadingState>> {
+        self.reading_service.set_status(user, book_id, req_status).await
+    }
+
+    pub async fn log_session(&self, user: AuthenticatedUser, book_id: i32, req_session: Json<ReqReadingSession>) -> Response<Json<ResSessionLogged>> {
+        self.reading_service.log_session(user, book_id, req_session).await
+    }
+
+    pub async fn current(&self, user: AuthenticatedUser) -> Response<Json<ResCurrentReading>> {
+        self.reading_service.current(user).await
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::review_repository::ReviewRepository, value_object::review::{ReqReview, ReqReviewQuery, ResReview, ResReviewList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct ReviewService<T>
+where
+    T: ReviewRepository + Send + Sync,
+{
+    review_service: Arc<T>
+}
+
+impl<T> ReviewService<T>
+where
+    T: ReviewRepository + Send + Sync
+{
+    pub fn new(review_service: Arc<T>) -> Self {
+        Self {
+            review_service
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser, book_id: i32, query: ReqReviewQuery) -> Response<Json<ResReviewList>> {
+        self.review_service.index(user, book_id, query).await
+    }
+
+    pub async fn show(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<Json<ResReview>> {
+        self.review_service.show(user, book_id, id).await
+    }
+
+    pub async fn create(&self, user: AuthenticatedUser, book_id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>> {
+        self.review_service.create(user, book_id, req_review).await
+    }
+
+    pub async fn update(&self, user: AuthenticatedUser, book_id: i32, id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>> {
+        self.review_service.update(user, book_id, id, req_review).await
+    }
+
+    pub async fn delete(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<String> {
+        self.review_service.delete(user, book_id, id).await
+    }
+}
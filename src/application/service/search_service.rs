@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::search_repository::SearchRepository, value_object::search::ResSearchList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+
+pub struct SearchService<T>
+where
+    T: SearchRepository + Send + Sync,
+{
+    search_service: Arc<T>
+}
+
+impl<T> SearchService<T>
+where
+    T: SearchRepository + Send + Sync,
+{
+    pub fn new(search_service: Arc<T>) -> Self {
+        Self {
+            search_service
+        }
+    }
+
+    pub async fn search(&self, user: AuthenticatedUser, q: String, limit: Option<u64>) -> Response<Json<ResSearchList>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        self.search_service.search(user, q, limit).await
+    }
+}
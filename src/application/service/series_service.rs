@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::series_repository::SeriesRepository, value_object::series::{ReqSeries, ReqSeriesPatch, ReqSeriesQuery, ResSeries, ResSeriesBooks, ResSeriesList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct SeriesService<T>
+where
+    T: SeriesRepository + Send + Sync,
+{
+    series_service: Arc<T>
+}
+
+impl<T> SeriesService<T>
+where
+    T: SeriesRepository + Send + Sync
+{
+    pub fn new(series_service: Arc<T>) -> Self {
+        Self {
+            series_service
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser, query: ReqSeriesQuery) -> Response<Json<ResSeriesList>> {
+        self.series_service.index(user, query).await
+    }
+
+    pub async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeries>> {
+        self.series_service.show(user, id).await
+    }
+
+    pub async fn create(&self, user: AuthenticatedUser, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>> {
+        self.series_service.create(user, req_series).await
+    }
+
+    pub async fn update(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>> {
+        self.series_service.update(user, id, req_series).await
+    }
+
+    pub async fn patch(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeriesPatch>) -> Response<Json<ResSeries>> {
+        self.series_service.patch(user, id, req_series).await
+    }
+
+    pub async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        self.series_service.delete(user, id).await
+    }
+
+    pub async fn books(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeriesBooks>> {
+        self.series_service.books(user, id).await
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::shelf_repository::ShelfRepository, value_object::shelf::{ReqShelf, ResShelf, ResShelfList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct ShelfService<T>
+where
+    T: ShelfRepository + Send + Sync,
+{
+    shelf_service: Arc<T>
+}
+
+impl<T> ShelfService<T>
+where
+    T: ShelfRepository + Send + Sync
+{
+    pub fn new(shelf_service: Arc<T>) -> Self {
+        Self {
+            shelf_service
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser) -> Response<Json<ResShelfList>> {
+        self.shelf_service.index(user).await
+    }
+
+    pub async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResShelf>> {
+        self.shelf_service.show(user, id).await
+    }
+
+    pub async fn create(&self, user: AuthenticatedUser, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>> {
+        self.shelf_service.create(user, req_shelf).await
+    }
+
+    pub async fn update(&self, user: AuthenticatedUser, id: i32, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>> {
+        self.shelf_service.update(user, id, req_shelf).await
+    }
+
+    pub async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        self.shelf_service.delete(user, id).await
+    }
+
+    pub async fn add_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String> {
+        self.shelf_service.add_book(user, id, book_id).await
+    }
+
+    pub async fn remove_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String> {
+        self.shelf_service.remove_book(user, id, book_id).await
+    }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::stats_repository::StatsRepository, value_object::stats::{ReqReadingGoal, ReqStatsQuery, ResReadingGoal, ResStats}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct StatsService<T>
+where
+    T: StatsRepository + Send + Sync,
+{
+    stats_service: Arc<T>
+}
+
+impl<T> StatsService<T>
+where
+    T: StatsRepository + Send + Sync
+{
+    pub fn new(stats_service: Arc<T>) -> Self {
+        Self {
+            stats_service
+        }
+    }
+
+    pub async fn stats(&self, user: AuthenticatedUser, query: ReqStatsQuery) -> Response<Json<ResStats>> {
+        self.stats_service.stats(user, query).await
+    }
+
+    pub async fn set_goal(&self, user: AuthenticatedUser, year: i32, req_goal: Json<ReqReadingGoal>) -> Response<Json<ResReadingGoal>> {
+        self.stats_service.set_goal(user, year, req_goal).await
+    }
+
+    pub async fn delete_goal(&self, user: AuthenticatedUser, year: i32) -> Response<String> {
+        self.stats_service.delete_goal(user, year).await
+    }
+}
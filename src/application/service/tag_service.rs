@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::tag_repository::TagRepository, value_object::tag::{ReqBookTags, ReqTag, ReqTagQuery, ResBookTags, ResTag, ResTagList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+pub struct TagService<T>
+where
+    T: TagRepository + Send + Sync,
+{
+    tag_service: Arc<T>
+}
+
+impl<T> TagService<T>
+where
+    T: TagRepository + Send + Sync
+{
+    pub fn new(tag_service: Arc<T>) -> Self {
+        Self {
+            tag_service
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser, query: ReqTagQuery) -> Response<Json<ResTagList>> {
+        self.tag_service.index(user, query).await
+    }
+
+    pub async fn rename(&self, user: AuthenticatedUser, id: i32, req_tag: Json<ReqTag>) -> Response<Json<ResTag>> {
+        self.tag_service.rename(user, id, req_tag).await
+    }
+
+    pub async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        self.tag_service.delete(user, id).await
+    }
+
+    pub async fn set_book_tags(&self, user: AuthenticatedUser, book_id: i32, req_tags: Json<ReqBookTags>) -> Response<Json<ResBookTags>> {
+        self.tag_service.set_book_tags(user, book_id, req_tags).await
+    }
+}
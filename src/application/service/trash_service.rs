@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use rocket::serde::json::Json;
+
+use crate::{config::trash_config::TrashConfig, domain::{repositories::{blob_store::BlobStore, trash_repository::TrashRepository}, value_object::{cover::CoverSize, trash::{PurgeCount, ResTrash}}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::Response}}};
+
+pub struct TrashService<T>
+where
+    T: TrashRepository + Send + Sync,
+{
+    trash_service: Arc<T>,
+    blobs: Arc<dyn BlobStore + Send + Sync>,
+    retention_days: u64,
+}
+
+impl<T> TrashService<T>
+where
+    T: TrashRepository + Send + Sync,
+{
+    pub fn new(trash_service: Arc<T>, blobs: Arc<dyn BlobStore + Send + Sync>) -> Self {
+        Self {
+            trash_service,
+            blobs,
+            retention_days: TrashConfig::default().retention_days,
+        }
+    }
+
+    pub async fn index(&self, user: AuthenticatedUser) -> Response<Json<ResTrash>> {
+        self.trash_service.index(user, self.retention_days).await
+    }
+
+    // remove what has been in the trash longer than the retention period
+    pub async fn purge_expired(&self) -> Result<PurgeCount, AppError> {
+        let before = Utc::now() - Duration::days(self.retention_days as i64);
+        let purged = self.trash_service.purge(before).await?;
+
+        // the rows are gone, a blob that fails to go is only logged
+        for (book_id, cover) in &purged.covers {
+            for size in CoverSize::ALL {
+                if let Err(e) = self.blobs.delete(&cover.key(*book_id, size)).await {
+                    e.log();
+                }
+            }
+        }
+
+        Ok(purged.count)
+    }
+}
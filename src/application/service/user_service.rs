@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use rocket::serde::json::Json;
+
+use crate::{domain::{repositories::user_repository::UserRepository, value_object::user::{ReqRole, ResUser, ResUserList}}, infrastructure::rocket_http::response_type::custom_response::Response};
+
+pub struct UserService<T>
+where
+    T: UserRepository + Send + Sync,
+{
+    user_service: Arc<T>
+}
+
+impl<T> UserService<T>
+where
+    T: UserRepository + Send + Sync,
+{
+    pub fn new(user_service: Arc<T>) -> Self {
+        Self {
+            user_service
+        }
+    }
+
+    pub async fn index(&self) -> Response<Json<ResUserList>> {
+        self.user_service.index().await
+    }
+
+    pub async fn update_role(&self, id: i32, req_role: Json<ReqRole>) -> Response<Json<ResUser>> {
+        self.user_service.update_role(id, req_role).await
+    }
+}
@@ -0,0 +1,28 @@
+pub struct BlobConfig {
+    // `fs` or `s3`
+    pub backend: String,
+    // root of the filesystem backend
+    pub dir: String,
+    // S3 or a compatible server like MinIO, path-style URLs over plain HTTP
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key: String,
+    pub s3_secret_key: String,
+}
+
+impl Default for BlobConfig {
+    fn default() -> Self {
+        let var = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+
+        Self {
+            backend: var("BOOKSTORE_BLOB_BACKEND", "fs"),
+            dir: var("BOOKSTORE_BLOB_DIR", "storage"),
+            s3_endpoint: var("BOOKSTORE_S3_ENDPOINT", "http://localhost:9000"),
+            s3_bucket: var("BOOKSTORE_S3_BUCKET", "bookstore"),
+            s3_region: var("BOOKSTORE_S3_REGION", "us-east-1"),
+            s3_access_key: var("BOOKSTORE_S3_ACCESS_KEY", "minioadmin"),
+            s3_secret_key: var("BOOKSTORE_S3_SECRET_KEY", "minioadmin"),
+        }
+    }
+}
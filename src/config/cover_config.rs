@@ -0,0 +1,19 @@
+pub struct CoverConfig {
+    // largest accepted upload
+    pub max_bytes: u64,
+    // reads the image on stdin and writes the thumbnail in the same format on stdout,
+    // `{size}` is replaced by the longest side in pixels
+    pub thumbnail_command: String,
+    // `Cache-Control` max-age of served covers, in seconds
+    pub max_age: u64,
+}
+
+impl Default for CoverConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: std::env::var("BOOKSTORE_COVER_MAX_BYTES").ok().and_then(|v| v.parse().ok()).unwrap_or(5 * 1024 * 1024),
+            thumbnail_command: std::env::var("BOOKSTORE_THUMBNAIL_COMMAND").unwrap_or_else(|_| "convert - -thumbnail {size}x{size}> -".to_string()),
+            max_age: std::env::var("BOOKSTORE_COVER_MAX_AGE").ok().and_then(|v| v.parse().ok()).unwrap_or(24 * 60 * 60),
+        }
+    }
+}
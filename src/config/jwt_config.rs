@@ -1,11 +1,16 @@
 pub struct JwtSecret {
-    pub jwt_secret: String
+    pub jwt_secret: String,
+    // lifetimes in seconds
+    pub access_token_ttl: u64,
+    pub refresh_token_ttl: u64,
 }
 
 impl Default for JwtSecret {
     fn default() -> Self {
         Self {
-            jwt_secret: std::env::var("BOOKSTORE_JWT_SECRET").unwrap_or("meowmeow".to_string())
+            jwt_secret: std::env::var("BOOKSTORE_JWT_SECRET").unwrap_or("meowmeow".to_string()),
+            access_token_ttl: std::env::var("BOOKSTORE_ACCESS_TOKEN_TTL").ok().and_then(|v| v.parse().ok()).unwrap_or(15 * 60),
+            refresh_token_ttl: std::env::var("BOOKSTORE_REFRESH_TOKEN_TTL").ok().and_then(|v| v.parse().ok()).unwrap_or(30 * 24 * 60 * 60),
         }
     }
-}
\ No newline at end of file
+}
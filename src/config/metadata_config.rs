@@ -0,0 +1,18 @@
+pub struct MetadataConfig {
+    // Open Library or a stand-in serving the same JSON, over HTTP or HTTPS
+    pub base_url: String,
+    // a JSON file answering lookups instead of the HTTP provider
+    pub fixtures: Option<String>,
+    // seconds before a lookup is given up
+    pub timeout: u64,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self {
+            base_url: std::env::var("BOOKSTORE_METADATA_URL").unwrap_or_else(|_| "https://openlibrary.org".to_string()),
+            fixtures: std::env::var("BOOKSTORE_METADATA_FIXTURES").ok(),
+            timeout: std::env::var("BOOKSTORE_METADATA_TIMEOUT").ok().and_then(|v| v.parse().ok()).unwrap_or(5),
+        }
+    }
+}
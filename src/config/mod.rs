@@ -1,2 +1,6 @@
 pub mod db_config;
-pub mod jwt_config;
\ No newline at end of file
+pub mod jwt_config;
+pub mod trash_config;
+pub mod metadata_config;
+pub mod blob_config;
+pub mod cover_config;
\ No newline at end of file
@@ -0,0 +1,15 @@
+pub struct TrashConfig {
+    // trashed books and authors older than this are purged
+    pub retention_days: u64,
+    // seconds between two purges
+    pub purge_interval: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: std::env::var("BOOKSTORE_TRASH_RETENTION_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(30),
+            purge_interval: std::env::var("BOOKSTORE_TRASH_PURGE_INTERVAL").ok().and_then(|v| v.parse().ok()).unwrap_or(60 * 60),
+        }
+    }
+}
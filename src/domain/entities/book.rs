@@ -1,6 +1,8 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, Set};
+
+use crate::domain::value_object::published_on::PublishedOn;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
 #[sea_orm(table_name = "book")]
@@ -10,10 +12,23 @@ pub struct Model {
     pub user_id: i32,
     pub author_id: i32,
     pub title: String,
-    pub year: String,
     pub cover: String,
     pub created_at: Option<DateTimeUtc>,
     pub updated_at: Option<DateTimeUtc>,
+    pub version: i32,
+    pub deleted_at: Option<DateTimeUtc>,
+    pub isbn10: Option<String>,
+    pub isbn13: Option<String>,
+    pub cover_blob: Option<String>,
+    pub cover_type: Option<String>,
+    pub published_year: Option<i32>,
+    pub published_month: Option<u8>,
+    pub published_day: Option<u8>,
+    pub published_precision: Option<String>,
+    pub published_text: Option<String>,
+    pub series_id: Option<i32>,
+    pub series_index: Option<i32>,
+    pub total_pages: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -26,6 +41,14 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Author,
+    #[sea_orm(
+        belongs_to = "super::series::Entity",
+        from = "Column::SeriesId",
+        to = "super::series::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Series,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::UserId",
@@ -42,6 +65,12 @@ impl Related<super::author::Entity> for Entity {
     }
 }
 
+impl Related<super::series::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Series.def()
+    }
+}
+
 impl Related<super::user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::User.def()
@@ -49,3 +78,26 @@ impl Related<super::user::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn published_on(&self) -> Option<PublishedOn> {
+        PublishedOn::from_columns(
+            self.published_year,
+            self.published_month,
+            self.published_day,
+            self.published_precision.as_deref(),
+        )
+    }
+}
+
+impl ActiveModel {
+    // all `published_*` columns are empty when the date is unknown
+    pub fn set_published_on(&mut self, date: Option<PublishedOn>) {
+        self.published_year = Set(date.map(|d| d.year));
+        self.published_month = Set(date.and_then(|d| d.month));
+        self.published_day = Set(date.and_then(|d| d.day));
+        self.published_precision = Set(date.map(|d| d.precision.as_str().to_string()));
+        // the date replaces what the migration could not read
+        self.published_text = Set(None);
+    }
+}
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "book_contributor")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub book_id: i32,
+    pub author_id: i32,
+    pub role: String,
+    pub position: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::author::Entity",
+        from = "Column::AuthorId",
+        to = "super::author::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Author,
+    #[sea_orm(
+        belongs_to = "super::book::Entity",
+        from = "Column::BookId",
+        to = "super::book::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Book,
+}
+
+impl Related<super::author::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Author.def()
+    }
+}
+
+impl Related<super::book::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Book.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "book_shelf")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub shelf_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub book_id: i32,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::book::Entity",
+        from = "Column::BookId",
+        to = "super::book::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Book,
+    #[sea_orm(
+        belongs_to = "super::shelf::Entity",
+        from = "Column::ShelfId",
+        to = "super::shelf::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Shelf,
+}
+
+impl Related<super::book::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Book.def()
+    }
+}
+
+impl Related<super::shelf::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Shelf.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
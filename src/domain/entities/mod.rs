@@ -4,4 +4,16 @@ pub mod prelude;
 
 pub mod author;
 pub mod book;
+pub mod book_contributor;
+pub mod book_shelf;
+pub mod book_tag;
+pub mod loan;
+pub mod reading_goal;
+pub mod reading_session;
+pub mod reading_state;
+pub mod refresh_token;
+pub mod review;
+pub mod series;
+pub mod shelf;
+pub mod tag;
 pub mod user;
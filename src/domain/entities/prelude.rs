@@ -2,4 +2,16 @@
 
 pub use super::author::Entity as Author;
 pub use super::book::Entity as Book;
+pub use super::book_contributor::Entity as BookContributor;
+pub use super::book_shelf::Entity as BookShelf;
+pub use super::book_tag::Entity as BookTag;
+pub use super::loan::Entity as Loan;
+pub use super::reading_goal::Entity as ReadingGoal;
+pub use super::reading_session::Entity as ReadingSession;
+pub use super::reading_state::Entity as ReadingState;
+pub use super::refresh_token::Entity as RefreshToken;
+pub use super::review::Entity as Review;
+pub use super::series::Entity as Series;
+pub use super::shelf::Entity as Shelf;
+pub use super::tag::Entity as Tag;
 pub use super::user::Entity as User;
@@ -12,6 +12,7 @@ pub struct Model {
     pub password: String,
     pub firstname: Option<String>,
     pub lastname: Option<String>,
+    pub role: String,
     pub created_at: Option<DateTimeUtc>,
     pub updated_at: Option<DateTimeUtc>,
 }
@@ -22,6 +23,8 @@ pub enum Relation {
     Author,
     #[sea_orm(has_many = "super::book::Entity")]
     Book,
+    #[sea_orm(has_many = "super::refresh_token::Entity")]
+    RefreshToken,
 }
 
 impl Related<super::author::Entity> for Entity {
@@ -36,4 +39,10 @@ impl Related<super::book::Entity> for Entity {
     }
 }
 
+impl Related<super::refresh_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RefreshToken.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_user_table::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RefreshToken::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RefreshToken::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RefreshToken::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-refresh_token-user_id")
+                            .from(RefreshToken::Table, RefreshToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(RefreshToken::FamilyId).string_len(64).not_null())
+                    .col(
+                        ColumnDef::new(RefreshToken::TokenHash)
+                            .string_len(64)
+                            .unique_key()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(RefreshToken::ExpiresAt).timestamp().not_null())
+                    .col(ColumnDef::new(RefreshToken::UsedAt).timestamp().null())
+                    .col(ColumnDef::new(RefreshToken::RevokedAt).timestamp().null())
+                    .col(
+                        ColumnDef::new(RefreshToken::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-refresh_token-family_id")
+                            .col(RefreshToken::FamilyId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RefreshToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum RefreshToken {
+    Table,
+    Id,
+    UserId,
+    FamilyId,
+    TokenHash,
+    ExpiresAt,
+    UsedAt,
+    RevokedAt,
+    CreatedAt,
+}
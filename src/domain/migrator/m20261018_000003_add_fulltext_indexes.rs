@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20241207_151941_create_author_table::Author,
+    m20241207_152032_create_book_table::Book,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("ft-book-title")
+                    .table(Book::Table)
+                    .col(Book::Title)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await?;
+
+        // one index over all three columns so they can be matched together
+        manager
+            .create_index(
+                Index::create()
+                    .name("ft-author-firstname-lastname-bio")
+                    .table(Author::Table)
+                    .col(Author::Firstname)
+                    .col(Author::Lastname)
+                    .col(Author::Bio)
+                    .full_text()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("ft-author-firstname-lastname-bio")
+                    .table(Author::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("ft-book-title").table(Book::Table).to_owned())
+            .await
+    }
+}
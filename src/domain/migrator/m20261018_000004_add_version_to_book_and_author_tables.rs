@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // bumped on every write, the ETag of an author or a book
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Author::Table)
+                    .add_column(
+                        ColumnDef::new(Author::Version)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(
+                        ColumnDef::new(Book::Version)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::Version)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Author::Table)
+                    .drop_column(Author::Version)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Author {
+    Table,
+    Version,
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    Version,
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // set when moved to the trash, purged after the retention period
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Author::Table)
+                    .add_column(ColumnDef::new(Author::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-author-deleted_at")
+                    .table(Author::Table)
+                    .col(Author::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-book-deleted_at")
+                    .table(Book::Table)
+                    .col(Book::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx-book-deleted_at").table(Book::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx-author-deleted_at").table(Author::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Author::Table)
+                    .drop_column(Author::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Author {
+    Table,
+    DeletedAt,
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    DeletedAt,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // both forms are stored normalized, the 10 digit one only exists for 978 ISBNs
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::Isbn10).string_len(10).null())
+                    .add_column(ColumnDef::new(Book::Isbn13).string_len(13).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // an edition appears once in a user's library, NULLs do not collide
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-book-user_id-isbn10")
+                    .table(Book::Table)
+                    .col(Book::UserId)
+                    .col(Book::Isbn10)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-book-user_id-isbn13")
+                    .table(Book::Table)
+                    .col(Book::UserId)
+                    .col(Book::Isbn13)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx-book-user_id-isbn13").table(Book::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx-book-user_id-isbn10").table(Book::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::Isbn10)
+                    .drop_column(Book::Isbn13)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    UserId,
+    Isbn10,
+    Isbn13,
+}
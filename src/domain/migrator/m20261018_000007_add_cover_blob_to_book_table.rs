@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // the uploaded cover, named by the SHA-256 of the original image
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::CoverBlob).string_len(64).null())
+                    .add_column(ColumnDef::new(Book::CoverType).string_len(32).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::CoverBlob)
+                    .drop_column(Book::CoverType)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    CoverBlob,
+    CoverType,
+}
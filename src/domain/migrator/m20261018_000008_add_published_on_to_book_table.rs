@@ -0,0 +1,202 @@
+use chrono::NaiveDate;
+use sea_orm_migration::{prelude::*, sea_orm::ConnectionTrait};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::PublishedYear).integer().null())
+                    .add_column(ColumnDef::new(Book::PublishedMonth).tiny_unsigned().null())
+                    .add_column(ColumnDef::new(Book::PublishedDay).tiny_unsigned().null())
+                    .add_column(ColumnDef::new(Book::PublishedPrecision).string_len(8).null())
+                    .add_column(ColumnDef::new(Book::PublishedText).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // the free text years are parsed once, what is not a date is kept as it was in `published_text`
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let rows = db
+            .query_all(backend.build(Query::select().columns([Book::Id, Book::Year]).from(Book::Table)))
+            .await?;
+
+        let mut unparseable = 0;
+        for row in rows {
+            let id: i32 = row.try_get("", "id")?;
+            let year: String = row.try_get("", "year")?;
+            if year.trim().is_empty() {
+                continue;
+            }
+
+            let values = match parse(&year) {
+                Some((year, month, day, precision)) => vec![
+                    (Book::PublishedYear, year.into()),
+                    (Book::PublishedMonth, month.into()),
+                    (Book::PublishedDay, day.into()),
+                    (Book::PublishedPrecision, precision.into()),
+                ],
+                None => {
+                    tracing::warn!("book {}: year {:?} is not a date, kept in `published_text`", id, year);
+                    unparseable += 1;
+                    vec![(Book::PublishedText, year.into())]
+                }
+            };
+
+            db.execute(backend.build(Query::update().table(Book::Table).values(values).and_where(Expr::col(Book::Id).eq(id))))
+                .await?;
+        }
+
+        if unparseable > 0 {
+            tracing::warn!("{} book years could not be read as a date", unparseable);
+        }
+
+        manager
+            .alter_table(Table::alter().table(Book::Table).drop_column(Book::Year).to_owned())
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::Year).string().not_null().default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        // written back in the text form `parse` reads, or as the text that could not be parsed
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let rows = db
+            .query_all(
+                backend.build(
+                    Query::select()
+                        .columns([
+                            Book::Id,
+                            Book::PublishedYear,
+                            Book::PublishedMonth,
+                            Book::PublishedDay,
+                            Book::PublishedPrecision,
+                            Book::PublishedText,
+                        ])
+                        .from(Book::Table)
+                        .cond_where(
+                            Cond::any()
+                                .add(Expr::col(Book::PublishedYear).is_not_null())
+                                .add(Expr::col(Book::PublishedText).is_not_null()),
+                        ),
+                ),
+            )
+            .await?;
+
+        for row in rows {
+            let id: i32 = row.try_get("", "id")?;
+            let year: Option<i32> = row.try_get("", "published_year")?;
+            let month: Option<u8> = row.try_get("", "published_month")?;
+            let day: Option<u8> = row.try_get("", "published_day")?;
+            let precision: Option<String> = row.try_get("", "published_precision")?;
+            let text: Option<String> = row.try_get("", "published_text")?;
+
+            let year = match (year, month, day, precision.as_deref()) {
+                (Some(year), _, _, Some("circa")) => format!("c. {}", year),
+                (Some(year), Some(month), Some(day), _) => format!("{:04}-{:02}-{:02}", year, month, day),
+                (Some(year), Some(month), None, _) => format!("{:04}-{:02}", year, month),
+                (Some(year), _, _, _) => year.to_string(),
+                (None, _, _, _) => text.unwrap_or_default(),
+            };
+
+            db.execute(
+                backend.build(
+                    Query::update()
+                        .table(Book::Table)
+                        .value(Book::Year, year)
+                        .and_where(Expr::col(Book::Id).eq(id)),
+                ),
+            )
+            .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::PublishedYear)
+                    .drop_column(Book::PublishedMonth)
+                    .drop_column(Book::PublishedDay)
+                    .drop_column(Book::PublishedPrecision)
+                    .drop_column(Book::PublishedText)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+// the latest year the parser takes, fixed so the migration reads the same data the same way whenever it runs
+const LAST_YEAR: i32 = 2027;
+
+// `PublishedOn::parse` as it was when this migration was written: "2019", "2019-05", "2019-05-12" or "c. 1900",
+// as year, month, day and precision, `None` when it is not a date
+fn parse(value: &str) -> Option<(i32, Option<u8>, Option<u8>, &'static str)> {
+    let value = value.trim().to_lowercase();
+
+    let (value, circa) = match ["circa", "ca.", "ca", "c.", "c", "~"].iter().find_map(|p| value.strip_prefix(p)) {
+        Some(rest) => (rest.trim(), true),
+        None => match value.strip_suffix('?') {
+            Some(rest) => (rest.trim(), true),
+            None => (value.as_str(), false),
+        },
+    };
+
+    let parts: Vec<&str> = value.split(['-', '/', '.']).collect();
+    if parts.iter().any(|p| p.is_empty() || p.len() > 4 || !p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    if parts.len() > 3 || (circa && parts.len() > 1) {
+        return None;
+    }
+
+    let part = |index: usize| match parts.get(index) {
+        Some(part) => part.parse::<u8>().ok().map(Some),
+        None => Some(None),
+    };
+    let year: i32 = parts[0].parse().ok()?;
+    let (month, day) = (part(1)?, part(2)?);
+
+    if !(1..=LAST_YEAR).contains(&year) || month.is_some_and(|m| !(1..=12).contains(&m)) {
+        return None;
+    }
+
+    let precision = match (month, day) {
+        (Some(month), Some(day)) => {
+            NaiveDate::from_ymd_opt(year, month.into(), day.into())?;
+            "day"
+        }
+        (None, Some(_)) => return None,
+        (Some(_), None) => "month",
+        (None, None) if circa => "circa",
+        (None, None) => "year",
+    };
+
+    Some((year, month, day, precision))
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    Id,
+    Year,
+    PublishedYear,
+    PublishedMonth,
+    PublishedDay,
+    PublishedPrecision,
+    PublishedText,
+}
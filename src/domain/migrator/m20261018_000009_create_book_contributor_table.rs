@@ -0,0 +1,99 @@
+use sea_orm_migration::{prelude::*, sea_orm::ConnectionTrait};
+
+use super::{m20241207_151941_create_author_table::Author, m20241207_152032_create_book_table::Book};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookContributor::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BookContributor::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BookContributor::BookId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_contributor-book_id")
+                            .from(BookContributor::Table, BookContributor::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(BookContributor::AuthorId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_contributor-author_id")
+                            .from(BookContributor::Table, BookContributor::AuthorId)
+                            .to(Author::Table, Author::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(BookContributor::Role).string_len(16).not_null())
+                    .col(ColumnDef::new(BookContributor::Position).integer().not_null())
+                    // an author takes a role once per book
+                    .index(
+                        Index::create()
+                            .name("idx-book_contributor-book_author_role")
+                            .col(BookContributor::BookId)
+                            .col(BookContributor::AuthorId)
+                            .col(BookContributor::Role)
+                            .unique(),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-book_contributor-author_id")
+                            .col(BookContributor::AuthorId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // every book starts with its author, `book.author_id` stays the primary author
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        db.execute(
+            backend.build(
+                Query::insert()
+                    .into_table(BookContributor::Table)
+                    .columns([BookContributor::BookId, BookContributor::AuthorId, BookContributor::Role, BookContributor::Position])
+                    .select_from(
+                        Query::select()
+                            .column(Book::Id)
+                            .column(Book::AuthorId)
+                            .expr(Expr::val("author"))
+                            .expr(Expr::val(0))
+                            .from(Book::Table)
+                            .to_owned(),
+                    )
+                    .map_err(|e| DbErr::Migration(e.to_string()))?,
+            ),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BookContributor::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum BookContributor {
+    Table,
+    Id,
+    BookId,
+    AuthorId,
+    Role,
+    Position,
+}
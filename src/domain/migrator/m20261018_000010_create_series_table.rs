@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_user_table::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Series::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Series::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Series::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-series-user_id")
+                            .from(Series::Table, Series::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Series::Title).string().not_null())
+                    .col(ColumnDef::new(Series::Description).text().not_null())
+                    .col(
+                        ColumnDef::new(Series::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(Series::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // a book leaves its series when the series is deleted
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::SeriesId).integer().null())
+                    .add_column(ColumnDef::new(Book::SeriesIndex).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-book-series_id")
+                            .from_tbl(Book::Table)
+                            .from_col(Book::SeriesId)
+                            .to_tbl(Series::Table)
+                            .to_col(Series::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-book-series_id-series_index")
+                    .table(Book::Table)
+                    .col(Book::SeriesId)
+                    .col(Book::SeriesIndex)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_foreign_key(Alias::new("fk-book-series_id"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx-book-series_id-series_index").table(Book::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .drop_column(Book::SeriesId)
+                    .drop_column(Book::SeriesIndex)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Series::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Series {
+    Table,
+    Id,
+    UserId,
+    Title,
+    Description,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    SeriesId,
+    SeriesIndex,
+}
@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_user_table::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Book::Table)
+                    .add_column(ColumnDef::new(Book::TotalPages).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // one state per book, the book already belongs to a single user
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReadingState::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReadingState::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReadingState::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reading_state-user_id")
+                            .from(ReadingState::Table, ReadingState::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(ReadingState::BookId).integer().not_null().unique_key())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reading_state-book_id")
+                            .from(ReadingState::Table, ReadingState::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(ReadingState::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(ReadingState::CurrentPage).integer().null())
+                    .col(ColumnDef::new(ReadingState::Percent).tiny_unsigned().null())
+                    .col(ColumnDef::new(ReadingState::StartedAt).timestamp().null())
+                    .col(ColumnDef::new(ReadingState::FinishedAt).timestamp().null())
+                    .col(
+                        ColumnDef::new(ReadingState::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-reading_state-user_id-status")
+                            .col(ReadingState::UserId)
+                            .col(ReadingState::Status),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReadingSession::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReadingSession::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReadingSession::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reading_session-user_id")
+                            .from(ReadingSession::Table, ReadingSession::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(ReadingSession::BookId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reading_session-book_id")
+                            .from(ReadingSession::Table, ReadingSession::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(ReadingSession::StartedAt).timestamp().not_null())
+                    .col(ColumnDef::new(ReadingSession::EndedAt).timestamp().not_null())
+                    .col(ColumnDef::new(ReadingSession::StartPage).integer().null())
+                    .col(ColumnDef::new(ReadingSession::EndPage).integer().null())
+                    .col(
+                        ColumnDef::new(ReadingSession::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-reading_session-book_id-started_at")
+                            .col(ReadingSession::BookId)
+                            .col(ReadingSession::StartedAt),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReadingSession::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ReadingState::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(Table::alter().table(Book::Table).drop_column(Book::TotalPages).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Book {
+    Table,
+    Id,
+    TotalPages,
+}
+
+#[derive(Iden)]
+pub enum ReadingState {
+    Table,
+    Id,
+    UserId,
+    BookId,
+    Status,
+    CurrentPage,
+    Percent,
+    StartedAt,
+    FinishedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum ReadingSession {
+    Table,
+    Id,
+    UserId,
+    BookId,
+    StartedAt,
+    EndedAt,
+    StartPage,
+    EndPage,
+    CreatedAt,
+}
@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20220101_000001_create_user_table::User, m20241207_152032_create_book_table::Book};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Review::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Review::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Review::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-review-user_id")
+                            .from(Review::Table, Review::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Review::BookId).integer().not_null())
+                    // reviews go with their book
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-review-book_id")
+                            .from(Review::Table, Review::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // the rating in half stars, 2 to 10
+                    .col(ColumnDef::new(Review::HalfStars).tiny_unsigned().not_null())
+                    .col(ColumnDef::new(Review::Text).text().not_null())
+                    .col(ColumnDef::new(Review::Spoiler).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Review::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(Review::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    // one review per user and book
+                    .index(
+                        Index::create()
+                            .name("idx-review-book_id-user_id")
+                            .col(Review::BookId)
+                            .col(Review::UserId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Review::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Review {
+    Table,
+    Id,
+    UserId,
+    BookId,
+    HalfStars,
+    Text,
+    Spoiler,
+    CreatedAt,
+    UpdatedAt,
+}
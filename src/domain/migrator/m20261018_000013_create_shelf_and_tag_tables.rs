@@ -0,0 +1,201 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20220101_000001_create_user_table::User, m20241207_152032_create_book_table::Book};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Shelf::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Shelf::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Shelf::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-shelf-user_id")
+                            .from(Shelf::Table, Shelf::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Shelf::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Shelf::Description).text().null())
+                    // where the shelf sits among the user's shelves
+                    .col(ColumnDef::new(Shelf::Position).integer().not_null().default(0))
+                    .col(
+                        ColumnDef::new(Shelf::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(Shelf::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-shelf-user_id-name")
+                            .col(Shelf::UserId)
+                            .col(Shelf::Name)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookShelf::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(BookShelf::ShelfId).integer().not_null())
+                    .col(ColumnDef::new(BookShelf::BookId).integer().not_null())
+                    .primary_key(Index::create().col(BookShelf::ShelfId).col(BookShelf::BookId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_shelf-shelf_id")
+                            .from(BookShelf::Table, BookShelf::ShelfId)
+                            .to(Shelf::Table, Shelf::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_shelf-book_id")
+                            .from(BookShelf::Table, BookShelf::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(
+                        ColumnDef::new(BookShelf::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tag::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tag::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-tag-user_id")
+                            .from(Tag::Table, Tag::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Tag::Name).string_len(50).not_null())
+                    // also serves the autocomplete on name prefixes
+                    .index(
+                        Index::create()
+                            .name("idx-tag-user_id-name")
+                            .col(Tag::UserId)
+                            .col(Tag::Name)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookTag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(BookTag::BookId).integer().not_null())
+                    .col(ColumnDef::new(BookTag::TagId).integer().not_null())
+                    .primary_key(Index::create().col(BookTag::BookId).col(BookTag::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_tag-book_id")
+                            .from(BookTag::Table, BookTag::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-book_tag-tag_id")
+                            .from(BookTag::Table, BookTag::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-book_tag-tag_id")
+                            .col(BookTag::TagId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BookTag::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tag::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(BookShelf::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Shelf::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Shelf {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Description,
+    Position,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum BookShelf {
+    Table,
+    ShelfId,
+    BookId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum Tag {
+    Table,
+    Id,
+    UserId,
+    Name,
+}
+
+#[derive(Iden)]
+pub enum BookTag {
+    Table,
+    BookId,
+    TagId,
+}
@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20220101_000001_create_user_table::User, m20241207_152032_create_book_table::Book};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Loan::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Loan::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Loan::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-loan-user_id")
+                            .from(Loan::Table, Loan::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(Loan::BookId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-loan-book_id")
+                            .from(Loan::Table, Loan::BookId)
+                            .to(Book::Table, Book::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    // lent to the person, or borrowed from them
+                    .col(ColumnDef::new(Loan::Direction).string_len(16).not_null().default("lent"))
+                    .col(ColumnDef::new(Loan::Person).string().not_null())
+                    .col(ColumnDef::new(Loan::Contact).string().null())
+                    .col(ColumnDef::new(Loan::LentOn).date().not_null())
+                    .col(ColumnDef::new(Loan::DueOn).date().null())
+                    // a loan is out until it has a return date
+                    .col(ColumnDef::new(Loan::ReturnedOn).date().null())
+                    .col(
+                        ColumnDef::new(Loan::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(Loan::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-loan-book_id-lent_on")
+                            .col(Loan::BookId)
+                            .col(Loan::LentOn),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-loan-user_id-returned_on-due_on")
+                            .col(Loan::UserId)
+                            .col(Loan::ReturnedOn)
+                            .col(Loan::DueOn),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Loan::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum Loan {
+    Table,
+    Id,
+    UserId,
+    BookId,
+    Direction,
+    Person,
+    Contact,
+    LentOn,
+    DueOn,
+    ReturnedOn,
+    CreatedAt,
+    UpdatedAt,
+}
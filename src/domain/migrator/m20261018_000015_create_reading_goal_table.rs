@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_user_table::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReadingGoal::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReadingGoal::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReadingGoal::UserId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reading_goal-user_id")
+                            .from(ReadingGoal::Table, ReadingGoal::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .col(ColumnDef::new(ReadingGoal::Year).integer().not_null())
+                    // books to finish within the year, and optionally pages to read
+                    .col(ColumnDef::new(ReadingGoal::TargetBooks).integer().not_null())
+                    .col(ColumnDef::new(ReadingGoal::TargetPages).integer().null())
+                    .col(
+                        ColumnDef::new(ReadingGoal::CreatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    .col(
+                        ColumnDef::new(ReadingGoal::UpdatedAt)
+                            .timestamp()
+                            .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+                    )
+                    // one goal per user and year
+                    .index(
+                        Index::create()
+                            .name("idx-reading_goal-user_id-year")
+                            .col(ReadingGoal::UserId)
+                            .col(ReadingGoal::Year)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReadingGoal::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+pub enum ReadingGoal {
+    Table,
+    Id,
+    UserId,
+    Year,
+    TargetBooks,
+    TargetPages,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -1,8 +1,26 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_user_table;
+// the headers of these two are written as doc comments
+#[allow(clippy::empty_line_after_doc_comments)]
 mod m20241207_151941_create_author_table;
+#[allow(clippy::empty_line_after_doc_comments)]
 mod m20241207_152032_create_book_table;
+mod m20261018_000001_add_role_to_user_table;
+mod m20261018_000002_create_refresh_token_table;
+mod m20261018_000003_add_fulltext_indexes;
+mod m20261018_000004_add_version_to_book_and_author_tables;
+mod m20261018_000005_add_deleted_at_to_book_and_author_tables;
+mod m20261018_000006_add_isbn_to_book_table;
+mod m20261018_000007_add_cover_blob_to_book_table;
+mod m20261018_000008_add_published_on_to_book_table;
+mod m20261018_000009_create_book_contributor_table;
+mod m20261018_000010_create_series_table;
+mod m20261018_000011_create_reading_tables;
+mod m20261018_000012_create_review_table;
+mod m20261018_000013_create_shelf_and_tag_tables;
+mod m20261018_000014_create_loan_table;
+mod m20261018_000015_create_reading_goal_table;
 
 pub struct Migrator;
 
@@ -13,6 +31,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000001_create_user_table::Migration),
             Box::new(m20241207_151941_create_author_table::Migration),
             Box::new(m20241207_152032_create_book_table::Migration),
+            Box::new(m20261018_000001_add_role_to_user_table::Migration),
+            Box::new(m20261018_000002_create_refresh_token_table::Migration),
+            Box::new(m20261018_000003_add_fulltext_indexes::Migration),
+            Box::new(m20261018_000004_add_version_to_book_and_author_tables::Migration),
+            Box::new(m20261018_000005_add_deleted_at_to_book_and_author_tables::Migration),
+            Box::new(m20261018_000006_add_isbn_to_book_table::Migration),
+            Box::new(m20261018_000007_add_cover_blob_to_book_table::Migration),
+            Box::new(m20261018_000008_add_published_on_to_book_table::Migration),
+            Box::new(m20261018_000009_create_book_contributor_table::Migration),
+            Box::new(m20261018_000010_create_series_table::Migration),
+            Box::new(m20261018_000011_create_reading_tables::Migration),
+            Box::new(m20261018_000012_create_review_table::Migration),
+            Box::new(m20261018_000013_create_shelf_and_tag_tables::Migration),
+            Box::new(m20261018_000014_create_loan_table::Migration),
+            Box::new(m20261018_000015_create_reading_goal_table::Migration),
         ]
     }
 }
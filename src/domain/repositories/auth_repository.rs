@@ -1,7 +1,7 @@
 use rocket::async_trait;
 use rocket::serde::json::Json;
 
-use crate::domain::value_object::auth::{ReqSignIn, ReqSignUp, ResMe, ResSignIn};
+use crate::domain::value_object::auth::{ReqMePatch, ReqRefresh, ReqSignIn, ReqSignUp, ResMe, ResSignIn};
 use crate::infrastructure::rocket_http::middleware::jwt_auth::AuthenticatedUser;
 use crate::infrastructure::rocket_http::response_type::custom_response::Response;
 #[async_trait]
@@ -11,4 +11,13 @@ pub trait AuthRepository: Send + Sync {
     async fn sign_in(&self, sign_in_data: Json<ReqSignIn>) -> Response<Json<ResSignIn>>;
 
     async fn me(&self, user: AuthenticatedUser) -> Response<Json<ResMe>>;
+
+    // update the caller's own profile
+    async fn update_me(&self, user: AuthenticatedUser, profile: Json<ReqMePatch>) -> Response<Json<ResMe>>;
+
+    // exchange a refresh token for a new token pair, rotating the refresh token
+    async fn refresh(&self, refresh_data: Json<ReqRefresh>) -> Response<Json<ResSignIn>>;
+
+    // revoke the session the refresh token belongs to
+    async fn sign_out(&self, refresh_data: Json<ReqRefresh>) -> Response<String>;
 }
\ No newline at end of file
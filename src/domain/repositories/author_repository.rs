@@ -1,18 +1,26 @@
 use rocket::{async_trait, serde::json::Json};
 
-use crate::{domain::value_object::{author::{ReqAuthor, ResAuthor, ResAuthorList}, book::ResBookList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+use crate::{domain::value_object::{author::{ReqAuthor, ReqAuthorDelete, ReqAuthorPatch, ReqAuthorQuery, ResAuthor, ResAuthorList}, book::ResBookList}, infrastructure::rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::custom_response::{Response, Tagged}}};
 
 #[async_trait]
 pub trait AuthorRepository {
     // get all author
-    async fn index(&self,_user: AuthenticatedUser) -> Response<Json<ResAuthorList>>;
+    async fn index(&self,_user: AuthenticatedUser, query: ReqAuthorQuery) -> Response<Json<ResAuthorList>>;
 
-    // et athor by id
-    async fn show(&self,_user: AuthenticatedUser, id: i32) -> Response<Json<ResAuthor>>;
+    // et athor by id, 304 when `If-None-Match` lists the current ETag
+    async fn show(&self,_user: AuthenticatedUser, id: i32, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>>;
 
-    async fn update(&self,_user: AuthenticatedUser, _id: i32, _req_author: Json<ReqAuthor>) -> Response<Json<ResAuthor>>;
+    // writes fail with 412 when `If-Match` does not list the current ETag
+    async fn update(&self,_user: AuthenticatedUser, _id: i32, _req_author: Json<ReqAuthor>, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>>;
 
-    async fn delete(&self,_user: AuthenticatedUser, _id: i32) -> Response<String>;
+    // update only the fields present in the request
+    async fn patch(&self, user: AuthenticatedUser, id: i32, req_author: Json<ReqAuthorPatch>, preconditions: Preconditions) -> Response<Tagged<Json<ResAuthor>>>;
+
+    // moves the author to the trash, its books are handled as `query.on_books` says
+    async fn delete(&self,_user: AuthenticatedUser, _id: i32, query: ReqAuthorDelete, preconditions: Preconditions) -> Response<String>;
+
+    // take an author back out of the trash, with the books trashed along with it
+    async fn restore(&self, user: AuthenticatedUser, id: i32) -> Response<Tagged<Json<ResAuthor>>>;
 
     async fn create(&self, _user: AuthenticatedUser, _req_author: Json<ReqAuthor>) -> Response<Json<ResAuthor>>;
 
@@ -0,0 +1,15 @@
+use rocket::async_trait;
+
+use crate::{domain::value_object::cover::Blob, infrastructure::rocket_http::response_type::app_error::AppError};
+
+// where uploaded files live, keys are `/` separated paths
+#[async_trait]
+pub trait BlobStore {
+    async fn put(&self, key: &str, blob: Blob) -> Result<(), AppError>;
+
+    // `None` when nothing is stored under `key`
+    async fn get(&self, key: &str) -> Result<Option<Blob>, AppError>;
+
+    // deleting a missing key is not an error
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
@@ -1,19 +1,52 @@
 
 
-use rocket::{async_trait, serde::json::Json};
+use rocket::{async_trait, futures::stream::BoxStream, serde::json::Json};
 
-use crate::{domain::value_object::book::{ReqBook, ResBook, ResBookList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+use crate::{domain::value_object::{batch::ResBatch, book::{ReqBook, ReqBookBatch, ReqBookPatch, ReqBookQuery, ReqCatalogQuery, ResBook, ResBookList}, cover::CoverRef, export::ResExportRow, metadata::{BookMetadata, ReqBookDraft}}, infrastructure::rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::{app_error::AppError, custom_response::{Response, Tagged}}}};
 
 #[async_trait]
 pub trait BookRepository {
     // get all book
-    async fn index(&self,_user: AuthenticatedUser) -> Response<Json<ResBookList>>;
+    async fn index(&self,_user: AuthenticatedUser, query: ReqBookQuery) -> Response<Json<ResBookList>>;
 
-    async fn show(&self,_user: AuthenticatedUser, id: i32) -> Response<Json<ResBook>>;
+    // 304 when `If-None-Match` lists the current ETag
+    async fn show(&self,_user: AuthenticatedUser, id: i32, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>;
 
-    async fn update(&self,_user: AuthenticatedUser, _id: i32, _req_book: Json<ReqBook>) -> Response<Json<ResBook>>;
+    // the user's live book with this ISBN, in either form
+    async fn by_isbn(&self, user: AuthenticatedUser, isbn: String) -> Response<Tagged<Json<ResBook>>>;
 
-    async fn delete(&self,_user: AuthenticatedUser, _id: i32) -> Response<String>;
+    // writes fail with 412 when `If-Match` does not list the current ETag
+    async fn update(&self,_user: AuthenticatedUser, _id: i32, _req_book: Json<ReqBook>, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>;
+
+    // update only the fields present in the request
+    async fn patch(&self, user: AuthenticatedUser, id: i32, req_book: Json<ReqBookPatch>, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>;
+
+    // moves the book to the trash
+    async fn delete(&self,_user: AuthenticatedUser, _id: i32, preconditions: Preconditions) -> Response<String>;
+
+    // take a book back out of the trash
+    async fn restore(&self, user: AuthenticatedUser, id: i32) -> Response<Tagged<Json<ResBook>>>;
+
+    // the stored cover of the user's book, if any
+    async fn cover(&self, user: AuthenticatedUser, id: i32) -> Result<Option<CoverRef>, AppError>;
+
+    // point the book at an uploaded cover, checked against `If-Match`
+    async fn set_cover(&self, user: AuthenticatedUser, id: i32, cover: CoverRef, preconditions: Preconditions) -> Response<Tagged<Json<ResBook>>>;
 
     async fn create(&self, _user: AuthenticatedUser, _req_book: Json<ReqBook>) -> Response<Json<ResBook>>;
+
+    // the draft completed with the provider's metadata, creating its author when needed
+    async fn create_enriched(&self, user: AuthenticatedUser, draft: ReqBookDraft, metadata: BookMetadata) -> Response<Json<ResBook>>;
+
+    // several operations in one request, atomic or best-effort
+    async fn batch(&self, user: AuthenticatedUser, req_batch: Json<ReqBookBatch>) -> Response<Json<ResBatch>>;
+
+    // the user's live books with their authors, read lazily
+    fn export(&self, user: AuthenticatedUser) -> BoxStream<'static, Result<ResExportRow, AppError>>;
+
+    // get every user's books, for admins
+    async fn catalog(&self, query: ReqCatalogQuery) -> Response<Json<ResBookList>>;
+
+    // delete any user's book, for moderators
+    async fn moderate_delete(&self, id: i32) -> Response<String>;
 }
\ No newline at end of file
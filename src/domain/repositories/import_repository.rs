@@ -0,0 +1,10 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::import::{ImportRow, ResImport}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait ImportRepository {
+    // add the rows as books of the user, creating missing authors,
+    // without writing anything on a dry run
+    async fn import(&self, user: AuthenticatedUser, rows: Vec<ImportRow>, dry_run: bool) -> Response<Json<ResImport>>;
+}
@@ -0,0 +1,17 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::loan::{ReqLoan, ReqLoanQuery, ReqLoanReturn, ResLoan, ResLoanList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait LoanRepository {
+    // the loans of the user's books outside the trash
+    async fn index(&self, user: AuthenticatedUser, query: ReqLoanQuery) -> Response<Json<ResLoanList>>;
+
+    // every loan of a book, the latest first
+    async fn history(&self, user: AuthenticatedUser, book_id: i32) -> Response<Json<ResLoanList>>;
+
+    // a book that is already out cannot be lent again
+    async fn lend(&self, user: AuthenticatedUser, book_id: i32, req_loan: Json<ReqLoan>) -> Response<Json<ResLoan>>;
+
+    async fn return_loan(&self, user: AuthenticatedUser, id: i32, req_return: Json<ReqLoanReturn>) -> Response<Json<ResLoan>>;
+}
@@ -0,0 +1,10 @@
+use rocket::async_trait;
+
+use crate::{domain::value_object::{isbn::Isbn, metadata::BookMetadata}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+// where book details come from when only the ISBN is known
+#[async_trait]
+pub trait MetadataProvider {
+    // `None` when the provider has no record of the edition
+    async fn lookup(&self, isbn: &Isbn) -> Result<Option<BookMetadata>, AppError>;
+}
@@ -0,0 +1,15 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::reading::{ReqReadingSession, ReqReadingStatus, ResCurrentReading, ResReadingState, ResSessionLogged}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait ReadingRepository {
+    // the started and finished dates follow from the change of status
+    async fn set_status(&self, user: AuthenticatedUser, book_id: i32, req_status: Json<ReqReadingStatus>) -> Response<Json<ResReadingState>>;
+
+    // a session moves the book to reading, or to finished on its last page
+    async fn log_session(&self, user: AuthenticatedUser, book_id: i32, req_session: Json<ReqReadingSession>) -> Response<Json<ResSessionLogged>>;
+
+    // the books being read, the most recently touched first
+    async fn current(&self, user: AuthenticatedUser) -> Response<Json<ResCurrentReading>>;
+}
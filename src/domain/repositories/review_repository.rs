@@ -0,0 +1,18 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::review::{ReqReview, ReqReviewQuery, ResReview, ResReviewList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait ReviewRepository {
+    // the reviews of a book with its rating, hidden while the book is in the trash
+    async fn index(&self, user: AuthenticatedUser, book_id: i32, query: ReqReviewQuery) -> Response<Json<ResReviewList>>;
+
+    async fn show(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<Json<ResReview>>;
+
+    // a user reviews a book once
+    async fn create(&self, user: AuthenticatedUser, book_id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>>;
+
+    async fn update(&self, user: AuthenticatedUser, book_id: i32, id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>>;
+
+    async fn delete(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<String>;
+}
@@ -0,0 +1,9 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::search::ResSearchList, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait SearchRepository {
+    // ranked books and authors of the user matching `q`
+    async fn search(&self, user: AuthenticatedUser, q: String, limit: u64) -> Response<Json<ResSearchList>>;
+}
@@ -0,0 +1,23 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::series::{ReqSeries, ReqSeriesPatch, ReqSeriesQuery, ResSeries, ResSeriesBooks, ResSeriesList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait SeriesRepository {
+    async fn index(&self, user: AuthenticatedUser, query: ReqSeriesQuery) -> Response<Json<ResSeriesList>>;
+
+    async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeries>>;
+
+    async fn create(&self, user: AuthenticatedUser, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>>;
+
+    async fn update(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>>;
+
+    // update only the fields present in the request
+    async fn patch(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeriesPatch>) -> Response<Json<ResSeries>>;
+
+    // its books stay in the library, outside any series
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String>;
+
+    // the books in volume order, with the gaps in the numbering
+    async fn books(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeriesBooks>>;
+}
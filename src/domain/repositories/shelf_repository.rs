@@ -0,0 +1,23 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::shelf::{ReqShelf, ResShelf, ResShelfList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait ShelfRepository {
+    async fn index(&self, user: AuthenticatedUser) -> Response<Json<ResShelfList>>;
+
+    async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResShelf>>;
+
+    // shelf names are unique per user
+    async fn create(&self, user: AuthenticatedUser, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>>;
+
+    async fn update(&self, user: AuthenticatedUser, id: i32, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>>;
+
+    // its books stay in the library
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String>;
+
+    // putting a book on a shelf twice changes nothing
+    async fn add_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String>;
+
+    async fn remove_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String>;
+}
@@ -0,0 +1,14 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::stats::{ReqReadingGoal, ReqStatsQuery, ResReadingGoal, ResStats}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait StatsRepository {
+    // the reading figures of a year, counted by the database
+    async fn stats(&self, user: AuthenticatedUser, query: ReqStatsQuery) -> Response<Json<ResStats>>;
+
+    // sets the goal of a year, replacing the one already set
+    async fn set_goal(&self, user: AuthenticatedUser, year: i32, req_goal: Json<ReqReadingGoal>) -> Response<Json<ResReadingGoal>>;
+
+    async fn delete_goal(&self, user: AuthenticatedUser, year: i32) -> Response<String>;
+}
@@ -0,0 +1,18 @@
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::tag::{ReqBookTags, ReqTag, ReqTagQuery, ResBookTags, ResTag, ResTagList}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::Response}};
+
+#[async_trait]
+pub trait TagRepository {
+    // the user's tags starting with `q`, the most used first
+    async fn index(&self, user: AuthenticatedUser, query: ReqTagQuery) -> Response<Json<ResTagList>>;
+
+    // the new name must not be taken by another tag
+    async fn rename(&self, user: AuthenticatedUser, id: i32, req_tag: Json<ReqTag>) -> Response<Json<ResTag>>;
+
+    // the tag comes off every book
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String>;
+
+    // replace the tags of a book, unknown names become new tags
+    async fn set_book_tags(&self, user: AuthenticatedUser, book_id: i32, req_tags: Json<ReqBookTags>) -> Response<Json<ResBookTags>>;
+}
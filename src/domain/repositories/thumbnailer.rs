@@ -0,0 +1,9 @@
+use rocket::async_trait;
+
+use crate::infrastructure::rocket_http::response_type::app_error::AppError;
+
+#[async_trait]
+pub trait Thumbnailer {
+    // the image scaled down to fit `max_side`, in its own format
+    async fn thumbnail(&self, image: &[u8], max_side: u32) -> Result<Vec<u8>, AppError>;
+}
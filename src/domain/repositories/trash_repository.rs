@@ -0,0 +1,13 @@
+use rocket::{async_trait, serde::json::Json};
+use sea_orm::prelude::DateTimeUtc;
+
+use crate::{domain::value_object::trash::{Purged, ResTrash}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::Response}}};
+
+#[async_trait]
+pub trait TrashRepository {
+    // the user's trashed books and authors, purged after `retention_days`
+    async fn index(&self, user: AuthenticatedUser, retention_days: u64) -> Response<Json<ResTrash>>;
+
+    // permanently delete everything trashed before `before`
+    async fn purge(&self, before: DateTimeUtc) -> Result<Purged, AppError>;
+}
@@ -1,6 +1,11 @@
-use rocket::async_trait;
+use rocket::{async_trait, serde::json::Json};
+
+use crate::{domain::value_object::user::{ReqRole, ResUser, ResUserList}, infrastructure::rocket_http::response_type::custom_response::Response};
 
 #[async_trait]
 pub trait UserRepository {
-    
-}
\ No newline at end of file
+    // get all users
+    async fn index(&self) -> Response<Json<ResUserList>>;
+
+    async fn update_role(&self, id: i32, req_role: Json<ReqRole>) -> Response<Json<ResUser>>;
+}
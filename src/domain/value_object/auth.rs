@@ -1,4 +1,9 @@
-use rocket::{serde::{Deserialize, Serialize}, Responder};
+use rocket::serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::domain::entities::user;
+
+use super::{patch::nullable, validation::{not_blank, password_policy}};
 
 #[derive(Deserialize)]
 #[serde(crate = "rocket::serde")]
@@ -7,18 +12,30 @@ pub struct ReqSignIn {
     pub password: String,
 }
 
-#[derive(Deserialize, Serialize, Responder)]
+#[derive(Deserialize, Serialize)]
 #[serde(crate = "rocket::serde")]
 pub struct ResSignIn{
-    pub token: String
+    pub token: String,
+    pub refresh_token: String,
+    pub expires_in: u64
 }
 
 #[derive(Deserialize)]
 #[serde(crate = "rocket::serde")]
+pub struct ReqRefresh {
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
 pub struct ReqSignUp {
+    #[validate(email(message = "must be a valid email address"))]
     pub email: String,
+    #[validate(custom(function = "password_policy"))]
     pub password: String,
+    #[validate(custom(function = "not_blank"))]
     pub firstname: Option<String>,
+    #[validate(custom(function = "not_blank"))]
     pub lastname: Option<String>,
 }
 
@@ -29,5 +46,30 @@ pub struct ResMe {
     pub id: u32,
     pub email: String,
     pub firstname: Option<String>,
-    pub lastname: Option<String>
-}
\ No newline at end of file
+    pub lastname: Option<String>,
+    pub role: String
+}
+
+// JSON Merge Patch of the profile: absent keeps, `null` clears
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqMePatch {
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub firstname: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub lastname: Option<Option<String>>,
+}
+
+impl From<&user::Model> for ResMe {
+    fn from(u: &user::Model) -> Self {
+        Self {
+            id: u.id as u32,
+            email: u.email.to_owned(),
+            firstname: u.firstname.to_owned(),
+            lastname: u.lastname.to_owned(),
+            role: u.role.to_owned(),
+        }
+    }
+}
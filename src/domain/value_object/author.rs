@@ -1,7 +1,10 @@
-use rocket::serde::{Deserialize, Serialize};
+use rocket::{serde::{Deserialize, Serialize}, FromForm, FromFormField};
+use validator::Validate;
 
 use crate::domain::entities::author;
 
+use super::{pagination::{ResPagination, SortOrder}, validation::not_blank};
+
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "rocket::serde")]
 pub struct ResAuthor {
@@ -16,16 +19,74 @@ pub struct ResAuthor {
 pub struct ResAuthorList {
     pub total: usize,
     pub authors: Vec<ResAuthor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<ResPagination>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum AuthorSort {
+    Firstname,
+    Lastname,
+    #[field(value = "created_at")]
+    CreatedAt,
+    #[field(value = "updated_at")]
+    UpdatedAt,
+}
+
+// query string of the authors index
+#[derive(FromForm)]
+pub struct ReqAuthorQuery {
+    // full-text search on names and bio
+    pub q: Option<String>,
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+    pub sort: Option<AuthorSort>,
+    pub order: Option<SortOrder>,
+}
+
+// what happens to the books of a deleted author
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum OnBooks {
+    // refuse while the author has books
+    Reject,
+    // trash the books along with the author
+    Cascade,
+    // move the books to the author given in `to`
+    Reassign,
+}
+
+// query string of an author delete
+#[derive(FromForm)]
+pub struct ReqAuthorDelete {
+    // an unknown policy is an error rather than the default
+    #[field(default = OnBooks::Reject)]
+    pub on_books: OnBooks,
+    pub to: Option<i32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Validate)]
 #[serde(crate = "rocket::serde")]
 pub struct ReqAuthor {
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
     pub firstname: String,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
     pub lastname: String,
+    #[validate(length(max = 255, message = "must be at most 255 characters"))]
     pub bio: String,
 }
 
+// partial update, absent fields are left unchanged
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqAuthorPatch {
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub firstname: Option<String>,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub lastname: Option<String>,
+    #[validate(length(max = 255, message = "must be at most 255 characters"))]
+    pub bio: Option<String>,
+}
+
 impl From<&author::Model> for ResAuthor {
     fn from(a: &author::Model) -> Self {
         Self {
@@ -0,0 +1,64 @@
+use rocket::serde::Serialize;
+
+use crate::infrastructure::rocket_http::response_type::app_error::{AppError, ResError};
+
+// outcome of one operation of a batch
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResBatchItem {
+    pub index: usize,
+    pub op: &'static str,
+    // the HTTP status the operation would have on its own
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResError>,
+}
+
+impl ResBatchItem {
+    pub fn applied(index: usize, op: &'static str, status: u16, id: i32) -> Self {
+        Self { index, op, status, id: Some(id), error: None }
+    }
+
+    pub fn failed(index: usize, op: &'static str, error: &AppError) -> Self {
+        error.log();
+        Self { index, op, status: error.status().code, id: None, error: Some(error.to_body()) }
+    }
+
+    // rolled back or never run because another operation of an atomic batch failed
+    pub fn not_applied(index: usize, op: &'static str) -> Self {
+        Self {
+            index,
+            op,
+            status: 424,
+            id: None,
+            error: Some(ResError {
+                code: "not_applied",
+                message: "Not applied because another operation failed".to_string(),
+                details: None,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResBatch {
+    pub atomic: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<ResBatchItem>,
+}
+
+impl From<(bool, Vec<ResBatchItem>)> for ResBatch {
+    fn from((atomic, results): (bool, Vec<ResBatchItem>)) -> Self {
+        let failed = results.iter().filter(|item| item.error.is_some()).count();
+        Self {
+            atomic,
+            succeeded: results.len() - failed,
+            failed,
+            results,
+        }
+    }
+}
@@ -1,15 +1,38 @@
-use rocket::serde::{Deserialize, Serialize};
+use rocket::{serde::{Deserialize, Serialize}, FromForm, FromFormField};
+use validator::{Validate, ValidationErrors};
 
 use crate::domain::entities::book;
 
+use super::{contributor::{ReqContributor, ResContributor}, pagination::{ResPagination, SortOrder}, patch::nullable, published_on::{LegacyYear, PublishedOn, ReqPublishedOn}, review::ResRating, validation::{batch_size, not_blank, unique_contributors, legacy_year, valid_isbn, valid_published_on}};
+
 #[derive(Serialize,Deserialize)]
 #[serde(crate = "rocket::serde")]
 pub struct ResBook {
     pub id: i32,
     pub author_id: i32,
     pub title: String,
+    // the year of `published_on`, empty when unknown, kept for older clients
     pub year: String,
+    pub published_on: Option<PublishedOn>,
+    // the old year that was not a date, kept until a date is set
+    pub published_text: Option<String>,
     pub cover: String,
+    pub isbn10: Option<String>,
+    pub isbn13: Option<String>,
+    // where the uploaded cover is served, its thumbnails take a `size` query
+    pub cover_image: Option<String>,
+    pub series_id: Option<i32>,
+    // the volume number within the series
+    pub series_index: Option<i32>,
+    pub total_pages: Option<i32>,
+    // everyone who worked on the book, the primary author first
+    #[serde(default)]
+    pub contributors: Vec<ResContributor>,
+    // the average of the reviews' ratings
+    #[serde(default)]
+    pub rating: ResRating,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize,Deserialize)]
@@ -17,25 +40,207 @@ pub struct ResBook {
 pub struct ResBookList {
     pub total: usize,
     pub books: Vec<ResBook>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<ResPagination>,
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum BookSort {
+    Title,
+    Year,
+    #[field(value = "created_at")]
+    CreatedAt,
+    #[field(value = "updated_at")]
+    UpdatedAt,
+}
+
+// query string of the admin catalog of every user's books
+#[derive(FromForm)]
+pub struct ReqCatalogQuery {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+// query string of the books index
+#[derive(FromForm)]
+pub struct ReqBookQuery {
+    // full-text search on the title
+    pub q: Option<String>,
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+    pub sort: Option<BookSort>,
+    pub order: Option<SortOrder>,
+    pub author_id: Option<i32>,
+    pub year_from: Option<u32>,
+    pub year_to: Option<u32>,
+    // the books on one of the user's shelves
+    pub shelf: Option<i32>,
+    // the books carrying a tag, by name
+    pub tag: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
 #[serde(crate = "rocket::serde")]
 pub struct ReqBook {
+    // existence and ownership are checked by the repository
     pub author_id: i32,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
     pub title: String,
-    pub year: String,
+    // `null` or absent when unknown
+    #[serde(default)]
+    #[validate(custom(function = "valid_published_on"))]
+    pub published_on: Option<ReqPublishedOn>,
+    #[serde(default)]
+    #[validate(custom(function = "legacy_year"))]
+    pub year: Option<LegacyYear>,
+    #[validate(url(message = "must be a valid URL"), length(max = 255, message = "must be at most 255 characters"))]
     pub cover: String,
+    // either form, both are stored
+    #[serde(default)]
+    #[validate(custom(function = "valid_isbn"))]
+    pub isbn: Option<String>,
+    // more authors, editors, translators or illustrators in order, `author_id` is always the first author
+    #[serde(default)]
+    #[validate(custom(function = "unique_contributors"))]
+    pub contributors: Option<Vec<ReqContributor>>,
+    // ownership is checked by the repository, a volume number needs a series
+    #[serde(default)]
+    pub series_id: Option<i32>,
+    #[serde(default)]
+    #[validate(range(min = 1, message = "must be at least 1"))]
+    pub series_index: Option<i32>,
+    // reading progress is measured against it
+    #[serde(default)]
+    #[validate(range(min = 1, max = 100000, message = "must be between 1 and 100000"))]
+    pub total_pages: Option<i32>,
+}
+
+impl ReqBook {
+    // `published_on`, or the `year` of an older client
+    pub fn date(&self) -> Option<ReqPublishedOn> {
+        self.published_on.clone().or_else(|| self.year.as_ref().and_then(LegacyYear::to_req_published_on))
+    }
+}
+
+// partial update, absent fields are left unchanged
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBookPatch {
+    pub author_id: Option<i32>,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub title: Option<String>,
+    // `null` makes the date unknown
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom(function = "valid_published_on"))]
+    pub published_on: Option<Option<ReqPublishedOn>>,
+    #[serde(default)]
+    #[validate(custom(function = "legacy_year"))]
+    pub year: Option<LegacyYear>,
+    #[validate(url(message = "must be a valid URL"), length(max = 255, message = "must be at most 255 characters"))]
+    pub cover: Option<String>,
+    // `null` removes the ISBN
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(custom(function = "valid_isbn"))]
+    pub isbn: Option<Option<String>>,
+    // replaces the contributors after `author_id`
+    #[serde(default)]
+    #[validate(custom(function = "unique_contributors"))]
+    pub contributors: Option<Vec<ReqContributor>>,
+    // `null` takes the book out of its series, with its volume number
+    #[serde(default, deserialize_with = "nullable")]
+    pub series_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 1, message = "must be at least 1"))]
+    pub series_index: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "nullable")]
+    #[validate(range(min = 1, max = 100000, message = "must be between 1 and 100000"))]
+    pub total_pages: Option<Option<i32>>,
+}
+
+impl ReqBookPatch {
+    // `published_on`, or the `year` of an older client, a blank year makes the date unknown
+    pub fn date(&self) -> Option<Option<ReqPublishedOn>> {
+        self.published_on.clone().or_else(|| self.year.as_ref().map(LegacyYear::to_req_published_on))
+    }
+}
+
+// one operation of a books batch, tagged by `op`
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde", tag = "op", rename_all = "lowercase")]
+pub enum ReqBookOperation {
+    Create { book: ReqBook },
+    // partial, like PATCH
+    Update { id: i32, book: ReqBookPatch },
+    Delete { id: i32 },
+}
+
+impl ReqBookOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReqBookOperation::Create { .. } => "create",
+            ReqBookOperation::Update { .. } => "update",
+            ReqBookOperation::Delete { .. } => "delete",
+        }
+    }
+}
+
+// operations are validated one by one, so one bad item does not reject the batch
+impl Validate for ReqBookOperation {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            ReqBookOperation::Create { book } => book.validate(),
+            ReqBookOperation::Update { book, .. } => book.validate(),
+            ReqBookOperation::Delete { .. } => Ok(()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBookBatch {
+    // all-or-nothing when set, best-effort otherwise
+    #[serde(default)]
+    pub atomic: bool,
+    pub operations: Vec<ReqBookOperation>,
+}
+
+// only the size, each operation is validated when it runs
+impl Validate for ReqBookBatch {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if let Err(e) = batch_size(&self.operations) {
+            errors.add("operations", e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 impl From<&book::Model> for ResBook {
     fn from(b: &book::Model) -> Self {
+        let published_on = b.published_on();
+
         Self {
             id: b.id,
             author_id: b.author_id,
             title: b.title.to_owned(),
-            year: b.year.to_owned(),
-            cover: b.cover.to_owned()
+            year: published_on.map(|p| p.year.to_string()).unwrap_or_default(),
+            published_on,
+            published_text: b.published_text.to_owned(),
+            cover: b.cover.to_owned(),
+            isbn10: b.isbn10.to_owned(),
+            isbn13: b.isbn13.to_owned(),
+            cover_image: b.cover_blob.as_ref().map(|_| format!("/books/{}/cover", b.id)),
+            series_id: b.series_id,
+            series_index: b.series_index,
+            total_pages: b.total_pages,
+            contributors: Vec::new(),
+            rating: ResRating::default(),
+            tags: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{author, book_contributor};
+
+// what a contributor did for a book
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum ContributorRole {
+    #[default]
+    Author,
+    Editor,
+    Translator,
+    Illustrator,
+}
+
+impl ContributorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContributorRole::Author => "author",
+            ContributorRole::Editor => "editor",
+            ContributorRole::Translator => "translator",
+            ContributorRole::Illustrator => "illustrator",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "author" => Some(ContributorRole::Author),
+            "editor" => Some(ContributorRole::Editor),
+            "translator" => Some(ContributorRole::Translator),
+            "illustrator" => Some(ContributorRole::Illustrator),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqContributor {
+    // existence and ownership are checked by the repository
+    pub author_id: i32,
+    #[serde(default)]
+    pub role: ContributorRole,
+}
+
+impl ReqContributor {
+    // the stored order, the book's `author_id` always comes first as an author
+    pub fn ordered(author_id: i32, given: &[ReqContributor]) -> Vec<ReqContributor> {
+        let primary = ReqContributor { author_id, role: ContributorRole::Author };
+        let others = given.iter().filter(|c| **c != primary).copied();
+
+        std::iter::once(primary).chain(others).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResContributor {
+    pub author_id: i32,
+    pub firstname: String,
+    pub lastname: String,
+    pub role: ContributorRole,
+    pub position: i32,
+}
+
+impl From<(&book_contributor::Model, &author::Model)> for ResContributor {
+    fn from((contributor, author): (&book_contributor::Model, &author::Model)) -> Self {
+        Self {
+            author_id: contributor.author_id,
+            firstname: author.firstname.to_owned(),
+            lastname: author.lastname.to_owned(),
+            role: ContributorRole::parse(&contributor.role).unwrap_or_default(),
+            position: contributor.position,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use rocket::{fs::TempFile, FromForm, FromFormField};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum CoverSize {
+    Small,
+    Medium,
+    Large,
+    Original,
+}
+
+impl CoverSize {
+    // generated on upload next to the original
+    pub const THUMBNAILS: [CoverSize; 3] = [CoverSize::Small, CoverSize::Medium, CoverSize::Large];
+    pub const ALL: [CoverSize; 4] = [CoverSize::Small, CoverSize::Medium, CoverSize::Large, CoverSize::Original];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoverSize::Small => "small",
+            CoverSize::Medium => "medium",
+            CoverSize::Large => "large",
+            CoverSize::Original => "original",
+        }
+    }
+
+    // longest side in pixels, thumbnails are never larger than the original
+    pub fn max_side(&self) -> Option<u32> {
+        match self {
+            CoverSize::Small => Some(96),
+            CoverSize::Medium => Some(256),
+            CoverSize::Large => Some(512),
+            CoverSize::Original => None,
+        }
+    }
+}
+
+// accepted image formats, recognized by their first bytes rather than the declared type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageType {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl ImageType {
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0xFF, 0xD8, 0xFF, ..] => Some(ImageType::Jpeg),
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(ImageType::Png),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(ImageType::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            ImageType::Jpeg => "image/jpeg",
+            ImageType::Png => "image/png",
+            ImageType::Webp => "image/webp",
+        }
+    }
+}
+
+// multipart body of `PUT /books/<id>/cover`
+#[derive(FromForm)]
+pub struct ReqCoverUpload<'r> {
+    pub file: TempFile<'r>,
+}
+
+#[derive(FromForm)]
+pub struct ReqCoverQuery {
+    #[field(default = CoverSize::Original)]
+    pub size: CoverSize,
+}
+
+// the stored cover of a book
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverRef {
+    // SHA-256 of the original image, hex encoded
+    pub hash: String,
+    pub content_type: String,
+}
+
+impl CoverRef {
+    // blobs of a book never outlive a cover change, so they are keyed by book
+    pub fn key(&self, book_id: i32, size: CoverSize) -> String {
+        format!("covers/{}/{}/{}", book_id, self.hash, size.name())
+    }
+
+    pub fn etag(&self, size: CoverSize) -> String {
+        format!("\"{}-{}\"", self.hash, size.name())
+    }
+}
+
+pub struct Blob {
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
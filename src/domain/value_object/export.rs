@@ -0,0 +1,74 @@
+use rocket::{serde::Serialize, FromFormField};
+
+use crate::domain::entities::{author, book};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+// one book with its author, named like the import columns so an export can be imported back
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResExportRow {
+    pub id: i32,
+    pub title: String,
+    // the text form the import reads back
+    pub published_on: Option<String>,
+    pub cover: String,
+    pub isbn: Option<String>,
+    pub author_id: i32,
+    pub author_firstname: String,
+    pub author_lastname: String,
+    pub author_bio: String,
+}
+
+impl ResExportRow {
+    pub const COLUMNS: [&'static str; 9] = [
+        "id", "title", "published_on", "cover", "isbn", "author_id", "author_firstname", "author_lastname", "author_bio",
+    ];
+
+    pub fn fields(&self) -> [String; 9] {
+        [
+            self.id.to_string(),
+            self.title.to_owned(),
+            self.published_on.to_owned().unwrap_or_default(),
+            self.cover.to_owned(),
+            self.isbn.to_owned().unwrap_or_default(),
+            self.author_id.to_string(),
+            self.author_firstname.to_owned(),
+            self.author_lastname.to_owned(),
+            self.author_bio.to_owned(),
+        ]
+    }
+}
+
+impl From<(book::Model, author::Model)> for ResExportRow {
+    fn from((book, author): (book::Model, author::Model)) -> Self {
+        let published_on = book.published_on().map(|p| p.to_string());
+
+        Self {
+            id: book.id,
+            title: book.title,
+            published_on,
+            cover: book.cover,
+            isbn: book.isbn13,
+            author_id: author.id,
+            author_firstname: author.firstname,
+            author_lastname: author.lastname,
+            author_bio: author.bio,
+        }
+    }
+}
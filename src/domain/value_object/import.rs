@@ -0,0 +1,141 @@
+use rocket::{fs::TempFile, serde::{Deserialize, Serialize}, FromForm, FromFormField};
+use validator::Validate;
+
+use crate::infrastructure::rocket_http::response_type::app_error::{AppError, ResError};
+
+use super::{book::ReqBook, published_on::{PublishedOn, ReqPublishedOn}, validation::{not_blank, published_on_text, valid_isbn}};
+
+pub const IMPORT_MAX_ROWS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+// multipart body of `POST /import`
+#[derive(FromForm)]
+pub struct ReqImportUpload<'r> {
+    pub file: TempFile<'r>,
+    // taken from the file's content type when absent
+    pub format: Option<ImportFormat>,
+}
+
+// one book of an import, a CSV line or a JSON object with the same names
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqImportRow {
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub title: String,
+    // text like "1968", "1968-05-12" or "c. 1900", `year` in older files
+    #[serde(default, alias = "year")]
+    #[validate(custom(function = "published_on_text"))]
+    pub published_on: Option<String>,
+    #[serde(default)]
+    #[validate(url(message = "must be a valid URL"), length(max = 255, message = "must be at most 255 characters"))]
+    pub cover: Option<String>,
+    #[serde(default)]
+    #[validate(custom(function = "valid_isbn"))]
+    pub isbn: Option<String>,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub author_firstname: String,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub author_lastname: String,
+    // only used when the author is created
+    #[serde(default)]
+    pub author_bio: Option<String>,
+}
+
+impl ReqImportRow {
+    // an already validated date
+    pub fn published_on(&self) -> Option<PublishedOn> {
+        self.published_on.as_deref().and_then(PublishedOn::parse)
+    }
+
+    // the book as it would be created, validated like any other
+    pub fn to_req_book(&self, author_id: i32) -> ReqBook {
+        ReqBook {
+            author_id,
+            title: self.title.trim().to_string(),
+            published_on: self.published_on().map(ReqPublishedOn::from),
+            year: None,
+            cover: self.cover.clone().unwrap_or_default(),
+            isbn: self.isbn.clone(),
+            contributors: None,
+            series_id: None,
+            series_index: None,
+            total_pages: None,
+        }
+    }
+}
+
+// a row as read from the file, `row` is the spreadsheet line for CSV
+// and the position from 1 for JSON
+pub struct ImportRow {
+    pub row: usize,
+    pub fields: Result<ReqImportRow, AppError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum ImportStatus {
+    Inserted,
+    Skipped,
+    Failed,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResImportRow {
+    pub row: usize,
+    pub status: ImportStatus,
+    // unknown for rows a dry run would insert
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub book_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResError>,
+}
+
+impl ResImportRow {
+    pub fn inserted(row: usize, book_id: Option<i32>, author_id: Option<i32>) -> Self {
+        Self { row, status: ImportStatus::Inserted, book_id, author_id, reason: None, error: None }
+    }
+
+    pub fn skipped(row: usize, book_id: Option<i32>, author_id: Option<i32>, reason: &'static str) -> Self {
+        Self { row, status: ImportStatus::Skipped, book_id, author_id, reason: Some(reason), error: None }
+    }
+
+    pub fn failed(row: usize, error: &AppError) -> Self {
+        Self { row, status: ImportStatus::Failed, book_id: None, author_id: None, reason: None, error: Some(error.to_body()) }
+    }
+}
+
+// what the import did, or would do on a dry run
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResImport {
+    pub dry_run: bool,
+    pub inserted: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub authors_created: usize,
+    pub rows: Vec<ResImportRow>,
+}
+
+impl ResImport {
+    pub fn new(dry_run: bool, authors_created: usize, rows: Vec<ResImportRow>) -> Self {
+        let count = |status| rows.iter().filter(|r| r.status == status).count();
+        Self {
+            dry_run,
+            inserted: count(ImportStatus::Inserted),
+            skipped: count(ImportStatus::Skipped),
+            failed: count(ImportStatus::Failed),
+            authors_created,
+            rows,
+        }
+    }
+}
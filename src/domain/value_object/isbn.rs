@@ -0,0 +1,132 @@
+use std::{fmt, str::FromStr};
+
+// an ISBN kept as its 13 digits, the 10 digit form is derived when it exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Isbn {
+    isbn13: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsbnError {
+    Length,
+    Character,
+    Prefix,
+    Checksum,
+}
+
+impl IsbnError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            IsbnError::Length => "isbn_length",
+            IsbnError::Character => "isbn_character",
+            IsbnError::Prefix => "isbn_prefix",
+            IsbnError::Checksum => "isbn_checksum",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            IsbnError::Length => "must have 10 or 13 digits",
+            IsbnError::Character => "must only contain digits, hyphens, spaces and a final X for ISBN-10",
+            IsbnError::Prefix => "must start with 978 or 979",
+            IsbnError::Checksum => "has an invalid check digit",
+        }
+    }
+}
+
+impl fmt::Display for IsbnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for IsbnError {}
+
+fn digit(c: char) -> u32 {
+    c.to_digit(10).unwrap_or(10)
+}
+
+// check digit of the first 9 digits of an ISBN-10, `X` stands for 10
+fn check10(digits: &str) -> char {
+    let sum: u32 = digits.chars().zip((2..=10).rev()).map(|(c, w)| digit(c) * w).sum();
+    match (11 - sum % 11) % 11 {
+        10 => 'X',
+        d => char::from_digit(d, 10).unwrap(),
+    }
+}
+
+// check digit of the first 12 digits of an ISBN-13
+fn check13(digits: &str) -> char {
+    let sum: u32 = digits.chars().zip([1, 3].iter().cycle()).map(|(c, w)| digit(c) * w).sum();
+    char::from_digit((10 - sum % 10) % 10, 10).unwrap()
+}
+
+impl Isbn {
+    // either form, hyphens and spaces are ignored
+    pub fn parse(value: &str) -> Result<Self, IsbnError> {
+        let value: String = value
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        // lengths below are counted in bytes, which only match the characters for ASCII
+        if !value.is_ascii() {
+            return Err(IsbnError::Character);
+        }
+
+        match value.len() {
+            10 => {
+                let (body, check) = value.split_at(9);
+                if !body.chars().all(|c| c.is_ascii_digit()) || !check.chars().all(|c| c.is_ascii_digit() || c == 'X') {
+                    return Err(IsbnError::Character);
+                }
+                if check10(body) != check.chars().next().unwrap() {
+                    return Err(IsbnError::Checksum);
+                }
+
+                let body = format!("978{}", body);
+                Ok(Self { isbn13: format!("{}{}", body, check13(&body)) })
+            }
+            13 => {
+                if !value.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(IsbnError::Character);
+                }
+                if !value.starts_with("978") && !value.starts_with("979") {
+                    return Err(IsbnError::Prefix);
+                }
+                let (body, check) = value.split_at(12);
+                if check13(body) != check.chars().next().unwrap() {
+                    return Err(IsbnError::Checksum);
+                }
+
+                Ok(Self { isbn13: value })
+            }
+            _ if value.chars().all(|c| c.is_ascii_digit() || c == 'X') => Err(IsbnError::Length),
+            _ => Err(IsbnError::Character),
+        }
+    }
+
+    pub fn isbn13(&self) -> &str {
+        &self.isbn13
+    }
+
+    // only ISBNs of the 978 prefix have a 10 digit form
+    pub fn isbn10(&self) -> Option<String> {
+        let body = self.isbn13.strip_prefix("978")?.get(..9)?;
+        Some(format!("{}{}", body, check10(body)))
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = IsbnError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Isbn::parse(value)
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.isbn13)
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::NaiveDate;
+use rocket::{serde::{Deserialize, Serialize}, FromForm, FromFormField};
+use sea_orm::prelude::DateTimeUtc;
+use validator::Validate;
+
+use crate::{domain::entities::{book, loan}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::{pagination::ResPagination, validation::not_blank};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromFormField)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum LoanDirection {
+    // the user lent the book to the person
+    #[default]
+    Lent,
+    // the user borrowed the book from the person
+    Borrowed,
+}
+
+impl LoanDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoanDirection::Lent => "lent",
+            LoanDirection::Borrowed => "borrowed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "lent" => Some(LoanDirection::Lent),
+            "borrowed" => Some(LoanDirection::Borrowed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqLoan {
+    #[serde(default)]
+    pub direction: LoanDirection,
+    // who has the book, or whose it is when borrowed
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub person: String,
+    #[validate(length(max = 255, message = "must be at most 255 characters"))]
+    pub contact: Option<String>,
+    // today when absent
+    pub lent_on: Option<NaiveDate>,
+    pub due_on: Option<NaiveDate>,
+}
+
+impl ReqLoan {
+    // the rules across fields, the loan has started and is due after it started
+    pub fn lent_on(&self, today: NaiveDate) -> Result<NaiveDate, AppError> {
+        let lent_on = self.lent_on.unwrap_or(today);
+        if lent_on > today {
+            return Err(AppError::invalid_field("lent_on", "must not be in the future"));
+        }
+        if self.due_on.is_some_and(|due_on| due_on < lent_on) {
+            return Err(AppError::invalid_field("due_on", "must not be before lent_on"));
+        }
+        Ok(lent_on)
+    }
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqLoanReturn {
+    // today when absent
+    pub returned_on: Option<NaiveDate>,
+}
+
+// query string of the loans index, the latest first
+#[derive(FromForm)]
+pub struct ReqLoanQuery {
+    // only the loans still out past their due date
+    pub overdue: Option<bool>,
+    // only the loans still out, or only the returned ones
+    pub open: Option<bool>,
+    pub direction: Option<LoanDirection>,
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResLoan {
+    pub id: i32,
+    pub book_id: i32,
+    pub book_title: String,
+    pub direction: LoanDirection,
+    pub person: String,
+    pub contact: Option<String>,
+    pub lent_on: NaiveDate,
+    pub due_on: Option<NaiveDate>,
+    pub returned_on: Option<NaiveDate>,
+    pub overdue: bool,
+    pub created_at: Option<DateTimeUtc>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResLoanList {
+    pub total: usize,
+    pub loans: Vec<ResLoan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<ResPagination>,
+}
+
+impl ResLoan {
+    pub fn new(loan: &loan::Model, book: &book::Model, today: NaiveDate) -> Self {
+        Self {
+            id: loan.id,
+            book_id: loan.book_id,
+            book_title: book.title.to_owned(),
+            direction: LoanDirection::parse(&loan.direction).unwrap_or_default(),
+            person: loan.person.to_owned(),
+            contact: loan.contact.to_owned(),
+            lent_on: loan.lent_on,
+            due_on: loan.due_on,
+            returned_on: loan.returned_on,
+            overdue: loan.returned_on.is_none() && loan.due_on.is_some_and(|due_on| due_on < today),
+            created_at: loan.created_at,
+        }
+    }
+}
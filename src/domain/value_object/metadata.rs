@@ -0,0 +1,56 @@
+use rocket::serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::{published_on::{LegacyYear, PublishedOn, ReqPublishedOn}, validation::{legacy_year, not_blank, valid_isbn, valid_published_on}};
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBookLookup {
+    #[validate(custom(function = "valid_isbn"))]
+    pub isbn: String,
+}
+
+// a book to complete from its ISBN, the fields given here win over the provider's
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBookDraft {
+    #[validate(custom(function = "valid_isbn"))]
+    pub isbn: String,
+    // matched or created from the provider's first author when absent
+    pub author_id: Option<i32>,
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub title: Option<String>,
+    #[validate(custom(function = "valid_published_on"))]
+    pub published_on: Option<ReqPublishedOn>,
+    #[serde(default)]
+    #[validate(custom(function = "legacy_year"))]
+    pub year: Option<LegacyYear>,
+    #[validate(url(message = "must be a valid URL"), length(max = 255, message = "must be at most 255 characters"))]
+    pub cover: Option<String>,
+}
+
+impl ReqBookDraft {
+    // `published_on`, or the `year` of an older client
+    pub fn date(&self) -> Option<ReqPublishedOn> {
+        self.published_on.clone().or_else(|| self.year.as_ref().and_then(LegacyYear::to_req_published_on))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct AuthorMetadata {
+    pub firstname: String,
+    pub lastname: String,
+}
+
+// what a provider knows about an edition, any field may be missing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct BookMetadata {
+    pub isbn10: Option<String>,
+    pub isbn13: String,
+    pub title: Option<String>,
+    pub published_on: Option<PublishedOn>,
+    pub cover: Option<String>,
+    pub authors: Vec<AuthorMetadata>,
+}
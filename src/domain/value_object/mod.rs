@@ -1,3 +1,24 @@
 pub mod auth;
 pub mod book;
-pub mod author;
\ No newline at end of file
+pub mod author;
+pub mod user;
+pub mod pagination;
+pub mod search;
+pub mod validation;
+pub mod patch;
+pub mod trash;
+pub mod batch;
+pub mod import;
+pub mod export;
+pub mod isbn;
+pub mod metadata;
+pub mod cover;
+pub mod published_on;
+pub mod contributor;
+pub mod series;
+pub mod reading;
+pub mod review;
+pub mod shelf;
+pub mod tag;
+pub mod loan;
+pub mod stats;
@@ -0,0 +1,17 @@
+use rocket::{serde::{Deserialize, Serialize}, FromFormField};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromFormField)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResPagination {
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+    pub next_page: Option<u64>,
+    pub prev_page: Option<u64>,
+}
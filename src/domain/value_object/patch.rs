@@ -0,0 +1,11 @@
+use rocket::serde::{Deserialize, Deserializer};
+
+// JSON Merge Patch field: absent is `None`, `null` is `Some(None)`,
+// use with `#[serde(default, deserialize_with = "nullable")]`
+pub fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use rocket::serde::{Deserialize, Serialize};
+
+// how much of a publication date is known
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum DatePrecision {
+    Day,
+    Month,
+    Year,
+    // an approximate year, e.g. "c. 1900"
+    Circa,
+}
+
+impl DatePrecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DatePrecision::Day => "day",
+            DatePrecision::Month => "month",
+            DatePrecision::Year => "year",
+            DatePrecision::Circa => "circa",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "day" => Some(DatePrecision::Day),
+            "month" => Some(DatePrecision::Month),
+            "year" => Some(DatePrecision::Year),
+            "circa" => Some(DatePrecision::Circa),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishedOnError {
+    Year,
+    Month,
+    Day,
+    Precision,
+}
+
+impl PublishedOnError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PublishedOnError::Year => "year_out_of_range",
+            PublishedOnError::Month => "month_out_of_range",
+            PublishedOnError::Day => "invalid_day",
+            PublishedOnError::Precision => "invalid_precision",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            PublishedOnError::Year => "year must be between 1 and next year",
+            PublishedOnError::Month => "month must be between 1 and 12",
+            PublishedOnError::Day => "day must be a day of the given month",
+            PublishedOnError::Precision => "precision must match the given parts, circa only goes with a year",
+        }
+    }
+}
+
+impl fmt::Display for PublishedOnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for PublishedOnError {}
+
+// a publication date known to the year, month or day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct PublishedOn {
+    pub year: i32,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub precision: DatePrecision,
+}
+
+// the request form, the precision is taken from the given parts when absent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqPublishedOn {
+    pub year: i32,
+    #[serde(default)]
+    pub month: Option<u8>,
+    #[serde(default)]
+    pub day: Option<u8>,
+    #[serde(default)]
+    pub precision: Option<DatePrecision>,
+}
+
+impl ReqPublishedOn {
+    pub fn to_published_on(&self) -> Result<PublishedOn, PublishedOnError> {
+        PublishedOn::new(self.year, self.month, self.day, self.precision)
+    }
+}
+
+impl From<PublishedOn> for ReqPublishedOn {
+    fn from(date: PublishedOn) -> Self {
+        Self {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            precision: Some(date.precision),
+        }
+    }
+}
+
+// the free text `year` older clients send instead of `published_on`, read only when `published_on` is
+// absent so an old client does not erase the date, blank when the date is unknown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", untagged)]
+pub enum LegacyYear {
+    Number(i64),
+    Text(String),
+}
+
+impl LegacyYear {
+    pub fn is_blank(&self) -> bool {
+        matches!(self, LegacyYear::Text(text) if text.trim().is_empty())
+    }
+
+    // `None` when blank or not a date
+    pub fn to_req_published_on(&self) -> Option<ReqPublishedOn> {
+        let date = match self {
+            LegacyYear::Number(year) => PublishedOn::parse(&year.to_string()),
+            LegacyYear::Text(text) => PublishedOn::parse(text),
+        };
+        date.map(ReqPublishedOn::from)
+    }
+}
+
+impl PublishedOn {
+    pub fn new(year: i32, month: Option<u8>, day: Option<u8>, precision: Option<DatePrecision>) -> Result<Self, PublishedOnError> {
+        if !(1..=Utc::now().year() + 1).contains(&year) {
+            return Err(PublishedOnError::Year);
+        }
+        if month.is_some_and(|m| !(1..=12).contains(&m)) {
+            return Err(PublishedOnError::Month);
+        }
+
+        let given = match (month, day) {
+            (Some(month), Some(day)) => {
+                NaiveDate::from_ymd_opt(year, month.into(), day.into()).ok_or(PublishedOnError::Day)?;
+                DatePrecision::Day
+            }
+            (None, Some(_)) => return Err(PublishedOnError::Day),
+            (Some(_), None) => DatePrecision::Month,
+            (None, None) => DatePrecision::Year,
+        };
+
+        let precision = match precision {
+            None => given,
+            Some(DatePrecision::Circa) if given == DatePrecision::Year => DatePrecision::Circa,
+            Some(precision) if precision == given => precision,
+            Some(_) => return Err(PublishedOnError::Precision),
+        };
+
+        Ok(Self { year, month, day, precision })
+    }
+
+    // free text as clients used to send it: "2019", "2019-05", "2019-05-12" or "c. 1900",
+    // `None` when it is not a date, e.g. "unknown"
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+
+        let (value, circa) = match ["circa", "ca.", "ca", "c.", "c", "~"].iter().find_map(|p| value.strip_prefix(p)) {
+            Some(rest) => (rest.trim(), true),
+            None => match value.strip_suffix('?') {
+                Some(rest) => (rest.trim(), true),
+                None => (value.as_str(), false),
+            },
+        };
+
+        let parts: Vec<&str> = value.split(['-', '/', '.']).collect();
+        if parts.iter().any(|p| p.is_empty() || p.len() > 4 || !p.chars().all(|c| c.is_ascii_digit())) {
+            return None;
+        }
+
+        if parts.len() > 3 || (circa && parts.len() > 1) {
+            return None;
+        }
+
+        // a missing part is `None`, a part out of range fails the parse
+        let part = |index: usize| match parts.get(index) {
+            Some(part) => part.parse::<u8>().ok().map(Some),
+            None => Some(None),
+        };
+        let year = parts[0].parse().ok()?;
+        let (month, day) = (part(1)?, part(2)?);
+
+        PublishedOn::new(year, month, day, circa.then_some(DatePrecision::Circa)).ok()
+    }
+
+    // as stored in the `published_*` columns of a book
+    pub fn from_columns(year: Option<i32>, month: Option<u8>, day: Option<u8>, precision: Option<&str>) -> Option<Self> {
+        let precision = precision.and_then(DatePrecision::parse);
+        Some(Self {
+            year: year?,
+            month,
+            day,
+            precision: precision.unwrap_or(match (month, day) {
+                (Some(_), Some(_)) => DatePrecision::Day,
+                (Some(_), None) => DatePrecision::Month,
+                _ => DatePrecision::Year,
+            }),
+        })
+    }
+}
+
+// the text form `parse` reads back
+impl fmt::Display for PublishedOn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.precision, self.month, self.day) {
+            (DatePrecision::Circa, _, _) => write!(f, "c. {}", self.year),
+            (_, Some(month), Some(day)) => write!(f, "{:04}-{:02}-{:02}", self.year, month, day),
+            (_, Some(month), None) => write!(f, "{:04}-{:02}", self.year, month),
+            _ => write!(f, "{}", self.year),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use rocket::serde::{Deserialize, Serialize};
+use sea_orm::prelude::DateTimeUtc;
+use validator::Validate;
+
+use crate::{domain::entities::{reading_session, reading_state}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::book::ResBook;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ReadingStatus {
+    WantToRead,
+    Reading,
+    Finished,
+    Abandoned,
+}
+
+impl ReadingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReadingStatus::WantToRead => "want_to_read",
+            ReadingStatus::Reading => "reading",
+            ReadingStatus::Finished => "finished",
+            ReadingStatus::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "want_to_read" => Some(ReadingStatus::WantToRead),
+            "reading" => Some(ReadingStatus::Reading),
+            "finished" => Some(ReadingStatus::Finished),
+            "abandoned" => Some(ReadingStatus::Abandoned),
+            _ => None,
+        }
+    }
+
+    // the started and finished dates after moving from `from` to this status at `at`,
+    // reading again after finishing or abandoning starts over
+    pub fn dates(self, from: Option<&reading_state::Model>, at: DateTimeUtc) -> (Option<DateTimeUtc>, Option<DateTimeUtc>) {
+        let previous = from.and_then(|f| ReadingStatus::parse(&f.status));
+        let started_at = from.and_then(|f| f.started_at);
+        let finished_at = from.and_then(|f| f.finished_at);
+
+        match (self, previous) {
+            (ReadingStatus::WantToRead, _) => (None, None),
+            (ReadingStatus::Reading, Some(ReadingStatus::Reading)) => (started_at.or(Some(at)), None),
+            (ReadingStatus::Reading, _) => (Some(at), None),
+            (ReadingStatus::Finished, Some(ReadingStatus::Finished)) => (started_at.or(Some(at)), finished_at.or(Some(at))),
+            (ReadingStatus::Finished, _) => (started_at.or(Some(at)), Some(at)),
+            (ReadingStatus::Abandoned, _) => (started_at.or(Some(at)), None),
+        }
+    }
+}
+
+// where the reader is in a book, the page wins over the percentage when both are known
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub current_page: Option<i32>,
+    pub percent: Option<u8>,
+}
+
+impl Progress {
+    pub fn new(current_page: Option<i32>, percent: Option<u8>, total_pages: Option<i32>) -> Result<Self, AppError> {
+        match (current_page, total_pages) {
+            (Some(page), Some(total)) if page > total => {
+                Err(AppError::invalid_field("current_page", "must not be past the book's total_pages"))
+            }
+            (Some(page), Some(total)) => Ok(Self { current_page: Some(page), percent: Some((page as i64 * 100 / total as i64) as u8) }),
+            (None, Some(total)) => Ok(Self {
+                current_page: percent.map(|p| (p as i64 * total as i64 / 100) as i32),
+                percent,
+            }),
+            (page, None) => Ok(Self { current_page: page, percent }),
+        }
+    }
+
+    // a finished book is read to the end, a wished one not at all
+    pub fn for_status(self, status: ReadingStatus, total_pages: Option<i32>) -> Self {
+        match status {
+            ReadingStatus::WantToRead => Self::default(),
+            ReadingStatus::Finished => Self { current_page: total_pages.or(self.current_page), percent: Some(100) },
+            ReadingStatus::Reading | ReadingStatus::Abandoned => self,
+        }
+    }
+}
+
+// the progress fields are kept as they are when absent
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqReadingStatus {
+    pub status: ReadingStatus,
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub current_page: Option<i32>,
+    #[validate(range(max = 100, message = "must be between 0 and 100"))]
+    pub percent: Option<u8>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqReadingSession {
+    pub started_at: DateTimeUtc,
+    pub ended_at: DateTimeUtc,
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub start_page: Option<i32>,
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub end_page: Option<i32>,
+}
+
+impl ReqReadingSession {
+    // the rules across fields, a session is over and runs forward
+    pub fn check(&self, now: DateTimeUtc) -> Result<(), AppError> {
+        if self.ended_at <= self.started_at {
+            return Err(AppError::invalid_field("ended_at", "must be after started_at"));
+        }
+        if self.ended_at > now {
+            return Err(AppError::invalid_field("ended_at", "must not be in the future"));
+        }
+        if let (Some(start), Some(end)) = (self.start_page, self.end_page) {
+            if end < start {
+                return Err(AppError::invalid_field("end_page", "must not be before start_page"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReadingState {
+    pub book_id: i32,
+    pub status: ReadingStatus,
+    pub current_page: Option<i32>,
+    pub total_pages: Option<i32>,
+    pub percent: Option<u8>,
+    pub started_at: Option<DateTimeUtc>,
+    pub finished_at: Option<DateTimeUtc>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReadingSession {
+    pub id: i32,
+    pub book_id: i32,
+    pub started_at: DateTimeUtc,
+    pub ended_at: DateTimeUtc,
+    pub start_page: Option<i32>,
+    pub end_page: Option<i32>,
+    pub minutes: i64,
+    pub pages: Option<i32>,
+}
+
+// a logged session and the state it left the book in
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSessionLogged {
+    pub session: ResReadingSession,
+    pub state: ResReadingState,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResCurrentBook {
+    pub book: ResBook,
+    pub state: ResReadingState,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResCurrentReading {
+    pub total: usize,
+    pub books: Vec<ResCurrentBook>,
+}
+
+impl ResReadingState {
+    pub fn new(state: &reading_state::Model, total_pages: Option<i32>) -> Self {
+        Self {
+            book_id: state.book_id,
+            status: ReadingStatus::parse(&state.status).unwrap_or(ReadingStatus::WantToRead),
+            current_page: state.current_page,
+            total_pages,
+            percent: state.percent,
+            started_at: state.started_at,
+            finished_at: state.finished_at,
+        }
+    }
+}
+
+impl From<&reading_session::Model> for ResReadingSession {
+    fn from(s: &reading_session::Model) -> Self {
+        Self {
+            id: s.id,
+            book_id: s.book_id,
+            started_at: s.started_at,
+            ended_at: s.ended_at,
+            start_page: s.start_page,
+            end_page: s.end_page,
+            minutes: (s.ended_at - s.started_at).num_minutes(),
+            pages: s.start_page.zip(s.end_page).map(|(start, end)| end - start),
+        }
+    }
+}
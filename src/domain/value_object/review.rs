@@ -0,0 +1,88 @@
+use rocket::{serde::{Deserialize, Serialize}, FromForm};
+use sea_orm::prelude::DateTimeUtc;
+use validator::Validate;
+
+use crate::domain::entities::review;
+
+use super::{pagination::ResPagination, validation::half_star_rating};
+
+// a rating of 1 to 5 stars kept in half stars
+pub fn half_stars(rating: f32) -> u8 {
+    (rating * 2.0).round() as u8
+}
+
+// the average and number of ratings of a book, no average without ratings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResRating {
+    pub average: Option<f64>,
+    pub count: i64,
+}
+
+impl ResRating {
+    pub fn new(half_stars_sum: i64, count: i64) -> Self {
+        Self {
+            // rounded to two decimals, e.g. 4.33
+            average: (count > 0).then(|| (half_stars_sum as f64 / count as f64 / 2.0 * 100.0).round() / 100.0),
+            count,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReview {
+    pub id: i32,
+    pub book_id: i32,
+    pub user_id: i32,
+    pub rating: f32,
+    pub text: String,
+    pub spoiler: bool,
+    pub created_at: Option<DateTimeUtc>,
+    pub updated_at: Option<DateTimeUtc>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReviewList {
+    pub total: usize,
+    pub rating: ResRating,
+    pub reviews: Vec<ResReview>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<ResPagination>,
+}
+
+// query string of the reviews of a book, the latest first
+#[derive(FromForm)]
+pub struct ReqReviewQuery {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqReview {
+    #[validate(custom(function = "half_star_rating"))]
+    pub rating: f32,
+    #[serde(default)]
+    #[validate(length(max = 10000, message = "must be at most 10000 characters"))]
+    pub text: String,
+    // the text gives away the plot
+    #[serde(default)]
+    pub spoiler: bool,
+}
+
+impl From<&review::Model> for ResReview {
+    fn from(r: &review::Model) -> Self {
+        Self {
+            id: r.id,
+            book_id: r.book_id,
+            user_id: r.user_id,
+            rating: r.half_stars as f32 / 2.0,
+            text: r.text.to_owned(),
+            spoiler: r.spoiler,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum SearchHitKind {
+    Book,
+    Author,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSearchHit {
+    pub kind: SearchHitKind,
+    pub id: i32,
+    // book title or author full name
+    pub title: String,
+    // title with matches wrapped in <mark>
+    pub title_highlight: String,
+    // part of the author bio around the first match
+    pub snippet: Option<String>,
+    pub score: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSearchList {
+    pub total: usize,
+    pub results: Vec<ResSearchHit>,
+}
@@ -0,0 +1,108 @@
+use rocket::{serde::{Deserialize, Serialize}, FromForm};
+use validator::Validate;
+
+use crate::domain::entities::series;
+
+use super::{book::ResBook, pagination::ResPagination, validation::not_blank};
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSeries {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSeriesList {
+    pub total: usize,
+    pub series: Vec<ResSeries>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<ResPagination>,
+}
+
+// query string of the series index, sorted by title
+#[derive(FromForm)]
+pub struct ReqSeriesQuery {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqSeries {
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub title: String,
+    #[serde(default)]
+    #[validate(length(max = 2000, message = "must be at most 2000 characters"))]
+    pub description: String,
+}
+
+// partial update, absent fields are left unchanged
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqSeriesPatch {
+    #[validate(custom(function = "not_blank"), length(max = 255, message = "must be at most 255 characters"))]
+    pub title: Option<String>,
+    #[validate(length(max = 2000, message = "must be at most 2000 characters"))]
+    pub description: Option<String>,
+}
+
+// a run of missing volumes, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResGap {
+    pub from: i32,
+    pub to: i32,
+}
+
+// the books of a series in reading order, unnumbered books last
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSeriesBooks {
+    pub series: ResSeries,
+    pub total: usize,
+    pub books: Vec<ResBook>,
+    // volumes missing before the last numbered one
+    pub gaps: Vec<ResGap>,
+    // numbers held by more than one book
+    pub duplicates: Vec<i32>,
+}
+
+// the missing numbers from volume 1 up to the highest one given
+pub fn gaps(indexes: &[i32]) -> Vec<ResGap> {
+    let mut indexes = indexes.to_vec();
+    indexes.sort_unstable();
+    indexes.dedup();
+
+    let mut gaps = Vec::new();
+    let mut next = 1;
+    for index in indexes {
+        if index > next {
+            gaps.push(ResGap { from: next, to: index - 1 });
+        }
+        next = next.max(index + 1);
+    }
+    gaps
+}
+
+// numbers given to more than one book, in order
+pub fn duplicates(indexes: &[i32]) -> Vec<i32> {
+    let mut indexes = indexes.to_vec();
+    indexes.sort_unstable();
+
+    let mut duplicates: Vec<i32> = indexes.windows(2).filter(|w| w[0] == w[1]).map(|w| w[0]).collect();
+    duplicates.dedup();
+    duplicates
+}
+
+impl From<&series::Model> for ResSeries {
+    fn from(s: &series::Model) -> Self {
+        Self {
+            id: s.id,
+            title: s.title.to_owned(),
+            description: s.description.to_owned(),
+        }
+    }
+}
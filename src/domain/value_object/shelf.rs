@@ -0,0 +1,46 @@
+use rocket::serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::domain::entities::shelf;
+
+use super::validation::not_blank;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResShelf {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub position: i32,
+}
+
+// all the user's shelves in their order
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResShelfList {
+    pub total: usize,
+    pub shelves: Vec<ResShelf>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqShelf {
+    #[validate(custom(function = "not_blank"), length(max = 100, message = "must be at most 100 characters"))]
+    pub name: String,
+    #[validate(length(max = 2000, message = "must be at most 2000 characters"))]
+    pub description: Option<String>,
+    // a new shelf goes last and a moved one keeps its place when absent
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub position: Option<i32>,
+}
+
+impl From<&shelf::Model> for ResShelf {
+    fn from(s: &shelf::Model) -> Self {
+        Self {
+            id: s.id,
+            name: s.name.to_owned(),
+            description: s.description.to_owned(),
+            position: s.position,
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{Datelike, NaiveDate};
+use rocket::{serde::{Deserialize, Serialize}, FromForm};
+use validator::Validate;
+
+use crate::{domain::entities::reading_goal, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::review::ResRating;
+
+pub const TOP_AUTHORS: u64 = 5;
+
+// a year the statistics can be asked for
+pub fn check_year(year: i32) -> Result<i32, AppError> {
+    if !(1..=9999).contains(&year) {
+        return Err(AppError::invalid_field("year", "must be between 1 and 9999"));
+    }
+    Ok(year)
+}
+
+// query string of the statistics, the current year by default
+#[derive(FromForm)]
+pub struct ReqStatsQuery {
+    pub year: Option<i32>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqReadingGoal {
+    #[validate(range(min = 1, max = 10000, message = "must be between 1 and 10000"))]
+    pub target_books: i32,
+    #[validate(range(min = 1, max = 10000000, message = "must be between 1 and 10000000"))]
+    pub target_pages: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReadingGoal {
+    pub year: i32,
+    pub target_books: i32,
+    pub target_pages: Option<i32>,
+}
+
+// books finished in a year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResYearCount {
+    pub year: i32,
+    pub books: i64,
+}
+
+// books finished in a month of the asked year
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResMonthCount {
+    pub month: u32,
+    pub books: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResAuthorCount {
+    pub author_id: i32,
+    pub firstname: String,
+    pub lastname: String,
+    pub books: i64,
+}
+
+// where the year stands against its goal
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResGoalProgress {
+    pub target_books: i32,
+    pub target_pages: Option<i32>,
+    pub books_read: i64,
+    pub pages_read: i64,
+    // may pass 100 once the goal is beaten
+    pub percent: i64,
+    // the books the goal asks for by the given day
+    pub expected_books: i64,
+    pub on_track: bool,
+}
+
+impl ResGoalProgress {
+    pub fn new(goal: &reading_goal::Model, books_read: i64, pages_read: i64, today: NaiveDate) -> Self {
+        let target = goal.target_books as i64;
+        // the share of the year gone by, all of a past year and none of a future one
+        let elapsed = match goal.year.cmp(&today.year()) {
+            std::cmp::Ordering::Less => 1.0,
+            std::cmp::Ordering::Greater => 0.0,
+            std::cmp::Ordering::Equal => {
+                let days = if today.leap_year() { 366.0 } else { 365.0 };
+                today.ordinal() as f64 / days
+            }
+        };
+        let expected_books = (target as f64 * elapsed).floor() as i64;
+
+        Self {
+            target_books: goal.target_books,
+            target_pages: goal.target_pages,
+            books_read,
+            pages_read,
+            percent: books_read * 100 / target.max(1),
+            expected_books,
+            on_track: books_read >= expected_books,
+        }
+    }
+}
+
+// finished books count, sessions give the pages
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResStats {
+    pub year: i32,
+    pub books_per_year: Vec<ResYearCount>,
+    // the twelve months of the year, empty ones included
+    pub books_per_month: Vec<ResMonthCount>,
+    pub pages_read: i64,
+    pub sessions: i64,
+    // the ratings the user gave
+    pub rating: ResRating,
+    pub top_authors: Vec<ResAuthorCount>,
+    pub goal: Option<ResGoalProgress>,
+}
+
+impl From<&reading_goal::Model> for ResReadingGoal {
+    fn from(g: &reading_goal::Model) -> Self {
+        Self {
+            year: g.year,
+            target_books: g.target_books,
+            target_pages: g.target_pages,
+        }
+    }
+}
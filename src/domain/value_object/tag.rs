@@ -0,0 +1,68 @@
+use rocket::{serde::{Deserialize, Serialize}, FromForm};
+use validator::Validate;
+
+use super::validation::{valid_tag, valid_tags};
+
+pub const TAG_SUGGESTIONS_DEFAULT: u64 = 10;
+pub const TAG_SUGGESTIONS_MAX: u64 = 50;
+
+// tags compare lowercased with single spaces, "Science  Fiction " is "science fiction"
+pub fn normalize_tag(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+// the distinct normalized names in the given order
+pub fn normalize_tags(names: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    names
+        .iter()
+        .map(|n| normalize_tag(n))
+        .filter(|n| seen.insert(n.clone()))
+        .collect()
+}
+
+// a tag with the number of the user's books carrying it
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTag {
+    pub id: i32,
+    pub name: String,
+    pub books: i64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTagList {
+    pub total: usize,
+    pub tags: Vec<ResTag>,
+}
+
+// query string of the autocomplete, the most used tags first
+#[derive(FromForm)]
+pub struct ReqTagQuery {
+    // the start of the name
+    pub q: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqTag {
+    #[validate(custom(function = "valid_tag"))]
+    pub name: String,
+}
+
+// the whole set of a book's tags, replacing the previous one
+#[derive(Deserialize, Validate)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBookTags {
+    #[validate(custom(function = "valid_tags"))]
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResBookTags {
+    pub book_id: i32,
+    pub tags: Vec<String>,
+}
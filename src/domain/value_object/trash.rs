@@ -0,0 +1,44 @@
+use rocket::serde::Serialize;
+use sea_orm::prelude::DateTimeUtc;
+
+use super::{author::ResAuthor, book::ResBook, cover::CoverRef};
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTrashedBook {
+    #[serde(flatten)]
+    pub book: ResBook,
+    pub deleted_at: DateTimeUtc,
+    // when the purge task removes it for good
+    pub purge_at: DateTimeUtc,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTrashedAuthor {
+    #[serde(flatten)]
+    pub author: ResAuthor,
+    pub deleted_at: DateTimeUtc,
+    pub purge_at: DateTimeUtc,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTrash {
+    pub books: Vec<ResTrashedBook>,
+    pub authors: Vec<ResTrashedAuthor>,
+}
+
+// rows removed by one purge
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PurgeCount {
+    pub books: u64,
+    pub authors: u64,
+}
+
+// what one purge removed from the database
+pub struct Purged {
+    pub count: PurgeCount,
+    // the covers of the purged books by book id, their blobs are deleted after the rows
+    pub covers: Vec<(i32, CoverRef)>,
+}
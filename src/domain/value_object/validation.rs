@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+
+use validator::ValidationError;
+
+use super::{contributor::ReqContributor, isbn::Isbn, published_on::{LegacyYear, PublishedOn, ReqPublishedOn}, tag::normalize_tag};
+
+pub const PASSWORD_MIN_CHARS: usize = 8;
+pub const BATCH_MAX_ITEMS: usize = 100;
+pub const CONTRIBUTORS_MAX: usize = 20;
+pub const TAG_MAX_CHARS: usize = 50;
+pub const TAGS_MAX: usize = 50;
+
+fn invalid(code: &'static str, message: &'static str) -> ValidationError {
+    ValidationError::new(code).with_message(Cow::Borrowed(message))
+}
+
+// rejects empty strings and strings of only whitespace
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(invalid("blank", "must not be blank"));
+    }
+    Ok(())
+}
+
+// at least 8 characters with a letter and a digit
+pub fn password_policy(value: &str) -> Result<(), ValidationError> {
+    if value.chars().count() < PASSWORD_MIN_CHARS {
+        return Err(invalid("password_too_short", "must be at least 8 characters long"));
+    }
+    if !value.chars().any(char::is_alphabetic) || !value.chars().any(|c| c.is_ascii_digit()) {
+        return Err(invalid("password_too_weak", "must contain a letter and a digit"));
+    }
+    Ok(())
+}
+
+// a date between year 1 and next year, with a month and day that exist
+pub fn valid_published_on(value: &ReqPublishedOn) -> Result<(), ValidationError> {
+    value
+        .to_published_on()
+        .map(|_| ())
+        .map_err(|e| invalid(e.code(), e.message()))
+}
+
+// the same as text, e.g. "1968", "1968-05-12" or "c. 1900"
+pub fn published_on_text(value: &str) -> Result<(), ValidationError> {
+    match PublishedOn::parse(value) {
+        Some(_) => Ok(()),
+        None => Err(invalid("invalid_date", "must be a year, a year-month, a year-month-day or c. and a year")),
+    }
+}
+
+// blank, or a date `published_on_text` reads
+pub fn legacy_year(value: &LegacyYear) -> Result<(), ValidationError> {
+    if value.is_blank() || value.to_req_published_on().is_some() {
+        return Ok(());
+    }
+    Err(invalid("invalid_date", "must be a year, a year-month, a year-month-day or c. and a year"))
+}
+
+// between 1 and 100 items in one request
+pub fn batch_size<T>(items: &[T]) -> Result<(), ValidationError> {
+    if items.is_empty() || items.len() > BATCH_MAX_ITEMS {
+        return Err(invalid("batch_size", "must hold between 1 and 100 items"));
+    }
+    Ok(())
+}
+
+// an ISBN-10 or ISBN-13 with a valid check digit
+pub fn valid_isbn(value: &str) -> Result<(), ValidationError> {
+    Isbn::parse(value)
+        .map(|_| ())
+        .map_err(|e| invalid(e.code(), e.message()))
+}
+
+// at most 20, and an author takes a role once
+pub fn unique_contributors(contributors: &[ReqContributor]) -> Result<(), ValidationError> {
+    if contributors.len() > CONTRIBUTORS_MAX {
+        return Err(invalid("too_many_contributors", "must hold at most 20 contributors"));
+    }
+    let mut seen = std::collections::HashSet::new();
+    if !contributors.iter().all(|c| seen.insert((c.author_id, c.role))) {
+        return Err(invalid("duplicate_contributor", "must not give an author the same role twice"));
+    }
+    Ok(())
+}
+
+// from 1 to 5 stars, in steps of half a star
+pub fn half_star_rating(value: f32) -> Result<(), ValidationError> {
+    if !(1.0..=5.0).contains(&value) || (value * 2.0).fract() != 0.0 {
+        return Err(invalid("invalid_rating", "must be between 1 and 5 in steps of 0.5"));
+    }
+    Ok(())
+}
+
+// not blank and at most 50 characters once normalized
+pub fn valid_tag(value: &str) -> Result<(), ValidationError> {
+    let name = normalize_tag(value);
+    if name.is_empty() {
+        return Err(invalid("blank", "must not be blank"));
+    }
+    if name.chars().count() > TAG_MAX_CHARS {
+        return Err(invalid("tag_too_long", "must be at most 50 characters"));
+    }
+    Ok(())
+}
+
+// at most 50 tags, each a valid tag, the first invalid one is reported
+pub fn valid_tags(values: &[String]) -> Result<(), ValidationError> {
+    if values.len() > TAGS_MAX {
+        return Err(invalid("too_many_tags", "must hold at most 50 tags"));
+    }
+    values.iter().try_for_each(|v| valid_tag(v))
+}
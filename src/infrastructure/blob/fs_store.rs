@@ -0,0 +1,79 @@
+use std::{io::ErrorKind, path::{Path, PathBuf}};
+
+use rocket::{async_trait, tokio::fs};
+
+use crate::{domain::{repositories::blob_store::BlobStore, value_object::cover::Blob}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+// the content type is kept next to each file
+const TYPE_SUFFIX: &str = ".content-type";
+
+// blobs as files under a root directory
+pub struct FsBlobStore {
+    root: PathBuf,
+}
+
+impl FsBlobStore {
+    pub fn new(root: &str) -> Self {
+        Self { root: PathBuf::from(root) }
+    }
+
+    // keys never leave the root
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        let segments: Vec<&str> = key.split('/').collect();
+        if segments.iter().any(|s| s.is_empty() || *s == "." || *s == ".." || s.contains('\\')) {
+            return Err(AppError::Internal(format!("invalid blob key {}", key)));
+        }
+        Ok(segments.iter().fold(self.root.clone(), |path, segment| path.join(segment)))
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Internal(format!("blob storage: {}", e))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[async_trait]
+impl BlobStore for FsBlobStore {
+    async fn put(&self, key: &str, blob: Blob) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+
+        // written aside then renamed, readers never see half a file
+        let partial = with_suffix(&path, ".partial");
+        fs::write(&partial, &blob.bytes).await.map_err(io_error)?;
+        fs::write(with_suffix(&path, TYPE_SUFFIX), blob.content_type.as_bytes()).await.map_err(io_error)?;
+        fs::rename(&partial, &path).await.map_err(io_error)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, AppError> {
+        let path = self.path(key)?;
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        };
+        let content_type = fs::read_to_string(with_suffix(&path, TYPE_SUFFIX))
+            .await
+            .unwrap_or_else(|_| "application/octet-stream".to_string());
+
+        Ok(Some(Blob { content_type, bytes }))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        for file in [with_suffix(&path, TYPE_SUFFIX), path] {
+            match fs::remove_file(&file).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(io_error(e)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::{config::blob_config::BlobConfig, domain::repositories::blob_store::BlobStore};
+
+use self::{fs_store::FsBlobStore, s3_store::S3BlobStore};
+
+pub mod fs_store;
+pub mod s3_store;
+
+pub fn blob_store(config: &BlobConfig) -> Arc<dyn BlobStore + Send + Sync> {
+    match config.backend.as_str() {
+        "s3" => Arc::new(S3BlobStore::new(
+            &config.s3_endpoint,
+            &config.s3_bucket,
+            &config.s3_region,
+            &config.s3_access_key,
+            &config.s3_secret_key,
+        )),
+        "fs" => Arc::new(FsBlobStore::new(&config.dir)),
+        other => panic!("unknown blob backend {}, expected fs or s3", other),
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use hyper::{body, client::HttpConnector, header::CONTENT_TYPE, Body, Client, Method, Request, StatusCode};
+use rocket::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::{domain::{repositories::blob_store::BlobStore, value_object::cover::Blob}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+// every byte but the unreserved ones is percent-encoded, `/` is kept between segments
+pub fn uri_encode(path: &str) -> String {
+    path.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// the AWS Signature Version 4 `Authorization` header of a request,
+// `headers` are the signed ones with lowercase names
+#[allow(clippy::too_many_arguments)]
+pub fn sign_v4(
+    method: &str,
+    uri: &str,
+    headers: &BTreeMap<String, String>,
+    payload_hash: &str,
+    amz_date: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+
+    let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v.trim())).collect();
+    let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method, uri, canonical_headers, signed_headers, payload_hash
+    );
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        sha256_hex(canonical_request.as_bytes())
+    );
+
+    let key = [date, region, "s3", "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", secret_key).into_bytes(), |key, part| hmac(&key, part));
+    let signature = hex::encode(hmac(&key, &string_to_sign));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{},SignedHeaders={},Signature={}",
+        access_key, scope, signed_headers, signature
+    )
+}
+
+// S3 or a compatible server, with path-style URLs
+pub struct S3BlobStore {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    client: Client<HttpConnector>,
+}
+
+impl S3BlobStore {
+    pub fn new(endpoint: &str, bucket: &str, region: &str, access_key: &str, secret_key: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    async fn send(&self, method: Method, key: &str, blob: Option<Blob>) -> Result<hyper::Response<Body>, AppError> {
+        let uri = uri_encode(&format!("/{}/{}", self.bucket, key));
+        let host = self.endpoint.split("://").nth(1).unwrap_or(&self.endpoint).to_string();
+        let (content_type, bytes) = match blob {
+            Some(blob) => (Some(blob.content_type), blob.bytes),
+            None => (None, Vec::new()),
+        };
+
+        let payload_hash = sha256_hex(&bytes);
+        let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let headers = BTreeMap::from([
+            ("host".to_string(), host),
+            ("x-amz-content-sha256".to_string(), payload_hash.to_owned()),
+            ("x-amz-date".to_string(), amz_date.to_owned()),
+        ]);
+        let authorization = sign_v4(
+            method.as_str(),
+            &uri,
+            &headers,
+            &payload_hash,
+            &amz_date,
+            &self.region,
+            &self.access_key,
+            &self.secret_key,
+        );
+
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.endpoint, uri))
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization);
+        if let Some(content_type) = content_type {
+            request = request.header(CONTENT_TYPE, content_type);
+        }
+        let request = request
+            .body(Body::from(bytes))
+            .map_err(|e| AppError::Internal(format!("blob storage request: {}", e)))?;
+
+        self.client
+            .request(request)
+            .await
+            .map_err(|e| AppError::Upstream(format!("blob storage: {}", e)))
+    }
+}
+
+fn unexpected(status: StatusCode) -> AppError {
+    AppError::Upstream(format!("blob storage answered {}", status))
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, blob: Blob) -> Result<(), AppError> {
+        let res = self.send(Method::PUT, key, Some(blob)).await?;
+        match res.status() {
+            status if status.is_success() => Ok(()),
+            status => Err(unexpected(status)),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Blob>, AppError> {
+        let res = self.send(Method::GET, key, None).await?;
+        match res.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                let content_type = res
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let bytes = body::to_bytes(res.into_body())
+                    .await
+                    .map_err(|e| AppError::Upstream(format!("blob storage: {}", e)))?;
+                Ok(Some(Blob { content_type, bytes: bytes.to_vec() }))
+            }
+            status => Err(unexpected(status)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let res = self.send(Method::DELETE, key, None).await?;
+        match res.status() {
+            status if status.is_success() || status == StatusCode::NOT_FOUND => Ok(()),
+            status => Err(unexpected(status)),
+        }
+    }
+}
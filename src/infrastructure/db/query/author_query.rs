@@ -0,0 +1,40 @@
+use sea_orm::{sea_query::SimpleExpr, ColumnTrait, EntityTrait, IntoSimpleExpr, Order, QueryFilter, QueryOrder, Select};
+
+use crate::domain::{
+    entities::author,
+    value_object::{author::{AuthorSort, ReqAuthorQuery}, pagination::SortOrder},
+};
+
+use super::search_query::{author_match, boolean_query, search_terms};
+
+// the user's authors, sorted as requested
+pub fn authors_query(user_id: i32, query: &ReqAuthorQuery) -> Select<author::Entity> {
+    let mut select = author::Entity::find()
+        .filter(author::Column::UserId.eq(user_id))
+        .filter(author::Column::DeletedAt.is_null());
+
+    let search = query.q.as_deref().map(search_terms).as_deref().and_then(boolean_query);
+    if let Some(search) = &search {
+        select = select.filter(author_match(search));
+    }
+
+    let order = match query.order {
+        Some(SortOrder::Asc) => Order::Asc,
+        Some(SortOrder::Desc) => Order::Desc,
+        // newest first by default, alphabetical for names
+        None if matches!(query.sort, Some(AuthorSort::Firstname | AuthorSort::Lastname)) => Order::Asc,
+        None => Order::Desc,
+    };
+
+    let column: SimpleExpr = match (query.sort, &search) {
+        // best matches first when searching without an explicit sort
+        (None, Some(search)) => author_match(search),
+        (None, None) | (Some(AuthorSort::UpdatedAt), _) => author::Column::UpdatedAt.into_simple_expr(),
+        (Some(AuthorSort::Firstname), _) => author::Column::Firstname.into_simple_expr(),
+        (Some(AuthorSort::Lastname), _) => author::Column::Lastname.into_simple_expr(),
+        (Some(AuthorSort::CreatedAt), _) => author::Column::CreatedAt.into_simple_expr(),
+    };
+
+    // id as tie breaker keeps pages stable
+    select.order_by(column, order.clone()).order_by(author::Column::Id, order)
+}
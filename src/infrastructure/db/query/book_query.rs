@@ -0,0 +1,113 @@
+use sea_orm::{
+    sea_query::{Expr, Query, SimpleExpr},
+    IntoSimpleExpr,
+    ColumnTrait, ConnectionTrait, EntityTrait, Order, QueryFilter, QueryOrder, Select, UpdateMany,
+};
+
+use crate::{
+    domain::{
+        entities::{book, book_shelf, book_tag, tag},
+        value_object::{book::{BookSort, ReqBookQuery}, pagination::SortOrder, tag::normalize_tag},
+    },
+    infrastructure::rocket_http::response_type::app_error::AppError,
+};
+
+use super::search_query::{book_match, boolean_query, search_terms};
+
+// a new version of the books to filter, for a change to what their answers embed: ratings, tags or contributor names
+pub fn bump_versions() -> UpdateMany<book::Entity> {
+    book::Entity::update_many().col_expr(book::Column::Version, Expr::col(book::Column::Version).add(1))
+}
+
+// a book among the user's own books, outside the trash
+pub fn own_book(user_id: i32, id: i32) -> Select<book::Entity> {
+    book::Entity::find_by_id(id)
+        .filter(book::Column::UserId.eq(user_id))
+        .filter(book::Column::DeletedAt.is_null())
+}
+
+// the user's book, not found when it is someone else's or in the trash
+pub async fn find_book<C: ConnectionTrait>(db: &C, user_id: i32, id: i32) -> Result<book::Model, AppError> {
+    own_book(user_id, id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::not_found("Book"))
+}
+
+// the user's books, filtered and sorted as requested
+pub fn books_query(user_id: i32, query: &ReqBookQuery) -> Select<book::Entity> {
+    let mut select = book::Entity::find()
+        .filter(book::Column::UserId.eq(user_id))
+        .filter(book::Column::DeletedAt.is_null());
+
+    if let Some(author_id) = query.author_id {
+        select = select.filter(book::Column::AuthorId.eq(author_id));
+    }
+
+    // books of an unknown date are left out of a year range
+    if let Some(year_from) = query.year_from {
+        select = select.filter(book::Column::PublishedYear.gte(year_from));
+    }
+    if let Some(year_to) = query.year_to {
+        select = select.filter(book::Column::PublishedYear.lte(year_to));
+    }
+
+    // the books stay the user's own, a foreign shelf or tag finds nothing
+    if let Some(shelf_id) = query.shelf {
+        select = select.filter(
+            book::Column::Id.in_subquery(
+                Query::select()
+                    .column(book_shelf::Column::BookId)
+                    .from(book_shelf::Entity)
+                    .and_where(book_shelf::Column::ShelfId.eq(shelf_id))
+                    .to_owned(),
+            ),
+        );
+    }
+    if let Some(name) = &query.tag {
+        select = select.filter(
+            book::Column::Id.in_subquery(
+                Query::select()
+                    .column(book_tag::Column::BookId)
+                    .from(book_tag::Entity)
+                    .inner_join(tag::Entity, tag::Column::Id.into_expr().equals((book_tag::Entity, book_tag::Column::TagId)))
+                    .and_where(tag::Column::UserId.eq(user_id))
+                    .and_where(tag::Column::Name.eq(normalize_tag(name)))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    let search = query.q.as_deref().map(search_terms).as_deref().and_then(boolean_query);
+    if let Some(search) = &search {
+        select = select.filter(book_match(search));
+    }
+
+    let order = match query.order {
+        Some(SortOrder::Asc) => Order::Asc,
+        Some(SortOrder::Desc) => Order::Desc,
+        // newest first by default, alphabetical for titles
+        None if query.sort == Some(BookSort::Title) => Order::Asc,
+        None => Order::Desc,
+    };
+
+    let column: SimpleExpr = match (query.sort, &search) {
+        // best matches first when searching without an explicit sort
+        (None, Some(search)) => book_match(search),
+        (None, None) | (Some(BookSort::UpdatedAt), _) => book::Column::UpdatedAt.into_simple_expr(),
+        (Some(BookSort::Title), _) => book::Column::Title.into_simple_expr(),
+        // by year, then month and day below
+        (Some(BookSort::Year), _) => book::Column::PublishedYear.into_simple_expr(),
+        (Some(BookSort::CreatedAt), _) => book::Column::CreatedAt.into_simple_expr(),
+    };
+
+    select = select.order_by(column, order.clone());
+    if query.sort == Some(BookSort::Year) {
+        select = select
+            .order_by(book::Column::PublishedMonth, order.clone())
+            .order_by(book::Column::PublishedDay, order.clone());
+    }
+
+    // id as tie breaker keeps pages stable
+    select.order_by(book::Column::Id, order)
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::domain::{entities::{author, book, book_contributor}, value_object::{book::ResBook, contributor::ResContributor}};
+
+use super::{review_query::ratings_of, tag_query::tags_of};
+
+// the contributors of the given books in their order, authors in the trash are left out
+pub async fn contributors_of<C: ConnectionTrait>(db: &C, book_ids: &[i32]) -> Result<HashMap<i32, Vec<ResContributor>>, DbErr> {
+    let mut contributors: HashMap<i32, Vec<ResContributor>> = HashMap::new();
+    if book_ids.is_empty() {
+        return Ok(contributors);
+    }
+
+    let rows = book_contributor::Entity::find()
+        .find_also_related(author::Entity)
+        .filter(book_contributor::Column::BookId.is_in(book_ids.iter().copied()))
+        .filter(author::Column::DeletedAt.is_null())
+        .order_by_asc(book_contributor::Column::BookId)
+        .order_by_asc(book_contributor::Column::Position)
+        .all(db)
+        .await?;
+
+    for (contributor, author) in rows {
+        if let Some(author) = author {
+            contributors
+                .entry(contributor.book_id)
+                .or_default()
+                .push(ResContributor::from((&contributor, &author)));
+        }
+    }
+
+    Ok(contributors)
+}
+
+// books as they are answered, with their contributors, ratings and tags read in one query each
+pub async fn res_books<C: ConnectionTrait>(db: &C, books: &[book::Model]) -> Result<Vec<ResBook>, DbErr> {
+    let ids: Vec<i32> = books.iter().map(|b| b.id).collect();
+    let mut contributors = contributors_of(db, &ids).await?;
+    let ratings = ratings_of(db, &ids).await?;
+    let mut tags = tags_of(db, &ids).await?;
+
+    Ok(books
+        .iter()
+        .map(|b| ResBook {
+            contributors: contributors.remove(&b.id).unwrap_or_default(),
+            rating: ratings.get(&b.id).copied().unwrap_or_default(),
+            tags: tags.remove(&b.id).unwrap_or_default(),
+            ..ResBook::from(b)
+        })
+        .collect())
+}
+
+pub async fn res_book<C: ConnectionTrait>(db: &C, book: &book::Model) -> Result<ResBook, DbErr> {
+    let mut books = res_books(db, std::slice::from_ref(book)).await?;
+    Ok(books.remove(0))
+}
@@ -0,0 +1,7 @@
+pub mod pagination;
+pub mod book_query;
+pub mod author_query;
+pub mod search_query;
+pub mod contributor_query;
+pub mod review_query;
+pub mod tag_query;
@@ -0,0 +1,58 @@
+use sea_orm::{ConnectionTrait, DbErr, Paginator, SelectorTrait};
+
+use crate::domain::value_object::pagination::ResPagination;
+
+const DEFAULT_PER_PAGE: u64 = 20;
+const MAX_PER_PAGE: u64 = 100;
+
+pub struct PageParams {
+    // 1-based
+    pub page: u64,
+    pub per_page: u64,
+}
+
+impl PageParams {
+    pub fn new(page: Option<u64>, per_page: Option<u64>) -> Self {
+        Self {
+            page: page.unwrap_or(1).max(1),
+            per_page: per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE),
+        }
+    }
+}
+
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub pagination: ResPagination,
+}
+
+// count the matching rows and fetch the requested page
+pub async fn fetch_page<'db, C, S>(
+    paginator: Paginator<'db, C, S>,
+    params: &PageParams,
+) -> Result<Page<S::Item>, DbErr>
+where
+    C: ConnectionTrait,
+    S: SelectorTrait + 'db,
+{
+    let counts = paginator.num_items_and_pages().await?;
+
+    // nothing to fetch past the last page
+    let items = if params.page <= counts.number_of_pages {
+        paginator.fetch_page(params.page - 1).await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Page {
+        items,
+        total: counts.number_of_items,
+        pagination: ResPagination {
+            page: params.page,
+            per_page: params.per_page,
+            total_pages: counts.number_of_pages,
+            next_page: (params.page < counts.number_of_pages).then_some(params.page + 1),
+            prev_page: (params.page > 1).then(|| (params.page - 1).min(counts.number_of_pages.max(1))),
+        },
+    })
+}
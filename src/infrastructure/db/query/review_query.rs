@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use sea_orm::{sea_query::{Alias, Expr}, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, QueryFilter, QuerySelect};
+
+use crate::domain::{entities::review, value_object::review::ResRating};
+
+#[derive(FromQueryResult)]
+struct RatingRow {
+    book_id: i32,
+    half_stars_sum: i64,
+    count: i64,
+}
+
+// the ratings of the given books in one grouped query, books without reviews are left out
+pub async fn ratings_of<C: ConnectionTrait>(db: &C, book_ids: &[i32]) -> Result<HashMap<i32, ResRating>, DbErr> {
+    if book_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    // summed as an integer, MySQL answers SUM and AVG as decimals
+    let rows = review::Entity::find()
+        .select_only()
+        .column(review::Column::BookId)
+        .column_as(Expr::col(review::Column::HalfStars).sum().cast_as(Alias::new("SIGNED")), "half_stars_sum")
+        .column_as(review::Column::Id.count(), "count")
+        .filter(review::Column::BookId.is_in(book_ids.iter().copied()))
+        .group_by(review::Column::BookId)
+        .into_model::<RatingRow>()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.book_id, ResRating::new(row.half_stars_sum, row.count)))
+        .collect())
+}
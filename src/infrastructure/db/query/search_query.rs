@@ -0,0 +1,100 @@
+use sea_orm::sea_query::{Expr, SimpleExpr};
+
+const MARK_OPEN: &str = "<mark>";
+const MARK_CLOSE: &str = "</mark>";
+
+// words of the user input, without any full-text operators
+pub fn search_terms(q: &str) -> Vec<String> {
+    q.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+// MySQL boolean mode query matching every word as a prefix
+pub fn boolean_query(terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    Some(terms.iter().map(|term| format!("{}*", term)).collect::<Vec<_>>().join(" "))
+}
+
+// relevance of a book, non-zero when it matches
+pub fn book_match(boolean_query: &str) -> SimpleExpr {
+    Expr::cust_with_values(
+        "MATCH (`book`.`title`) AGAINST (? IN BOOLEAN MODE)",
+        [boolean_query],
+    )
+}
+
+// relevance of an author, the columns must be those of the full-text index
+pub fn author_match(boolean_query: &str) -> SimpleExpr {
+    Expr::cust_with_values(
+        "MATCH (`author`.`firstname`, `author`.`lastname`, `author`.`bio`) AGAINST (? IN BOOLEAN MODE)",
+        [boolean_query],
+    )
+}
+
+// wrap every occurrence of the terms in <mark>, keeping at most `max_chars`
+// characters of `text` around the first match
+pub fn highlight(text: &str, terms: &[String], max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    // one lowercase char per char so indexes line up
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for term in terms {
+        let term: Vec<char> = term.chars().collect();
+        if term.is_empty() || term.len() > lower.len() {
+            continue;
+        }
+        for start in 0..=lower.len() - term.len() {
+            if lower[start..start + term.len()] == term[..] {
+                ranges.push((start, start + term.len()));
+            }
+        }
+    }
+    ranges.sort();
+
+    // merge overlapping matches
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let (from, to) = if chars.len() <= max_chars {
+        (0, chars.len())
+    } else {
+        let first = merged.first().map(|r| r.0).unwrap_or(0);
+        let from = first.saturating_sub(max_chars / 4).min(chars.len() - max_chars);
+        (from, from + max_chars)
+    };
+
+    let mut out = String::new();
+    if from > 0 {
+        out.push('…');
+    }
+    let mut pos = from;
+    for (start, end) in merged {
+        let (start, end) = (start.max(from), end.min(to));
+        if start >= end {
+            continue;
+        }
+        out.extend(&chars[pos..start]);
+        out.push_str(MARK_OPEN);
+        out.extend(&chars[start..end]);
+        out.push_str(MARK_CLOSE);
+        pos = end;
+    }
+    out.extend(&chars[pos..to]);
+    if to < chars.len() {
+        out.push('…');
+    }
+    out
+}
@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::domain::entities::{book_tag, tag};
+
+// the tag names of the given books, alphabetical
+pub async fn tags_of<C: ConnectionTrait>(db: &C, book_ids: &[i32]) -> Result<HashMap<i32, Vec<String>>, DbErr> {
+    let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+    if book_ids.is_empty() {
+        return Ok(tags);
+    }
+
+    let rows = book_tag::Entity::find()
+        .find_also_related(tag::Entity)
+        .filter(book_tag::Column::BookId.is_in(book_ids.iter().copied()))
+        .order_by_asc(tag::Column::Name)
+        .all(db)
+        .await?;
+
+    for (link, tag) in rows {
+        if let Some(tag) = tag {
+            tags.entry(link.book_id).or_default().push(tag.name);
+        }
+    }
+
+    Ok(tags)
+}
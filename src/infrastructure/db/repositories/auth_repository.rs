@@ -1,20 +1,24 @@
 
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use bcrypt::{hash, verify, DEFAULT_COST};
 use jsonwebtoken::{encode, EncodingKey, Header};
 use rocket::async_trait;
 use rocket::http::Status;
 use rocket::serde::json::Json;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use sea_orm::prelude::DateTimeUtc;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
 
 use crate::config::jwt_config::JwtSecret;
 use crate::domain::repositories::auth_repository::AuthRepository;
-use crate::domain::value_object::auth::{ReqSignIn, ReqSignUp, ResMe, ResSignIn};
+use crate::domain::value_object::auth::{ReqMePatch, ReqRefresh, ReqSignIn, ReqSignUp, ResMe, ResSignIn};
 use crate::infrastructure::rocket_http::jwt::jwt_claim::Claims;
+use crate::infrastructure::rocket_http::jwt::refresh_token as opaque_token;
 use crate::infrastructure::rocket_http::middleware::jwt_auth::AuthenticatedUser;
-use crate::infrastructure::rocket_http::response_type::custom_response::{ErrorResponse, Response, SuccessResponse};
-use crate::domain::entities::{prelude::*, user};
+use crate::infrastructure::rocket_http::response_type::app_error::AppError;
+use crate::infrastructure::rocket_http::response_type::custom_response::{Response, SuccessResponse};
+use crate::domain::entities::{prelude::*, refresh_token, user};
 pub struct AuthRepositoryImplSql {
     pub db_pool: Arc<DatabaseConnection>
 }
@@ -25,6 +29,69 @@ impl AuthRepositoryImplSql {
             db_pool
         }
     }
+
+    // store a new refresh token in `family_id` and sign a matching access token
+    async fn issue_tokens<C: ConnectionTrait>(
+        &self,
+        conn: &C,
+        user: &user::Model,
+        family_id: String
+    ) -> Result<ResSignIn, AppError> {
+        let jwt_config = JwtSecret::default();
+        let now = SystemTime::now();
+
+        let refresh_token = opaque_token::generate();
+        RefreshToken::insert(refresh_token::ActiveModel {
+            user_id: Set(user.id),
+            family_id: Set(family_id.to_owned()),
+            token_hash: Set(opaque_token::hash(&refresh_token)),
+            expires_at: Set(DateTimeUtc::from(now + Duration::from_secs(jwt_config.refresh_token_ttl))),
+            ..Default::default()
+        })
+        .exec(conn)
+        .await?;
+
+        let claims = Claims {
+            sub: user.id,
+            role: user.role.to_owned(),
+            sid: family_id,
+            exp: now
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_err(|e| AppError::Internal(format!("time error: {}", e)))?
+                .as_secs() + jwt_config.access_token_ttl,
+        };
+
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(jwt_config.jwt_secret.as_bytes())
+        )?;
+
+        Ok(ResSignIn {
+            token,
+            refresh_token,
+            expires_in: jwt_config.access_token_ttl
+        })
+    }
+
+    // revoke every token of a session
+    async fn revoke_family<C: ConnectionTrait>(&self, conn: &C, family_id: &str) -> Result<(), AppError> {
+        RefreshToken::update_many()
+            .col_expr(refresh_token::Column::RevokedAt, Expr::value(DateTimeUtc::from(SystemTime::now())))
+            .filter(refresh_token::Column::FamilyId.eq(family_id))
+            .filter(refresh_token::Column::RevokedAt.is_null())
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_refresh_token(&self, token: &str) -> Result<refresh_token::Model, AppError> {
+        RefreshToken::find()
+            .filter(refresh_token::Column::TokenHash.eq(opaque_token::hash(token)))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))
+    }
 }
 
 #[async_trait]
@@ -35,158 +102,173 @@ impl AuthRepository for AuthRepositoryImplSql {
         register_data: Json<ReqSignUp>
     )
     -> Response<Json<String>>
-{
-        let conn = Arc::clone(&self.db_pool);
-          // Start transaction
-    let txn = conn.begin().await.map_err(|e| ErrorResponse((
-        Status::InternalServerError,
-        format!("Database error: {}", e),
-    )))?;
-
-           // Check existing user
-    if let Ok(Some(_)) = User::find()
-    .filter(user::Column::Email.eq(&register_data.email))
-    .one(&*conn)
-    .await 
-{
-    return Err(ErrorResponse((
-        Status::UnprocessableEntity,
-        "An account exists with this email address".to_string(),
-    )));
-}
-    // Create user
-    match User::insert(user::ActiveModel {
-        email: Set(register_data.email.to_owned()),
-        password: Set(hash(&register_data.password, DEFAULT_COST).map_err(|e| ErrorResponse((
-            Status::InternalServerError,
-            format!("Password hashing error: {}", e),
-        )))?,),
-        firstname: Set(register_data.firstname.to_owned()),
-        lastname: Set(register_data.lastname.to_owned()),
-        ..Default::default()
-    })
-    .exec(&*conn)
-    .await
     {
-        Ok(_) => {
-            txn.commit().await.map_err(|e| ErrorResponse((
-                Status::InternalServerError,
-                format!("Transaction commit error: {}", e),
-            )))?;
-            
-            Ok(SuccessResponse((
-                Status::Created,
-                Json("Account created successfully!".to_string())
-            )))
+        let conn = Arc::clone(&self.db_pool);
+        // Start transaction
+        let txn = conn.begin().await?;
+
+        // Check existing user
+        let existing = User::find()
+            .filter(user::Column::Email.eq(&register_data.email))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            return Err(AppError::conflict("An account exists with this email address", None));
         }
-        Err(e) => Err(ErrorResponse((
-            Status::InternalServerError,
-            format!("Failed to create account: {}", e),
-        )))
-    }
-    }
 
+        // Create user
+        User::insert(user::ActiveModel {
+            email: Set(register_data.email.to_owned()),
+            password: Set(hash(&register_data.password, DEFAULT_COST)?),
+            firstname: Set(register_data.firstname.to_owned()),
+            lastname: Set(register_data.lastname.to_owned()),
+            ..Default::default()
+        })
+        .exec(&txn)
+        .await?;
 
+        txn.commit().await?;
 
+        Ok(SuccessResponse((
+            Status::Created,
+            Json("Account created successfully!".to_string())
+        )))
+    }
 
 
 
     // SIGN_IN == LOGIN
     async fn sign_in(&self, sign_in_data: Json<ReqSignIn>) -> Response<Json<ResSignIn>> {
         let conn = Arc::clone(&self.db_pool);
-        let jwt_config = JwtSecret::default();
 
-        // 1. Find user and handle database errors explicitly
+        // 1. Find user
         let user = User::find()
-        .filter(user::Column::Email.eq(&sign_in_data.email))
-        .one(&*conn)
-        .await
-        .map_err(|e| ErrorResponse((
-            Status::InternalServerError,
-            format!("Database error: {}", e)
-        )))?;
-
-    // 2. Check if user exists with better error message
-    let user = user.ok_or_else(|| ErrorResponse((
-        Status::Unauthorized,
-        "Email or password is incorrect".to_string()
-    )))?;
-
-    // 3. Verify password with proper error handling
-    let is_valid = verify(&sign_in_data.password, &user.password)
-        .map_err(|e| ErrorResponse((
-            Status::InternalServerError,
-            format!("Password verification error: {}", e)
-        )))?;
-
-    if !is_valid {
-        return Err(ErrorResponse((
-            Status::Unauthorized,
-            "Email or password is incorrect".to_string()
-        )));
-    }
+            .filter(user::Column::Email.eq(&sign_in_data.email))
+            .one(&*conn)
+            .await?;
+
+        // 2. Check if user exists, without telling which part was wrong
+        let user = user.ok_or_else(|| AppError::Unauthorized("Email or password is incorrect".to_string()))?;
+
+        // 3. Verify password
+        if !verify(&sign_in_data.password, &user.password)? {
+            return Err(AppError::Unauthorized("Email or password is incorrect".to_string()));
+        }
+
+        // 4. Start a new session and generate the token pair
+        let tokens = self.issue_tokens(&*conn, &user, opaque_token::generate_family_id()).await?;
 
-    // 4. Generate JWT token with error handling
-    let claims = Claims {
-        sub: user.id,
-        role: "user".to_string(),
-        exp: SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map_err(|e| ErrorResponse((
-                Status::InternalServerError,
-                format!("Time error: {}", e)
-            )))?
-            .as_secs() + 4 * 60 * 60,
-    };
-
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(jwt_config.jwt_secret.as_bytes())
-    ).map_err(|e| ErrorResponse((
-        Status::InternalServerError,
-        format!("Token generation error: {}", e)
-    )))?;
-
-    Ok(SuccessResponse((
-        Status::Ok,
-        Json(ResSignIn { token })
-    )))
-    
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(tokens)
+        )))
     }
 
 
 
     async fn me(&self, user: AuthenticatedUser) -> Response<Json<ResMe>> {
         let conn = Arc::clone(&self.db_pool);
-        
-        // Find user by ID and handle potential errors
+
+        // Find user by ID
         let user_data = User::find_by_id(user.id as i32)
             .one(&*conn)
-            .await
-            .map_err(|e| ErrorResponse((
-                Status::InternalServerError,
-                format!("Database error: {}", e)
-            )))?;
-    
-        // Check if user exists
-        let user_data = user_data.ok_or_else(|| ErrorResponse((
-            Status::NotFound,
-            "User not found".to_string()
-        )))?;
-    
+            .await?
+            .ok_or_else(|| AppError::not_found("User"))?;
+
         // Return user data
         Ok(SuccessResponse((
             Status::Ok,
-            Json(ResMe {
-                id: user_data.id as u32,
-                email: user_data.email,
-                firstname: user_data.firstname,
-                lastname: user_data.lastname
-            })
+            Json(ResMe::from(&user_data))
+        )))
+    }
+
+    async fn update_me(&self, user: AuthenticatedUser, profile: Json<ReqMePatch>) -> Response<Json<ResMe>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        let user_data = User::find_by_id(user.id as i32)
+            .one(&*conn)
+            .await?
+            .ok_or_else(|| AppError::not_found("User"))?;
+        let profile = profile.into_inner();
+
+        let mut user_active: user::ActiveModel = user_data.into();
+        // absent fields are kept, `null` clears them
+        if let Some(firstname) = profile.firstname {
+            user_active.firstname = Set(firstname);
+        }
+        if let Some(lastname) = profile.lastname {
+            user_active.lastname = Set(lastname);
+        }
+        user_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+
+        let updated_user = user_active.update(&*conn).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResMe::from(&updated_user))
+        )))
+    }
+
+    async fn refresh(&self, refresh_data: Json<ReqRefresh>) -> Response<Json<ResSignIn>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        let stored = self.find_refresh_token(&refresh_data.refresh_token).await?;
+
+        if stored.revoked_at.is_some() {
+            return Err(AppError::Unauthorized("Session has been revoked".to_string()));
+        }
+
+        // a rotated token presented again means it leaked, end the whole session
+        if stored.used_at.is_some() {
+            self.revoke_family(&*conn, &stored.family_id).await?;
+            return Err(AppError::Unauthorized("Refresh token reuse detected, session revoked".to_string()));
+        }
+
+        let now = DateTimeUtc::from(SystemTime::now());
+        if stored.expires_at <= now {
+            return Err(AppError::Unauthorized("Refresh token expired".to_string()));
+        }
+
+        let txn = conn.begin().await?;
+
+        // mark as used, only one concurrent refresh with the same token can win
+        let marked = RefreshToken::update_many()
+            .col_expr(refresh_token::Column::UsedAt, Expr::value(now))
+            .filter(refresh_token::Column::Id.eq(stored.id))
+            .filter(refresh_token::Column::UsedAt.is_null())
+            .exec(&txn)
+            .await?;
+
+        if marked.rows_affected != 1 {
+            drop(txn);
+            self.revoke_family(&*conn, &stored.family_id).await?;
+            return Err(AppError::Unauthorized("Refresh token reuse detected, session revoked".to_string()));
+        }
+
+        let user = User::find_by_id(stored.user_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        let tokens = self.issue_tokens(&txn, &user, stored.family_id).await?;
+
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(tokens)
         )))
     }
 
+    async fn sign_out(&self, refresh_data: Json<ReqRefresh>) -> Response<String> {
+        let conn = Arc::clone(&self.db_pool);
 
-    
-}
\ No newline at end of file
+        let stored = self.find_refresh_token(&refresh_data.refresh_token).await?;
+        self.revoke_family(&*conn, &stored.family_id).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Signed out successfully".to_string()
+        )))
+    }
+}
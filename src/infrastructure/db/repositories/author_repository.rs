@@ -1,10 +1,24 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 
-use rocket::{async_trait, http::Status, serde::json::Json};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryOrder, Set};
+use rocket::{async_trait, http::Status, serde::json::{json, Json}};
+use sea_orm::{prelude::DateTimeUtc, sea_query::{Expr, Query}, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait};
 
-use crate::{domain::{entities::author, entities::book, repositories::author_repository::AuthorRepository, value_object::{author::{ReqAuthor, ResAuthor, ResAuthorList}, book::{ResBook, ResBookList}}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::{ErrorResponse, Response, SuccessResponse}}};
+use crate::{domain::{entities::{author, book, book_contributor}, repositories::author_repository::AuthorRepository, value_object::{author::{OnBooks, ReqAuthor, ReqAuthorDelete, ReqAuthorPatch, ReqAuthorQuery, ResAuthor, ResAuthorList}, book::ResBookList, contributor::ContributorRole}}, infrastructure::{db::query::{author_query::authors_query, book_query::bump_versions, contributor_query::res_books, pagination::{fetch_page, PageParams}}, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse, Tagged}}}}};
 use crate::domain::entities::author::Entity as Author;
+
+// changes with every write of the author
+fn etag(author: &author::Model) -> String {
+    format!("\"{}-{}\"", author.id, author.version)
+}
+
+// the author tagged with its version
+fn tagged(author: &author::Model) -> Tagged<Json<ResAuthor>> {
+    Tagged {
+        etag: etag(author),
+        body: Some(Json(ResAuthor::from(author))),
+    }
+}
+
 pub struct AuthorRepositoryImplSql {
     pub db_pool: Arc<DatabaseConnection>
 }
@@ -15,6 +29,69 @@ impl AuthorRepositoryImplSql {
             db_pool
         }
     }
+
+    // find an author among the user's own authors, outside the trash
+    async fn find_own(&self, user_id: i32, id: i32) -> Result<author::Model, AppError> {
+        Author::find_by_id(id)
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_null())
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Author"))
+    }
+
+    // find an author among the user's trashed authors
+    async fn find_trashed(&self, user_id: i32, id: i32) -> Result<author::Model, AppError> {
+        Author::find_by_id(id)
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_not_null())
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Author not found in the trash".to_string()))
+    }
+
+    // write the author only if no other request changed it since it was read
+    async fn save_versioned<C: ConnectionTrait>(db: &C, current: &author::Model, mut author_active: author::ActiveModel) -> Result<author::Model, AppError> {
+        author_active.version = Set(current.version + 1);
+        author_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+
+        Author::update(author_active)
+            .filter(author::Column::Version.eq(current.version))
+            .exec(db)
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => AppError::PreconditionFailed(
+                    "The author was modified by another request".to_string()
+                ),
+                e => e.into(),
+            })
+    }
+
+    // the books answer with their contributors' names, so a rename is a new version of them
+    async fn save_renamed(&self, current: &author::Model, author_active: author::ActiveModel) -> Result<author::Model, AppError> {
+        let renamed = author_active.firstname.as_ref() != &current.firstname
+            || author_active.lastname.as_ref() != &current.lastname;
+
+        let txn = self.db_pool.begin().await?;
+        let updated_author = Self::save_versioned(&txn, current, author_active).await?;
+        if renamed {
+            bump_versions()
+                .filter(
+                    book::Column::Id.in_subquery(
+                        Query::select()
+                            .column(book_contributor::Column::BookId)
+                            .from(book_contributor::Entity)
+                            .and_where(book_contributor::Column::AuthorId.eq(current.id))
+                            .to_owned(),
+                    ),
+                )
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+
+        Ok(updated_author)
+    }
 }
 
 #[async_trait]
@@ -22,173 +99,252 @@ impl AuthorRepository for AuthorRepositoryImplSql {
     // get all author
     async fn index(
         &self,
-        _user: AuthenticatedUser
+        user: AuthenticatedUser,
+        query: ReqAuthorQuery
     )
     -> Response<Json<ResAuthorList>>
     {
         let conn = Arc::clone(&self.db_pool);
-        
-
-        // fetc all author from the database
-        match Author::find().order_by_desc(author::Column::UpdatedAt)
-                          .all(&*conn)
-                          .await {
-                            Ok(result) => {
-                                let authors: Vec<ResAuthor> = result.iter()
-                                                                    .map(ResAuthor::from)
-                                                                    .collect();
-                                // prepare restonse
-                                let result = ResAuthorList {
-                                    total: authors.len(),
-                                    authors
-                                };
-
-                                // return response
-                                Ok(SuccessResponse((
-                                    Status::Ok,
-                                    Json(result)
-                                )))
-                            },
-                            Err(_) => {
-                                // handle database error
-                                Err(ErrorResponse((Status::InternalServerError, "Internal Error".to_string())))
-                            }
-    }
+
+        let params = PageParams::new(query.page, query.per_page);
+
+        // fetc one page of author from the database
+        let page = fetch_page(
+            authors_query(user.id as i32, &query).paginate(&*conn, params.per_page),
+            &params
+        )
+        .await?;
+
+        let authors: Vec<ResAuthor> = page.items.iter()
+                                            .map(ResAuthor::from)
+                                            .collect();
+        // prepare restonse
+        let result = ResAuthorList {
+            total: page.total as usize,
+            authors,
+            pagination: Some(page.pagination)
+        };
+
+        // return response
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(result)
+        )))
     }
 
     // show author by id
     async fn show(
-        &self,_user: AuthenticatedUser, 
-        id: i32
+        &self,user: AuthenticatedUser,
+        id: i32,
+        preconditions: Preconditions
     )
-    -> Response<Json<ResAuthor>>
+    -> Response<Tagged<Json<ResAuthor>>>
     {
-        let conn = Arc::clone(&self.db_pool);
+        let the_author = self.find_own(user.id as i32, id).await?;
 
-        let author = Author::find_by_id(id).one(&*conn).await;
-        match author {
-            Ok(Some(the_author)) => {
-                Ok(SuccessResponse((
-                    Status::Ok,
-                    Json(ResAuthor::from(&the_author))
-                )))
-            }
-            Ok(None) => {
-                Err(ErrorResponse((
-                    Status::NotFound,
-                    "no author found with the specified Id".to_string()
-                )))
-            }
-            Err(_) => {
-                Err(ErrorResponse((
-                    Status::InternalServerError,
-                    "Internal Error please try again".to_string()
-                )))
-            }
+        // the client copy is still current
+        if preconditions.not_modified(&etag(&the_author)) {
+            return Ok(SuccessResponse((
+                Status::NotModified,
+                Tagged { etag: etag(&the_author), body: None },
+            )));
         }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&the_author)
+        )))
     }
 
     async fn update(
-        &self,_user: AuthenticatedUser, 
+        &self,user: AuthenticatedUser,
         id: i32,
-        req_author: Json<ReqAuthor>
+        req_author: Json<ReqAuthor>,
+        preconditions: Preconditions
     )
-    -> Response<Json<ResAuthor>>
+    -> Response<Tagged<Json<ResAuthor>>>
     {
-        let conn = Arc::clone(&self.db_pool);
+        // find the author with specific id, only among the user's own authors
+        let author = self.find_own(user.id as i32, id).await?;
+        preconditions.check_match(&etag(&author))?;
 
-        // find the author with specific id
-        let author_result = Author::find_by_id(id).one(&*conn).await;
-
-        match author_result {
-            Ok(Some(author)) => {
-                // get book model
-                let mut author_active: author::ActiveModel = author.into();
-                // update field
-                author_active.firstname = Set(req_author.firstname.to_string());
-                author_active.lastname = Set(req_author.lastname.to_string());
-                author_active.bio = Set(req_author.bio.to_string());
-                // save update
-                match author_active.update(&*conn).await {
-                   Ok(updated_author) => {
-                    Ok(SuccessResponse((Status::Ok,Json(ResAuthor::from(&updated_author)))))
-                   },
-                   Err(_) => {
-                    Err(ErrorResponse((
-                        Status::InternalServerError,
-                        "Failed to update author".to_string()
-                    )))
-                   }
-                }
-            }
-            Ok(None) => {
-                Err(ErrorResponse((
-                    Status::NotFound,
-                    "Author not found".to_string()
-                )))
-            }
-            Err(_) => {
-                Err(ErrorResponse((
-                    Status::InternalServerError,
-                    "Database error".to_string()
-                )))
-            }
+        // get book model
+        let mut author_active: author::ActiveModel = author.clone().into();
+        // update field
+        author_active.firstname = Set(req_author.firstname.to_string());
+        author_active.lastname = Set(req_author.lastname.to_string());
+        author_active.bio = Set(req_author.bio.to_string());
+        // save update
+        let updated_author = self.save_renamed(&author, author_active).await?;
+
+        Ok(SuccessResponse((Status::Ok, tagged(&updated_author))))
+    }
+
+    async fn patch(
+        &self,user: AuthenticatedUser,
+        id: i32,
+        req_author: Json<ReqAuthorPatch>,
+        preconditions: Preconditions
+    )
+    -> Response<Tagged<Json<ResAuthor>>>
+    {
+        let author = self.find_own(user.id as i32, id).await?;
+        preconditions.check_match(&etag(&author))?;
+        let req_author = req_author.into_inner();
+
+        let mut author_active: author::ActiveModel = author.clone().into();
+        // only the provided fields are written
+        if let Some(firstname) = req_author.firstname {
+            author_active.firstname = Set(firstname);
+        }
+        if let Some(lastname) = req_author.lastname {
+            author_active.lastname = Set(lastname);
         }
+        if let Some(bio) = req_author.bio {
+            author_active.bio = Set(bio);
+        }
+
+        let updated_author = self.save_renamed(&author, author_active).await?;
+
+        Ok(SuccessResponse((Status::Ok, tagged(&updated_author))))
     }
 
     async fn delete(
-        &self,_user: AuthenticatedUser,
+        &self,user: AuthenticatedUser,
         id: i32,
+        query: ReqAuthorDelete,
+        preconditions: Preconditions
     )
     -> Response<String>
     {
         // get connection
         let conn = Arc::clone(&self.db_pool);
 
-        // find the author wiht this id
-        match Author::find_by_id(id).one(&*conn).await {
-            Ok(Some(author)) => {
-                // delete author
-                match author.delete(&*conn).await {
-                    Ok(res) => {
-                        if res.rows_affected == 1 {
-                            Ok(SuccessResponse((
-                                Status::Ok,
-                                "Author successfully deleted".to_string()
-                            )))
-                        } else {
-                            Err(ErrorResponse((
-                                Status::InternalServerError,
-                                "Failed to delete book".to_string()
-                            )))
-                        }
-                    }
-                    Err(_) => {
-                        Err(ErrorResponse((
-                            Status::InternalServerError,
-                            "Error occurred while deleting author".to_string()
-                        )))
-                    }
+        // find the author wiht this id, only among the user's own authors
+        let author = self.find_own(user.id as i32, id).await?;
+        preconditions.check_match(&etag(&author))?;
+
+        let now = DateTimeUtc::from(SystemTime::now());
+        let txn = conn.begin().await?;
+
+        // the author's live books
+        let books = book::Entity::update_many()
+            .col_expr(book::Column::Version, Expr::col(book::Column::Version).add(1))
+            .filter(book::Column::AuthorId.eq(author.id))
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null());
+
+        match query.on_books {
+            OnBooks::Reject => {
+                let book_ids: Vec<i32> = book::Entity::find()
+                    .select_only()
+                    .column(book::Column::Id)
+                    .filter(book::Column::AuthorId.eq(author.id))
+                    .filter(book::Column::UserId.eq(user.id as i32))
+                    .filter(book::Column::DeletedAt.is_null())
+                    .order_by_asc(book::Column::Id)
+                    .into_tuple()
+                    .all(&txn)
+                    .await?;
+
+                if !book_ids.is_empty() {
+                    return Err(AppError::conflict(
+                        "The author still has books",
+                        Some(json!({ "book_ids": book_ids })),
+                    ));
                 }
             }
-            Ok(None) => {
-                Err(ErrorResponse((
-                    Status::NotFound,
-                    "Author not found with specific id".to_string()
-                )))
+            // the author and its books go to the trash together, with the same time
+            OnBooks::Cascade => {
+                books.col_expr(book::Column::DeletedAt, Expr::value(now)).exec(&txn).await?;
             }
-            
-            Err(_) => {
-                Err(ErrorResponse((
-                    Status::InternalServerError,
-                    "Database error".to_string()
-                )))
+            OnBooks::Reassign => {
+                let to = query.to.ok_or_else(|| AppError::invalid_field("to", "is required to reassign the books"))?;
+                if to == author.id {
+                    return Err(AppError::invalid_field("to", "must be another author"));
+                }
+
+                // the new author must be another live author of the user
+                let target = Author::find_by_id(to)
+                    .filter(author::Column::UserId.eq(user.id as i32))
+                    .filter(author::Column::DeletedAt.is_null())
+                    .one(&txn)
+                    .await?
+                    .ok_or_else(|| AppError::invalid_field("to", "Author not found"))?;
+
+                // the primary author row of each book follows, the new author's own author row there goes
+                let moved = Query::select()
+                    .column(book::Column::Id)
+                    .from(book::Entity)
+                    .and_where(book::Column::AuthorId.eq(author.id))
+                    .and_where(book::Column::UserId.eq(user.id as i32))
+                    .and_where(book::Column::DeletedAt.is_null())
+                    .to_owned();
+                book_contributor::Entity::delete_many()
+                    .filter(book_contributor::Column::BookId.in_subquery(moved.to_owned()))
+                    .filter(book_contributor::Column::AuthorId.eq(target.id))
+                    .filter(book_contributor::Column::Role.eq(ContributorRole::Author.as_str()))
+                    .exec(&txn)
+                    .await?;
+                book_contributor::Entity::update_many()
+                    .col_expr(book_contributor::Column::AuthorId, Expr::value(target.id))
+                    .filter(book_contributor::Column::BookId.in_subquery(moved))
+                    .filter(book_contributor::Column::AuthorId.eq(author.id))
+                    .filter(book_contributor::Column::Position.eq(0))
+                    .exec(&txn)
+                    .await?;
+
+                books.col_expr(book::Column::AuthorId, Expr::value(target.id)).exec(&txn).await?;
             }
         }
+
+        let mut author_active: author::ActiveModel = author.clone().into();
+        author_active.deleted_at = Set(Some(now));
+        Self::save_versioned(&txn, &author, author_active).await?;
+
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Author moved to the trash".to_string()
+        )))
+    }
+
+    async fn restore(
+        &self,user: AuthenticatedUser,
+        id: i32,
+    )
+    -> Response<Tagged<Json<ResAuthor>>>
+    {
+        let conn = Arc::clone(&self.db_pool);
+
+        let author = self.find_trashed(user.id as i32, id).await?;
+        let txn = conn.begin().await?;
+
+        // bring back the books that were trashed along with the author
+        book::Entity::update_many()
+            .col_expr(book::Column::DeletedAt, Expr::value(Option::<DateTimeUtc>::None))
+            .col_expr(book::Column::Version, Expr::col(book::Column::Version).add(1))
+            .filter(book::Column::AuthorId.eq(author.id))
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.eq(author.deleted_at))
+            .exec(&txn)
+            .await?;
+
+        let mut author_active: author::ActiveModel = author.clone().into();
+        author_active.deleted_at = Set(None);
+        let restored_author = Self::save_versioned(&txn, &author, author_active).await?;
+
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&restored_author)
+        )))
     }
 
     async fn create(
-        &self, _user: AuthenticatedUser,
+        &self, user: AuthenticatedUser,
         req_author: Json<ReqAuthor>
     )
     -> Response<Json<ResAuthor>>
@@ -198,70 +354,55 @@ impl AuthorRepository for AuthorRepositoryImplSql {
 
         // create object to persist to db
         let author = author::ActiveModel{
+            user_id: Set(user.id as i32),
             firstname: Set(req_author.firstname.to_owned()),
             lastname: Set(req_author.lastname.to_owned()),
             bio: Set(req_author.bio.to_owned()),
             ..Default::default()
         };
 
-        // insert and catch the error
-        match author.insert(&*conn).await {
-            Ok(inserted_author) => {
-                Ok(SuccessResponse((
-                    Status::Created,
-                    Json(ResAuthor::from(&inserted_author))
-                )))
-            }
-            Err(_) => {
-                Err(ErrorResponse((
-                    Status::InternalServerError,
-                    "Failed to create author".to_string()
-                )))
-            }
-        }
+        let inserted_author = author.insert(&*conn).await?;
 
+        Ok(SuccessResponse((
+            Status::Created,
+            Json(ResAuthor::from(&inserted_author))
+        )))
     }
 
     async fn get_books(
-        &self, _user:AuthenticatedUser, 
+        &self, user:AuthenticatedUser,
         author_id:i32
-    ) 
+    )
     -> Response<Json<ResBookList>>
     {
         let conn = Arc::clone(&self.db_pool);
-        // Find author and load related books
-    match Author::find_by_id(author_id)
-    .find_with_related(book::Entity)
-    .all(&*conn)
-    .await {
-        Ok(author_with_books) => {
-            if author_with_books.is_empty() {
-                return Err(ErrorResponse((
-                    Status::NotFound,
-                    "Author not found".to_string()
-                )));
-            }
+        // Find the user's author
+        let the_author = self.find_own(user.id as i32, author_id).await?;
 
-            // Convert books to ResBook format
-            let books: Vec<ResBook> = author_with_books[0].1
-                .iter()
-                .map(|book| ResBook::from(book))
-                .collect();
-
-            Ok(SuccessResponse((
-                Status::Ok,
-                Json(ResBookList {
-                    total: books.len(),
-                    books
-                })
-            )))
-        },
-        Err(_) => {
-            Err(ErrorResponse((
-                Status::InternalServerError,
-                "Failed to fetch books".to_string()
-            )))
-        }
-}
+        // books where the author appears in any role
+        let contributed = Query::select()
+            .column(book_contributor::Column::BookId)
+            .from(book_contributor::Entity)
+            .and_where(book_contributor::Column::AuthorId.eq(the_author.id))
+            .to_owned();
+        let result = book::Entity::find()
+            .filter(book::Column::Id.in_subquery(contributed))
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null())
+            .order_by_asc(book::Column::Id)
+            .all(&*conn)
+            .await?;
+
+        // Convert books to ResBook format, with their contributors
+        let books = res_books(&*conn, &result).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResBookList {
+                total: books.len(),
+                books,
+                pagination: None
+            })
+        )))
     }
-}
\ No newline at end of file
+}
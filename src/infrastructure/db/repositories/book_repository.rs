@@ -1,13 +1,48 @@
-use std::{sync::Arc, time::SystemTime};
+use std::{collections::BTreeMap, sync::Arc, time::SystemTime};
 
-use rocket::{async_trait, http::Status, serde::json::Json};
-use sea_orm::{prelude::DateTimeUtc, ActiveModelTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryOrder, Set};
+use rocket::{async_trait, futures::{stream::BoxStream, StreamExt, TryStreamExt}, http::Status, serde::json::{json, Json}};
+use sea_orm::{prelude::DateTimeUtc, sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use validator::Validate;
 
-use crate::{domain::{entities::book, repositories::book_repository::BookRepository, value_object::book::{ReqBook, ResBook, ResBookList}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::custom_response::{ErrorResponse, Response, SuccessResponse}}};
+use crate::{domain::{entities::{author, book, book_contributor, series}, repositories::book_repository::BookRepository, value_object::{batch::{ResBatch, ResBatchItem}, book::{ReqBook, ReqBookBatch, ReqCatalogQuery, ReqBookOperation, ReqBookPatch, ReqBookQuery, ResBook, ResBookList}, contributor::{ContributorRole, ReqContributor}, cover::CoverRef, export::ResExportRow, isbn::Isbn, metadata::{AuthorMetadata, BookMetadata, ReqBookDraft}, published_on::{PublishedOn, ReqPublishedOn}}}, infrastructure::{db::query::{book_query::books_query, contributor_query::{res_book, res_books}, pagination::{fetch_page, PageParams}}, rocket_http::{middleware::{jwt_auth::AuthenticatedUser, preconditions::Preconditions}, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse, Tagged}}}}};
 
 
 use crate::domain::entities::book::Entity as Book;
 
+// changes with every write of the book
+fn etag(book: &book::Model) -> String {
+    format!("\"{}-{}\"", book.id, book.version)
+}
+
+// an already validated date
+fn published_on(value: Option<&ReqPublishedOn>) -> Option<PublishedOn> {
+    value.and_then(|v| v.to_published_on().ok())
+}
+
+// a volume number only means something within a series
+fn check_series_index(series_id: Option<i32>, series_index: Option<i32>) -> Result<(), AppError> {
+    match (series_id, series_index) {
+        (None, Some(_)) => Err(AppError::invalid_field("series_index", "needs a series_id")),
+        _ => Ok(()),
+    }
+}
+
+// both stored forms of an already validated ISBN
+fn isbn_columns(value: Option<&str>) -> (Option<String>, Option<String>) {
+    match value.and_then(|v| Isbn::parse(v).ok()) {
+        Some(isbn) => (isbn.isbn10(), Some(isbn.isbn13().to_string())),
+        None => (None, None),
+    }
+}
+
+// the book with its contributors, tagged with its version
+async fn tagged<C: ConnectionTrait>(db: &C, book: &book::Model) -> Result<Tagged<Json<ResBook>>, AppError> {
+    Ok(Tagged {
+        etag: etag(book),
+        body: Some(Json(res_book(db, book).await?)),
+    })
+}
+
 pub struct BookRepositoryImplSql {
     pub db_pool: Arc<DatabaseConnection>
 }
@@ -19,6 +54,307 @@ impl BookRepositoryImplSql {
             db_pool
         }
     }
+
+    // find a book among the user's own books, outside the trash
+    async fn find_own<C: ConnectionTrait>(db: &C, user_id: i32, id: i32) -> Result<book::Model, AppError> {
+        Book::find_by_id(id)
+            .filter(book::Column::UserId.eq(user_id))
+            .filter(book::Column::DeletedAt.is_null())
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::not_found("Book"))
+    }
+
+    // find a book among the user's trashed books
+    async fn find_trashed(&self, user_id: i32, id: i32) -> Result<book::Model, AppError> {
+        Book::find_by_id(id)
+            .filter(book::Column::UserId.eq(user_id))
+            .filter(book::Column::DeletedAt.is_not_null())
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Book not found in the trash".to_string()))
+    }
+
+    // check that the author exists and belongs to the user, reported on `author_id`
+    async fn ensure_own_author<C: ConnectionTrait>(db: &C, user_id: i32, author_id: i32) -> Result<(), AppError> {
+        author::Entity::find_by_id(author_id)
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_null())
+            .one(db)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| AppError::invalid_field("author_id", "Author not found"))
+    }
+
+    // check that the series belongs to the user, reported on `series_id`
+    async fn ensure_own_series<C: ConnectionTrait>(db: &C, user_id: i32, series_id: Option<i32>) -> Result<(), AppError> {
+        let Some(series_id) = series_id else {
+            return Ok(());
+        };
+
+        series::Entity::find_by_id(series_id)
+            .filter(series::Column::UserId.eq(user_id))
+            .one(db)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| AppError::invalid_field("series_id", "Series not found"))
+    }
+
+    // check that the other contributors are live authors of the user, reported on `contributors`
+    async fn ensure_own_contributors<C: ConnectionTrait>(db: &C, user_id: i32, contributors: &[ReqContributor]) -> Result<(), AppError> {
+        let mut ids: Vec<i32> = contributors.iter().map(|c| c.author_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let found = author::Entity::find()
+            .filter(author::Column::Id.is_in(ids.iter().copied()))
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_null())
+            .count(db)
+            .await?;
+        if found != ids.len() as u64 {
+            return Err(AppError::invalid_field("contributors", "Author not found"));
+        }
+        Ok(())
+    }
+
+    // store the contributors of a book in place of the ones it had, already checked
+    async fn write_contributors<C: ConnectionTrait>(db: &C, book_id: i32, contributors: &[ReqContributor], replace: bool) -> Result<(), AppError> {
+        if replace {
+            book_contributor::Entity::delete_many()
+                .filter(book_contributor::Column::BookId.eq(book_id))
+                .exec(db)
+                .await?;
+        }
+
+        let rows = contributors.iter().enumerate().map(|(position, c)| book_contributor::ActiveModel {
+            book_id: Set(book_id),
+            author_id: Set(c.author_id),
+            role: Set(c.role.as_str().to_string()),
+            position: Set(position as i32),
+            ..Default::default()
+        });
+        book_contributor::Entity::insert_many(rows).exec(db).await?;
+        Ok(())
+    }
+
+    // the contributors after the primary author, as they would be sent
+    async fn other_contributors<C: ConnectionTrait>(db: &C, book_id: i32) -> Result<Vec<ReqContributor>, AppError> {
+        let rows = book_contributor::Entity::find()
+            .filter(book_contributor::Column::BookId.eq(book_id))
+            .filter(book_contributor::Column::Position.gt(0))
+            .order_by_asc(book_contributor::Column::Position)
+            .all(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| ContributorRole::parse(&row.role).map(|role| ReqContributor { author_id: row.author_id, role }))
+            .collect())
+    }
+
+    // the checked contributors to store after an update, `None` when they stay as they are,
+    // a new primary author keeps the other contributors unless they are given
+    async fn next_contributors<C: ConnectionTrait>(db: &C, user_id: i32, book: &book::Model, author_id: i32, given: Option<&[ReqContributor]>) -> Result<Option<Vec<ReqContributor>>, AppError> {
+        let given = match given {
+            Some(given) => given.to_vec(),
+            None if book.author_id != author_id => Self::other_contributors(db, book.id).await?,
+            None => return Ok(None),
+        };
+
+        let contributors = ReqContributor::ordered(author_id, &given);
+        Self::ensure_own_contributors(db, user_id, &contributors[1..]).await?;
+        Ok(Some(contributors))
+    }
+
+    // write the changes unless the book changed since it was read, bumping its version
+    async fn save_versioned<C: ConnectionTrait>(db: &C, current: &book::Model, mut book_active: book::ActiveModel) -> Result<book::Model, AppError> {
+        book_active.version = Set(current.version + 1);
+        book_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+
+        Book::update(book_active)
+            .filter(book::Column::Version.eq(current.version))
+            .exec(db)
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => AppError::PreconditionFailed(
+                    "The book was modified by another request".to_string()
+                ),
+                e => e.into(),
+            })
+    }
+
+    // an edition appears once in the user's library, the trash included
+    async fn ensure_unique_isbn<C: ConnectionTrait>(db: &C, user_id: i32, isbn13: Option<&str>, except: Option<i32>) -> Result<(), AppError> {
+        let Some(isbn13) = isbn13 else {
+            return Ok(());
+        };
+
+        let mut query = Book::find()
+            .filter(book::Column::UserId.eq(user_id))
+            .filter(book::Column::Isbn13.eq(isbn13));
+        if let Some(id) = except {
+            query = query.filter(book::Column::Id.ne(id));
+        }
+
+        match query.one(db).await? {
+            Some(other) => Err(AppError::conflict(
+                "A book with this ISBN already exists",
+                Some(json!({ "book_id": other.id, "in_trash": other.deleted_at.is_some() })),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // every new book goes through here, created one by one, in a batch or by an import
+    pub async fn insert_book<C: ConnectionTrait>(db: &C, user_id: i32, req_book: &ReqBook) -> Result<book::Model, AppError> {
+        // the author must belong to the user
+        Self::ensure_own_author(db, user_id, req_book.author_id).await?;
+
+        let (isbn10, isbn13) = isbn_columns(req_book.isbn.as_deref());
+        Self::ensure_unique_isbn(db, user_id, isbn13.as_deref(), None).await?;
+
+        let contributors = ReqContributor::ordered(req_book.author_id, req_book.contributors.as_deref().unwrap_or_default());
+        Self::ensure_own_contributors(db, user_id, &contributors[1..]).await?;
+
+        check_series_index(req_book.series_id, req_book.series_index)?;
+        Self::ensure_own_series(db, user_id, req_book.series_id).await?;
+
+        let mut book = book::ActiveModel {
+            user_id: Set(user_id),
+            author_id: Set(req_book.author_id.to_owned()),
+            title: Set(req_book.title.to_owned()),
+            cover: Set(req_book.cover.to_owned()),
+            isbn10: Set(isbn10),
+            isbn13: Set(isbn13),
+            series_id: Set(req_book.series_id),
+            series_index: Set(req_book.series_index),
+            total_pages: Set(req_book.total_pages),
+        ..Default::default()
+        };
+        book.set_published_on(published_on(req_book.date().as_ref()));
+
+        let inserted_book = book.insert(db).await?;
+        Self::write_contributors(db, inserted_book.id, &contributors, false).await?;
+
+        Ok(inserted_book)
+    }
+
+    // a live author of the user with this name, created when there is none
+    async fn find_or_create_author<C: ConnectionTrait>(db: &C, user_id: i32, name: &AuthorMetadata) -> Result<i32, AppError> {
+        let existing = author::Entity::find()
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_null())
+            .filter(author::Column::Firstname.eq(name.firstname.as_str()))
+            .filter(author::Column::Lastname.eq(name.lastname.as_str()))
+            .one(db)
+            .await?;
+        if let Some(existing) = existing {
+            return Ok(existing.id);
+        }
+
+        let author = author::ActiveModel {
+            user_id: Set(user_id),
+            firstname: Set(name.firstname.to_owned()),
+            lastname: Set(name.lastname.to_owned()),
+            bio: Set(String::new()),
+            ..Default::default()
+        };
+        Ok(author.insert(db).await?.id)
+    }
+
+    async fn patch_book<C: ConnectionTrait>(db: &C, user_id: i32, id: i32, req_book: ReqBookPatch, preconditions: Option<&Preconditions>) -> Result<book::Model, AppError> {
+        let book = Self::find_own(db, user_id, id).await?;
+        if let Some(preconditions) = preconditions {
+            preconditions.check_match(&etag(&book))?;
+        }
+
+        let mut book_active: book::ActiveModel = book.clone().into();
+
+        // only the provided fields are written
+        if let Some(author_id) = req_book.author_id {
+            Self::ensure_own_author(db, user_id, author_id).await?;
+            book_active.author_id = Set(author_id);
+        }
+        if req_book.series_id.is_some() || req_book.series_index.is_some() {
+            // leaving the series drops the volume number unless a new one is given
+            let series_id = req_book.series_id.unwrap_or(book.series_id);
+            let series_index = match (req_book.series_index, req_book.series_id) {
+                (Some(series_index), _) => series_index,
+                (None, Some(None)) => None,
+                (None, _) => book.series_index,
+            };
+            check_series_index(series_id, series_index)?;
+            if let Some(series_id) = req_book.series_id {
+                Self::ensure_own_series(db, user_id, series_id).await?;
+            }
+            book_active.series_id = Set(series_id);
+            book_active.series_index = Set(series_index);
+        }
+        if let Some(total_pages) = req_book.total_pages {
+            book_active.total_pages = Set(total_pages);
+        }
+        let author_id = req_book.author_id.unwrap_or(book.author_id);
+        let contributors = Self::next_contributors(db, user_id, &book, author_id, req_book.contributors.as_deref()).await?;
+        if let Some(date) = req_book.date() {
+            book_active.set_published_on(published_on(date.as_ref()));
+        }
+        if let Some(title) = req_book.title {
+            book_active.title = Set(title);
+        }
+        if let Some(cover) = req_book.cover {
+            book_active.cover = Set(cover);
+        }
+        if let Some(isbn) = req_book.isbn {
+            let (isbn10, isbn13) = isbn_columns(isbn.as_deref());
+            Self::ensure_unique_isbn(db, user_id, isbn13.as_deref(), Some(id)).await?;
+            book_active.isbn10 = Set(isbn10);
+            book_active.isbn13 = Set(isbn13);
+        }
+
+        let updated_book = Self::save_versioned(db, &book, book_active).await?;
+        if let Some(contributors) = contributors {
+            Self::write_contributors(db, id, &contributors, true).await?;
+        }
+
+        Ok(updated_book)
+    }
+
+    async fn trash_book<C: ConnectionTrait>(db: &C, user_id: i32, id: i32, preconditions: Option<&Preconditions>) -> Result<book::Model, AppError> {
+        // 1. Find the book, only among the user's own books
+        let book = Self::find_own(db, user_id, id).await?;
+        if let Some(preconditions) = preconditions {
+            preconditions.check_match(&etag(&book))?;
+        }
+
+        // 2. Move the book to the trash, unless it changed since it was read
+        let mut book_active: book::ActiveModel = book.clone().into();
+        book_active.deleted_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        Self::save_versioned(db, &book, book_active).await
+    }
+
+    // one batch operation, its status and the id of the book
+    async fn run_operation<C: ConnectionTrait>(db: &C, user_id: i32, operation: ReqBookOperation) -> Result<(Status, i32), AppError> {
+        operation.validate()?;
+
+        match operation {
+            ReqBookOperation::Create { book } => {
+                let inserted_book = Self::insert_book(db, user_id, &book).await?;
+                Ok((Status::Created, inserted_book.id))
+            }
+            ReqBookOperation::Update { id, book } => {
+                Self::patch_book(db, user_id, id, book, None).await?;
+                Ok((Status::Ok, id))
+            }
+            ReqBookOperation::Delete { id } => {
+                Self::trash_book(db, user_id, id, None).await?;
+                Ok((Status::Ok, id))
+            }
+        }
+    }
 }
 
 
@@ -27,210 +363,429 @@ impl BookRepository for BookRepositoryImplSql {
     // get all book
     async fn index(
         &self,
-        _user: AuthenticatedUser
+        user: AuthenticatedUser,
+        query: ReqBookQuery
     )
     -> Response<Json<ResBookList>>
     {
         // get connection
         let conn = Arc::clone(&self.db_pool);
 
-        
-        // Fetch books from database
-    match Book::find()
-    .order_by_desc(book::Column::UpdatedAt)
-    .all(&*conn)
-    .await {
-        Ok(result) => {
-            // Transform the books into ResBook format
-            let books: Vec<ResBook> = result
-                .iter()
-                .map(ResBook::from)
-                .collect();
+        let params = PageParams::new(query.page, query.per_page);
 
-            // Create the response structure
-            let result = ResBookList {
-                total: books.len(),
-                books,
-            };
+        // Fetch one page of books from database
+        let page = fetch_page(
+            books_query(user.id as i32, &query).paginate(&*conn, params.per_page),
+            &params
+        )
+        .await?;
 
-            // Return successful response
-            Ok(SuccessResponse((
-                Status::Ok,
-                Json(result)
-            )))
-        },
-        Err(_) => {
-            // Handle database error
-            
-            Err(ErrorResponse((Status::InternalServerError,"Internal Error".to_string())))
-        }
-}
+        // Transform the books into ResBook format, with their contributors
+        let books = res_books(&*conn, &page.items).await?;
 
+        // Create the response structure
+        let result = ResBookList {
+            total: page.total as usize,
+            books,
+            pagination: Some(page.pagination),
+        };
+
+        // Return successful response
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(result)
+        )))
     }
 
     // find by id
     async fn show(
         &self,
-        _user: AuthenticatedUser,
-        id: i32
-    ) -> Response<Json<ResBook>>
+        user: AuthenticatedUser,
+        id: i32,
+        preconditions: Preconditions
+    ) -> Response<Tagged<Json<ResBook>>>
     {
-        let conn = Arc::clone(&self.db_pool);
+        let the_book = Self::find_own(&*self.db_pool, user.id as i32, id).await?;
 
-        let book = Book::find_by_id(id).one(&*conn).await;
-        match book {
-            Ok(Some(the_book)) => {
-                Ok(SuccessResponse((
-                    Status::Ok,
-                    Json(ResBook::from(&the_book)),
-                )))
-            }
-            Ok(None) => {
-                Err(ErrorResponse((
-                    Status::NotFound,
-                    "no book found with the specified ID".to_string()
-                )))
-            }
-            Err(_) => {
-                Err(ErrorResponse((
-                    Status::InternalServerError,
-                    "Internal Error please try again".to_string()
-                )))
-            }
+        // the client copy is still current
+        if preconditions.not_modified(&etag(&the_book)) {
+            return Ok(SuccessResponse((
+                Status::NotModified,
+                Tagged { etag: etag(&the_book), body: None },
+            )));
         }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &the_book).await?,
+        )))
+    }
+
+    async fn by_isbn(
+        &self,
+        user: AuthenticatedUser,
+        isbn: String
+    )
+    -> Response<Tagged<Json<ResBook>>>
+    {
+        let isbn = Isbn::parse(&isbn).map_err(|e| AppError::invalid_field("isbn", e.message()))?;
+
+        // either form finds the book, both are stored from the same ISBN
+        let the_book = Book::find()
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null())
+            .filter(book::Column::Isbn13.eq(isbn.isbn13()))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Book"))?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &the_book).await?,
+        )))
     }
 
     async fn update(
         &self,
-        _user: AuthenticatedUser, 
-        id: i32, 
-        req_book: Json<ReqBook>
-    ) 
-    -> Response<Json<ResBook>>
+        user: AuthenticatedUser,
+        id: i32,
+        req_book: Json<ReqBook>,
+        preconditions: Preconditions
+    )
+    -> Response<Tagged<Json<ResBook>>>
     {
         let conn = Arc::clone(&self.db_pool);
 
-        // 1. Find the book and convert to ActiveModel
-        // 1. Find the book
-    let book_result = Book::find_by_id(id).one(&*conn).await;
-    
-    match book_result {
-        Ok(Some(book)) => {
-            // 2. Convert to ActiveModel
-            let mut book_active: book::ActiveModel = book.into();
-            
-            // 3. Update fields
-            book_active.author_id = Set(req_book.author_id as i32);
-            book_active.title = Set(req_book.title.to_owned());
-            book_active.year = Set(req_book.year.to_owned());
-            book_active.cover = Set(req_book.cover.to_owned());
-            book_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
-
-            // 4. Save updates
-            match book_active.update(&*conn).await  {
-                Ok(updated_book) => {
-                    Ok(SuccessResponse((
-                        Status::Ok,
-                        Json(ResBook::from(&updated_book)),
-                    )))
-                }
-                Err(_) => {
-                    Err(ErrorResponse((
-                        Status::InternalServerError,
-                        "Failed to update book".to_string()
-                    )))
-                }
-            }
-        }
-        Ok(None) => {
-            Err(ErrorResponse((
-                Status::NotFound,
-                "Book not found".to_string()
-            )))
-        }
-        Err(_) => {
-            Err(ErrorResponse((
-                Status::InternalServerError,
-                "Database error".to_string()
-            )))
+        // 1. Find the book, only among the user's own books
+        let book = Self::find_own(&*conn, user.id as i32, id).await?;
+        preconditions.check_match(&etag(&book))?;
+
+        // the new author must belong to the user as well
+        Self::ensure_own_author(&*conn, user.id as i32, req_book.author_id).await?;
+
+        let (isbn10, isbn13) = isbn_columns(req_book.isbn.as_deref());
+        Self::ensure_unique_isbn(&*conn, user.id as i32, isbn13.as_deref(), Some(id)).await?;
+        let contributors = Self::next_contributors(&*conn, user.id as i32, &book, req_book.author_id, req_book.contributors.as_deref()).await?;
+        check_series_index(req_book.series_id, req_book.series_index)?;
+        Self::ensure_own_series(&*conn, user.id as i32, req_book.series_id).await?;
+
+        // 2. Convert to ActiveModel
+        let mut book_active: book::ActiveModel = book.clone().into();
+
+        // 3. Update fields
+        book_active.author_id = Set(req_book.author_id);
+        book_active.title = Set(req_book.title.to_owned());
+        book_active.set_published_on(published_on(req_book.date().as_ref()));
+        book_active.cover = Set(req_book.cover.to_owned());
+        book_active.isbn10 = Set(isbn10);
+        book_active.isbn13 = Set(isbn13);
+        book_active.series_id = Set(req_book.series_id);
+        book_active.series_index = Set(req_book.series_index);
+        book_active.total_pages = Set(req_book.total_pages);
+
+        // 4. Save updates, the book and its contributors together
+        let txn = conn.begin().await?;
+        let updated_book = Self::save_versioned(&txn, &book, book_active).await?;
+        if let Some(contributors) = contributors {
+            Self::write_contributors(&txn, id, &contributors, true).await?;
         }
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &updated_book).await?,
+        )))
     }
+
+    async fn patch(
+        &self,
+        user: AuthenticatedUser,
+        id: i32,
+        req_book: Json<ReqBookPatch>,
+        preconditions: Preconditions
+    )
+    -> Response<Tagged<Json<ResBook>>>
+    {
+        let txn = self.db_pool.begin().await?;
+        let updated_book = Self::patch_book(
+            &txn,
+            user.id as i32,
+            id,
+            req_book.into_inner(),
+            Some(&preconditions)
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &updated_book).await?,
+        )))
     }
 
     async fn delete(
         &self,
-        _user: AuthenticatedUser, 
-        id: i32
-    ) 
+        user: AuthenticatedUser,
+        id: i32,
+        preconditions: Preconditions
+    )
     -> Response<String>{
-        let conn = Arc::clone(&self.db_pool);
+        Self::trash_book(&*self.db_pool, user.id as i32, id, Some(&preconditions)).await?;
 
-         // 1. Find the book
-    match Book::find_by_id(id).one(&*conn).await {
-        Ok(Some(book)) => {
-            // 2. Delete the book
-            match book.delete(&*conn).await {
-                Ok(res) => {
-                    if res.rows_affected == 1 {
-                        Ok(SuccessResponse((
-                            Status::Ok,
-                            "Book successfully deleted".to_string()
-                        )))
-                    } else {
-                        Err(ErrorResponse((
-                            Status::InternalServerError,
-                            "Failed to delete book".to_string()
-                        )))
-                    }
-                }
-                Err(_) => {
-                    Err(ErrorResponse((
-                        Status::InternalServerError,
-                        "Error occurred while deleting book".to_string()
-                    )))
-                }
-            }
-        }
-        Ok(None) => {
-            Err(ErrorResponse((
-                Status::NotFound,
-                "Book not found".to_string()
-            )))
-        }
-        Err(_) => {
-            Err(ErrorResponse((
-                Status::InternalServerError,
-                "Database error".to_string()
-            )))
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Book moved to the trash".to_string()
+        )))
+    }
+
+    async fn restore(
+        &self,
+        user: AuthenticatedUser,
+        id: i32
+    )
+    -> Response<Tagged<Json<ResBook>>>
+    {
+        let book = self.find_trashed(user.id as i32, id).await?;
+
+        // a book cannot come back without its author
+        let author_trashed = author::Entity::find_by_id(book.author_id)
+            .filter(author::Column::DeletedAt.is_not_null())
+            .one(&*self.db_pool)
+            .await?
+            .is_some();
+        if author_trashed {
+            return Err(AppError::conflict(
+                "The author of this book is in the trash, restore the author first",
+                Some(json!({ "author_id": book.author_id })),
+            ));
         }
+
+        let mut book_active: book::ActiveModel = book.clone().into();
+        book_active.deleted_at = Set(None);
+        let restored_book = Self::save_versioned(&*self.db_pool, &book, book_active).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &restored_book).await?,
+        )))
     }
+
+    async fn cover(&self, user: AuthenticatedUser, id: i32) -> Result<Option<CoverRef>, AppError> {
+        let the_book = Self::find_own(&*self.db_pool, user.id as i32, id).await?;
+
+        Ok(the_book.cover_blob.zip(the_book.cover_type).map(|(hash, content_type)| CoverRef { hash, content_type }))
     }
+
+    async fn set_cover(
+        &self,
+        user: AuthenticatedUser,
+        id: i32,
+        cover: CoverRef,
+        preconditions: Preconditions
+    )
+    -> Response<Tagged<Json<ResBook>>>
+    {
+        let the_book = Self::find_own(&*self.db_pool, user.id as i32, id).await?;
+        preconditions.check_match(&etag(&the_book))?;
+
+        let mut book_active: book::ActiveModel = the_book.to_owned().into();
+        book_active.cover_blob = Set(Some(cover.hash));
+        book_active.cover_type = Set(Some(cover.content_type));
+        let updated_book = Self::save_versioned(&*self.db_pool, &the_book, book_active).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            tagged(&*self.db_pool, &updated_book).await?,
+        )))
+    }
+
     async fn create(&self, user: AuthenticatedUser, req_book: Json<ReqBook>) -> Response<Json<ResBook>> {
+        // the book and its contributors together
+        let txn = self.db_pool.begin().await?;
+        let inserted_book = Self::insert_book(&txn, user.id as i32, &req_book).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Created,
+            Json(res_book(&*self.db_pool, &inserted_book).await?),
+        )))
+    }
+
+    async fn create_enriched(&self, user: AuthenticatedUser, draft: ReqBookDraft, metadata: BookMetadata) -> Response<Json<ResBook>> {
         let conn = Arc::clone(&self.db_pool);
 
-        let book = book::ActiveModel {
-            user_id: Set(user.id as i32),
-            author_id: Set(req_book.author_id.to_owned()),
-            title: Set(req_book.title.to_owned()),
-            year: Set(req_book.year.to_owned()),
-            cover: Set(req_book.cover.to_owned()),
-        ..Default::default()
+        // the draft wins, the provider fills the gaps
+        let date = draft.date().or(metadata.published_on.map(ReqPublishedOn::from));
+        let title = draft.title.or(metadata.title);
+        let cover = draft.cover.or(metadata.cover);
+        let author = metadata.authors.into_iter().next();
+
+        let missing: Vec<&str> = [
+            ("title", title.is_none()),
+            ("cover", cover.is_none()),
+            ("author_id", draft.author_id.is_none() && author.is_none()),
+        ]
+        .into_iter()
+        .filter(|(_, missing)| *missing)
+        .map(|(field, _)| field)
+        .collect();
+        if !missing.is_empty() {
+            let details: BTreeMap<&str, [&str; 1]> = missing
+                .into_iter()
+                .map(|field| (field, ["is required, the metadata provider has no value"]))
+                .collect();
+            return Err(AppError::validation("Validation failed", Some(json!(details))));
+        }
+
+        let txn = conn.begin().await?;
+
+        let author_id = match (draft.author_id, author) {
+            (Some(author_id), _) => author_id,
+            (None, Some(author)) => Self::find_or_create_author(&txn, user.id as i32, &author).await?,
+            (None, None) => unreachable!(),
+        };
+
+        let req_book = ReqBook {
+            author_id,
+            title: title.unwrap_or_default(),
+            published_on: date,
+            year: None,
+            cover: cover.unwrap_or_default(),
+            isbn: Some(draft.isbn),
+            contributors: None,
+            series_id: None,
+            series_index: None,
+            total_pages: None,
         };
+        // the provider's values go through the same rules as the client's
+        req_book.validate()?;
+
+        let inserted_book = Self::insert_book(&txn, user.id as i32, &req_book).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Created,
+            Json(res_book(&*conn, &inserted_book).await?)
+        )))
+    }
+
+    async fn batch(&self, user: AuthenticatedUser, req_batch: Json<ReqBookBatch>) -> Response<Json<ResBatch>> {
+        let conn = Arc::clone(&self.db_pool);
+        let ReqBookBatch { atomic, operations } = req_batch.into_inner();
+        let mut results = Vec::with_capacity(operations.len());
+
+        if !atomic {
+            // best-effort, every operation stands on its own, in its own transaction
+            for (index, operation) in operations.into_iter().enumerate() {
+                let op = operation.name();
+                let txn = conn.begin().await?;
+                results.push(match Self::run_operation(&txn, user.id as i32, operation).await {
+                    Ok((status, id)) => {
+                        txn.commit().await?;
+                        ResBatchItem::applied(index, op, status.code, id)
+                    }
+                    Err(e) => {
+                        txn.rollback().await?;
+                        ResBatchItem::failed(index, op, &e)
+                    }
+                });
+            }
+
+            return Ok(SuccessResponse((Status::Ok, Json(ResBatch::from((false, results))))));
+        }
+
+        // all-or-nothing, the first failure rolls everything back
+        let txn = conn.begin().await?;
+        let names: Vec<&'static str> = operations.iter().map(ReqBookOperation::name).collect();
 
-        // Insert with error handling
-    match book.insert(&*conn).await {
-        Ok(inserted_book) => {
-            Ok(SuccessResponse((
-                Status::Created,
-                Json(ResBook::from(&inserted_book)),
-            )))
+        for (index, operation) in operations.into_iter().enumerate() {
+            let op = operation.name();
+            match Self::run_operation(&txn, user.id as i32, operation).await {
+                Ok((status, id)) => results.push(ResBatchItem::applied(index, op, status.code, id)),
+                Err(e) => {
+                    txn.rollback().await?;
+
+                    let results = names
+                        .iter()
+                        .enumerate()
+                        .map(|(i, op)| match i == index {
+                            true => ResBatchItem::failed(i, op, &e),
+                            false => ResBatchItem::not_applied(i, op),
+                        })
+                        .collect();
+
+                    return Ok(SuccessResponse((e.status(), Json(ResBatch::from((true, results))))));
+                }
+            }
         }
-        Err(_) => {
-            Err(ErrorResponse((
-                Status::InternalServerError,
-                "Failed to create book".to_string()
-            )))
+
+        txn.commit().await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResBatch::from((true, results))))))
+    }
+
+    fn export(&self, user: AuthenticatedUser) -> BoxStream<'static, Result<ResExportRow, AppError>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        async_stream::try_stream! {
+            // rows are read one at a time instead of loading the whole library
+            let mut rows = Book::find()
+                .find_also_related(author::Entity)
+                .filter(book::Column::UserId.eq(user.id as i32))
+                .filter(book::Column::DeletedAt.is_null())
+                .order_by_asc(book::Column::Id)
+                .stream(&*conn)
+                .await?;
+
+            while let Some((book, author)) = rows.try_next().await? {
+                if let Some(author) = author {
+                    yield ResExportRow::from((book, author));
+                }
+            }
         }
+        .boxed()
     }
+
+    async fn catalog(&self, query: ReqCatalogQuery) -> Response<Json<ResBookList>> {
+        let conn = Arc::clone(&self.db_pool);
+        let params = PageParams::new(query.page, query.per_page);
+
+        let select = Book::find()
+            .filter(book::Column::DeletedAt.is_null())
+            .order_by_desc(book::Column::UpdatedAt)
+            .order_by_desc(book::Column::Id);
+        let page = fetch_page(select.paginate(&*conn, params.per_page), &params).await?;
+
+        let books = res_books(&*conn, &page.items).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResBookList {
+                total: page.total as usize,
+                books,
+                pagination: Some(page.pagination),
+            })
+        )))
     }
-}
\ No newline at end of file
+
+    async fn moderate_delete(&self, id: i32) -> Response<String> {
+        let conn = Arc::clone(&self.db_pool);
+
+        // into the owner's trash, like any other delete
+        let res = Book::update_many()
+            .col_expr(book::Column::DeletedAt, Expr::value(DateTimeUtc::from(SystemTime::now())))
+            .col_expr(book::Column::Version, Expr::col(book::Column::Version).add(1))
+            .filter(book::Column::Id.eq(id))
+            .filter(book::Column::DeletedAt.is_null())
+            .exec(&*conn)
+            .await?;
+        if res.rows_affected == 0 {
+            return Err(AppError::not_found("Book"));
+        }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Book moved to the owner's trash".to_string()
+        )))
+    }
+}
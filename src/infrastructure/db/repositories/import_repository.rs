@@ -0,0 +1,156 @@
+use std::{collections::HashMap, sync::Arc};
+
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use validator::Validate;
+
+use crate::{domain::{entities::{author, book}, repositories::import_repository::ImportRepository, value_object::{import::{ImportRow, ResImport, ResImportRow}, isbn::Isbn}}, infrastructure::{db::repositories::book_repository::BookRepositoryImplSql, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+// authors are matched by name, ignoring case and surrounding spaces
+type AuthorKey = (String, String);
+// books are the same when title, author and year are
+type BookKey = (AuthorKey, String, Option<i32>);
+
+fn author_key(firstname: &str, lastname: &str) -> AuthorKey {
+    (firstname.trim().to_lowercase(), lastname.trim().to_lowercase())
+}
+
+fn book_key(author: &AuthorKey, title: &str, year: Option<i32>) -> BookKey {
+    (author.clone(), title.trim().to_lowercase(), year)
+}
+
+pub struct ImportRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl ImportRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+}
+
+#[async_trait]
+impl ImportRepository for ImportRepositoryImplSql {
+    async fn import(&self, user: AuthenticatedUser, rows: Vec<ImportRow>, dry_run: bool) -> Response<Json<ResImport>> {
+        let conn = Arc::clone(&self.db_pool);
+        let user_id = user.id as i32;
+        let txn = conn.begin().await?;
+
+        // the user's live authors and all their books, ids are unknown for what a dry run would create
+        let authors = author::Entity::find()
+            .filter(author::Column::UserId.eq(user_id))
+            .filter(author::Column::DeletedAt.is_null())
+            .all(&txn)
+            .await?;
+        let books = book::Entity::find()
+            .filter(book::Column::UserId.eq(user_id))
+            .all(&txn)
+            .await?;
+
+        let mut author_ids: HashMap<AuthorKey, Option<i32>> = HashMap::new();
+        let mut author_keys: HashMap<i32, AuthorKey> = HashMap::new();
+        for author in authors {
+            let key = author_key(&author.firstname, &author.lastname);
+            author_ids.entry(key.clone()).or_insert(Some(author.id));
+            author_keys.insert(author.id, key);
+        }
+
+        let mut book_ids: HashMap<BookKey, Option<i32>> = HashMap::new();
+        // ISBNs are unique per user, trashed books included
+        let mut isbn_ids: HashMap<String, Option<i32>> = HashMap::new();
+        for book in books {
+            if let Some(isbn13) = &book.isbn13 {
+                isbn_ids.insert(isbn13.to_owned(), Some(book.id));
+            }
+            if book.deleted_at.is_some() {
+                continue;
+            }
+            if let Some(key) = author_keys.get(&book.author_id) {
+                book_ids.entry(book_key(key, &book.title, book.published_year)).or_insert(Some(book.id));
+            }
+        }
+
+        let mut authors_created = 0;
+        let mut results = Vec::with_capacity(rows.len());
+
+        for ImportRow { row, fields } in rows {
+            let fields = match fields.and_then(|f| f.validate().map(|_| f).map_err(AppError::from)) {
+                Ok(fields) => fields,
+                Err(e) => {
+                    results.push(ResImportRow::failed(row, &e));
+                    continue;
+                }
+            };
+
+            let a_key = author_key(&fields.author_firstname, &fields.author_lastname);
+            let published_on = fields.published_on();
+            let b_key = book_key(&a_key, &fields.title, published_on.map(|p| p.year));
+
+            let isbn = fields.isbn.as_deref().and_then(|v| Isbn::parse(v).ok());
+            let isbn13 = isbn.as_ref().map(|i| i.isbn13().to_string());
+
+            if let Some(book_id) = book_ids.get(&b_key).or_else(|| isbn13.as_ref().and_then(|i| isbn_ids.get(i))) {
+                let author_id = author_ids.get(&a_key).copied().flatten();
+                results.push(ResImportRow::skipped(row, *book_id, author_id, "duplicate"));
+                continue;
+            }
+
+            // held to the rules of a created book, the author is known below
+            let mut req_book = fields.to_req_book(0);
+            if let Err(e) = req_book.validate() {
+                results.push(ResImportRow::failed(row, &AppError::from(e)));
+                continue;
+            }
+
+            // the author, created under the user when it is missing
+            let author_id = match author_ids.get(&a_key) {
+                Some(author_id) => *author_id,
+                None => {
+                    authors_created += 1;
+                    let author_id = match dry_run {
+                        true => None,
+                        false => {
+                            let author = author::ActiveModel {
+                                user_id: Set(user_id),
+                                firstname: Set(fields.author_firstname.trim().to_string()),
+                                lastname: Set(fields.author_lastname.trim().to_string()),
+                                bio: Set(fields.author_bio.clone().unwrap_or_default()),
+                                ..Default::default()
+                            };
+                            Some(author.insert(&txn).await?.id)
+                        }
+                    };
+                    author_ids.insert(a_key, author_id);
+                    author_id
+                }
+            };
+
+            let book_id = match (dry_run, author_id) {
+                (false, Some(author_id)) => {
+                    req_book.author_id = author_id;
+                    Some(BookRepositoryImplSql::insert_book(&txn, user_id, &req_book).await?.id)
+                }
+                _ => None,
+            };
+
+            book_ids.insert(b_key, book_id);
+            if let Some(isbn13) = isbn13 {
+                isbn_ids.insert(isbn13, book_id);
+            }
+            results.push(ResImportRow::inserted(row, book_id, author_id));
+        }
+
+        // a dry run only reports
+        match dry_run {
+            true => txn.rollback().await?,
+            false => txn.commit().await?,
+        }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResImport::new(dry_run, authors_created, results))
+        )))
+    }
+}
@@ -0,0 +1,178 @@
+use std::{sync::Arc, time::SystemTime};
+
+use chrono::{NaiveDate, Utc};
+use rocket::{async_trait, http::Status, serde::json::{json, Json}};
+use sea_orm::{prelude::DateTimeUtc, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait};
+
+use crate::{domain::{entities::{book, loan}, repositories::loan_repository::LoanRepository, value_object::loan::{ReqLoan, ReqLoanQuery, ReqLoanReturn, ResLoan, ResLoanList}}, infrastructure::{db::query::{book_query::{find_book, own_book}, pagination::{fetch_page, PageParams}}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+// still out past the due date, the due date is checked for NULL so the negation holds
+fn overdue(today: NaiveDate) -> Condition {
+    Condition::all()
+        .add(loan::Column::ReturnedOn.is_null())
+        .add(loan::Column::DueOn.is_not_null())
+        .add(loan::Column::DueOn.lt(today))
+}
+
+pub struct LoanRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl LoanRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // find a book among the user's own books, locked to hold other loans of it until the transaction ends
+    async fn lock_book<C: ConnectionTrait>(db: &C, user_id: i32, id: i32) -> Result<book::Model, AppError> {
+        own_book(user_id, id)
+            .lock_exclusive()
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::not_found("Book"))
+    }
+}
+
+#[async_trait]
+impl LoanRepository for LoanRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser, query: ReqLoanQuery) -> Response<Json<ResLoanList>> {
+        let today = today();
+        let params = PageParams::new(query.page, query.per_page);
+
+        let mut select = loan::Entity::find()
+            .find_also_related(book::Entity)
+            .filter(loan::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null());
+        select = match query.overdue {
+            Some(true) => select.filter(overdue(today)),
+            Some(false) => select.filter(overdue(today).not()),
+            None => select,
+        };
+        select = match query.open {
+            Some(true) => select.filter(loan::Column::ReturnedOn.is_null()),
+            Some(false) => select.filter(loan::Column::ReturnedOn.is_not_null()),
+            None => select,
+        };
+        if let Some(direction) = query.direction {
+            select = select.filter(loan::Column::Direction.eq(direction.as_str()));
+        }
+        let select = select
+            .order_by_desc(loan::Column::LentOn)
+            .order_by_desc(loan::Column::Id);
+
+        let page = fetch_page(select.paginate(&*self.db_pool, params.per_page), &params).await?;
+        let loans: Vec<ResLoan> = page
+            .items
+            .iter()
+            .filter_map(|(loan, book)| book.as_ref().map(|book| ResLoan::new(loan, book, today)))
+            .collect();
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResLoanList {
+                total: page.total as usize,
+                loans,
+                pagination: Some(page.pagination),
+            })
+        )))
+    }
+
+    async fn history(&self, user: AuthenticatedUser, book_id: i32) -> Response<Json<ResLoanList>> {
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let today = today();
+
+        let loans = loan::Entity::find()
+            .filter(loan::Column::BookId.eq(the_book.id))
+            .order_by_desc(loan::Column::LentOn)
+            .order_by_desc(loan::Column::Id)
+            .all(&*self.db_pool)
+            .await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResLoanList {
+                total: loans.len(),
+                loans: loans.iter().map(|l| ResLoan::new(l, &the_book, today)).collect(),
+                pagination: None,
+            })
+        )))
+    }
+
+    async fn lend(&self, user: AuthenticatedUser, book_id: i32, req_loan: Json<ReqLoan>) -> Response<Json<ResLoan>> {
+        let today = today();
+        let lent_on = req_loan.lent_on(today)?;
+        let req_loan = req_loan.into_inner();
+
+        let txn = self.db_pool.begin().await?;
+        let the_book = Self::lock_book(&txn, user.id as i32, book_id).await?;
+
+        let out = loan::Entity::find()
+            .filter(loan::Column::BookId.eq(the_book.id))
+            .filter(loan::Column::ReturnedOn.is_null())
+            .one(&txn)
+            .await?;
+        if let Some(out) = out {
+            return Err(AppError::conflict(
+                "This book is already out",
+                Some(json!({ "loan_id": out.id, "person": out.person })),
+            ));
+        }
+
+        let inserted_loan = loan::ActiveModel {
+            user_id: Set(user.id as i32),
+            book_id: Set(the_book.id),
+            direction: Set(req_loan.direction.as_str().to_string()),
+            person: Set(req_loan.person.trim().to_string()),
+            contact: Set(req_loan.contact),
+            lent_on: Set(lent_on),
+            due_on: Set(req_loan.due_on),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((Status::Created, Json(ResLoan::new(&inserted_loan, &the_book, today)))))
+    }
+
+    async fn return_loan(&self, user: AuthenticatedUser, id: i32, req_return: Json<ReqLoanReturn>) -> Response<Json<ResLoan>> {
+        let today = today();
+
+        let (the_loan, the_book) = loan::Entity::find_by_id(id)
+            .find_also_related(book::Entity)
+            .filter(loan::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null())
+            .one(&*self.db_pool)
+            .await?
+            .and_then(|(loan, book)| book.map(|book| (loan, book)))
+            .ok_or_else(|| AppError::not_found("Loan"))?;
+
+        if let Some(returned_on) = the_loan.returned_on {
+            return Err(AppError::conflict(
+                "This loan was already returned",
+                Some(json!({ "returned_on": returned_on })),
+            ));
+        }
+
+        let returned_on = req_return.returned_on.unwrap_or(today);
+        if returned_on > today {
+            return Err(AppError::invalid_field("returned_on", "must not be in the future"));
+        }
+        if returned_on < the_loan.lent_on {
+            return Err(AppError::invalid_field("returned_on", "must not be before lent_on"));
+        }
+
+        let mut loan_active: loan::ActiveModel = the_loan.into();
+        loan_active.returned_on = Set(Some(returned_on));
+        loan_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        let returned_loan = loan_active.update(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResLoan::new(&returned_loan, &the_book, today)))))
+    }
+}
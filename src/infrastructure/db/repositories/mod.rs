@@ -1,3 +1,14 @@
 pub mod auth_repository;
 pub mod book_repository;
-pub mod author_repository;
\ No newline at end of file
+pub mod author_repository;
+pub mod user_repository;
+pub mod search_repository;
+pub mod trash_repository;
+pub mod import_repository;
+pub mod series_repository;
+pub mod reading_repository;
+pub mod review_repository;
+pub mod shelf_repository;
+pub mod tag_repository;
+pub mod loan_repository;
+pub mod stats_repository;
\ No newline at end of file
@@ -0,0 +1,190 @@
+use std::{sync::Arc, time::SystemTime};
+
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{prelude::DateTimeUtc, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait, TryIntoModel};
+
+use crate::{domain::{entities::{book, reading_session, reading_state}, repositories::reading_repository::ReadingRepository, value_object::reading::{Progress, ReadingStatus, ReqReadingSession, ReqReadingStatus, ResCurrentBook, ResCurrentReading, ResReadingSession, ResReadingState, ResSessionLogged}}, infrastructure::{db::query::{book_query::find_book, contributor_query::res_books}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+pub struct ReadingRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl ReadingRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    async fn find_state<C: ConnectionTrait>(db: &C, book_id: i32) -> Result<Option<reading_state::Model>, AppError> {
+        Ok(reading_state::Entity::find()
+            .filter(reading_state::Column::BookId.eq(book_id))
+            .one(db)
+            .await?)
+    }
+
+    // the state of a book, inserted the first time
+    #[allow(clippy::too_many_arguments)]
+    async fn save_state<C: ConnectionTrait>(
+        db: &C,
+        user_id: i32,
+        book_id: i32,
+        previous: Option<reading_state::Model>,
+        status: ReadingStatus,
+        progress: Progress,
+        (started_at, finished_at): (Option<DateTimeUtc>, Option<DateTimeUtc>),
+        now: DateTimeUtc,
+    ) -> Result<reading_state::Model, AppError> {
+        let mut state_active: reading_state::ActiveModel = match previous {
+            Some(previous) => previous.into(),
+            None => reading_state::ActiveModel {
+                user_id: Set(user_id),
+                book_id: Set(book_id),
+                ..Default::default()
+            },
+        };
+        state_active.status = Set(status.as_str().to_string());
+        state_active.current_page = Set(progress.current_page);
+        state_active.percent = Set(progress.percent);
+        state_active.started_at = Set(started_at);
+        state_active.finished_at = Set(finished_at);
+        state_active.updated_at = Set(Some(now));
+
+        Ok(state_active.save(db).await?.try_into_model()?)
+    }
+}
+
+#[async_trait]
+impl ReadingRepository for ReadingRepositoryImplSql {
+    async fn set_status(&self, user: AuthenticatedUser, book_id: i32, req_status: Json<ReqReadingStatus>) -> Response<Json<ResReadingState>> {
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let previous = Self::find_state(&*self.db_pool, book_id).await?;
+        let now = DateTimeUtc::from(SystemTime::now());
+
+        // the progress stays as it was unless some is given
+        let progress = match (req_status.current_page, req_status.percent, &previous) {
+            (None, None, Some(previous)) => Progress { current_page: previous.current_page, percent: previous.percent },
+            (current_page, percent, _) => Progress::new(current_page, percent, the_book.total_pages)?,
+        };
+        let status = req_status.status;
+        let dates = status.dates(previous.as_ref(), now);
+
+        let state = Self::save_state(
+            &*self.db_pool,
+            user.id as i32,
+            book_id,
+            previous,
+            status,
+            progress.for_status(status, the_book.total_pages),
+            dates,
+            now,
+        )
+        .await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResReadingState::new(&state, the_book.total_pages))
+        )))
+    }
+
+    async fn log_session(&self, user: AuthenticatedUser, book_id: i32, req_session: Json<ReqReadingSession>) -> Response<Json<ResSessionLogged>> {
+        let now = DateTimeUtc::from(SystemTime::now());
+        req_session.check(now)?;
+
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        if let (Some(end_page), Some(total_pages)) = (req_session.end_page, the_book.total_pages) {
+            if end_page > total_pages {
+                return Err(AppError::invalid_field("end_page", "must not be past the book's total_pages"));
+            }
+        }
+
+        let txn = self.db_pool.begin().await?;
+
+        let session = reading_session::ActiveModel {
+            user_id: Set(user.id as i32),
+            book_id: Set(book_id),
+            started_at: Set(req_session.started_at),
+            ended_at: Set(req_session.ended_at),
+            start_page: Set(req_session.start_page),
+            end_page: Set(req_session.end_page),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let previous = Self::find_state(&txn, book_id).await?;
+        let previous_status = previous.as_ref().and_then(|p| ReadingStatus::parse(&p.status));
+
+        // sessions may be logged late, the furthest page read counts
+        let furthest = previous.as_ref().and_then(|p| p.current_page).max(req_session.end_page);
+        let progress = match furthest {
+            Some(page) => Progress::new(Some(page), None, the_book.total_pages)?,
+            None => Progress { current_page: None, percent: previous.as_ref().and_then(|p| p.percent) },
+        };
+        let last_page = progress.current_page.is_some() && progress.current_page == the_book.total_pages;
+
+        // a finished book stays finished, a session on any other one means it is being read
+        let (status, dates) = match previous_status {
+            Some(ReadingStatus::Finished) => (ReadingStatus::Finished, ReadingStatus::Finished.dates(previous.as_ref(), req_session.ended_at)),
+            _ if last_page => {
+                let started_at = previous.as_ref().and_then(|p| p.started_at).unwrap_or(req_session.started_at);
+                (ReadingStatus::Finished, (Some(started_at), Some(req_session.ended_at)))
+            }
+            _ => (ReadingStatus::Reading, ReadingStatus::Reading.dates(previous.as_ref(), req_session.started_at)),
+        };
+
+        let state = Self::save_state(
+            &txn,
+            user.id as i32,
+            book_id,
+            previous,
+            status,
+            progress.for_status(status, the_book.total_pages),
+            dates,
+            now,
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Created,
+            Json(ResSessionLogged {
+                session: ResReadingSession::from(&session),
+                state: ResReadingState::new(&state, the_book.total_pages),
+            })
+        )))
+    }
+
+    async fn current(&self, user: AuthenticatedUser) -> Response<Json<ResCurrentReading>> {
+        let rows = reading_state::Entity::find()
+            .find_also_related(book::Entity)
+            .filter(reading_state::Column::UserId.eq(user.id as i32))
+            .filter(reading_state::Column::Status.eq(ReadingStatus::Reading.as_str()))
+            .filter(book::Column::DeletedAt.is_null())
+            .order_by_desc(reading_state::Column::UpdatedAt)
+            .order_by_desc(reading_state::Column::Id)
+            .all(&*self.db_pool)
+            .await?;
+
+        let (states, books): (Vec<reading_state::Model>, Vec<book::Model>) = rows
+            .into_iter()
+            .filter_map(|(state, book)| book.map(|book| (state, book)))
+            .unzip();
+        let res_books = res_books(&*self.db_pool, &books).await?;
+
+        let current: Vec<ResCurrentBook> = states
+            .iter()
+            .zip(books.iter())
+            .zip(res_books)
+            .map(|((state, b), book)| ResCurrentBook { state: ResReadingState::new(state, b.total_pages), book })
+            .collect();
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResCurrentReading {
+                total: current.len(),
+                books: current,
+            })
+        )))
+    }
+}
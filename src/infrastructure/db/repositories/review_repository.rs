@@ -0,0 +1,141 @@
+use std::{sync::Arc, time::SystemTime};
+
+use rocket::{async_trait, http::Status, serde::json::{json, Json}};
+use sea_orm::{prelude::DateTimeUtc, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+
+use crate::{domain::{entities::{book, review}, repositories::review_repository::ReviewRepository, value_object::review::{half_stars, ReqReview, ReqReviewQuery, ResReview, ResReviewList}}, infrastructure::{db::query::{book_query::{bump_versions, find_book}, pagination::{fetch_page, PageParams}, review_query::ratings_of}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+pub struct ReviewRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl ReviewRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // find a review of the book
+    async fn find_review(&self, book_id: i32, id: i32) -> Result<review::Model, AppError> {
+        review::Entity::find_by_id(id)
+            .filter(review::Column::BookId.eq(book_id))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Review"))
+    }
+
+    // find a review the user wrote on the book
+    async fn find_own(&self, user_id: i32, book_id: i32, id: i32) -> Result<review::Model, AppError> {
+        find_book(&*self.db_pool, user_id, book_id).await?;
+        let the_review = self.find_review(book_id, id).await?;
+        if the_review.user_id != user_id {
+            return Err(AppError::not_found("Review"));
+        }
+        Ok(the_review)
+    }
+
+    // the book answers with its rating, so a change to its reviews is a new version of it
+    async fn bump_book<C: ConnectionTrait>(db: &C, book_id: i32) -> Result<(), DbErr> {
+        bump_versions()
+            .filter(book::Column::Id.eq(book_id))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReviewRepository for ReviewRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser, book_id: i32, query: ReqReviewQuery) -> Response<Json<ResReviewList>> {
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let params = PageParams::new(query.page, query.per_page);
+
+        let select = review::Entity::find()
+            .filter(review::Column::BookId.eq(the_book.id))
+            .order_by_desc(review::Column::UpdatedAt)
+            .order_by_desc(review::Column::Id);
+        let page = fetch_page(select.paginate(&*self.db_pool, params.per_page), &params).await?;
+        let rating = ratings_of(&*self.db_pool, &[the_book.id]).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResReviewList {
+                total: page.total as usize,
+                rating: rating.get(&the_book.id).copied().unwrap_or_default(),
+                reviews: page.items.iter().map(ResReview::from).collect(),
+                pagination: Some(page.pagination),
+            })
+        )))
+    }
+
+    async fn show(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<Json<ResReview>> {
+        find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let the_review = self.find_review(book_id, id).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResReview::from(&the_review)))))
+    }
+
+    async fn create(&self, user: AuthenticatedUser, book_id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>> {
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let req_review = req_review.into_inner();
+
+        // the unique index has the last word, this check names the review in the way
+        let existing = review::Entity::find()
+            .filter(review::Column::BookId.eq(the_book.id))
+            .filter(review::Column::UserId.eq(user.id as i32))
+            .one(&*self.db_pool)
+            .await?;
+        if let Some(existing) = existing {
+            return Err(AppError::conflict(
+                "You already reviewed this book",
+                Some(json!({ "review_id": existing.id })),
+            ));
+        }
+
+        let review = review::ActiveModel {
+            user_id: Set(user.id as i32),
+            book_id: Set(the_book.id),
+            half_stars: Set(half_stars(req_review.rating)),
+            text: Set(req_review.text),
+            spoiler: Set(req_review.spoiler),
+            ..Default::default()
+        };
+        let txn = self.db_pool.begin().await?;
+        let inserted_review = review.insert(&txn).await?;
+        Self::bump_book(&txn, the_book.id).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((Status::Created, Json(ResReview::from(&inserted_review)))))
+    }
+
+    async fn update(&self, user: AuthenticatedUser, book_id: i32, id: i32, req_review: Json<ReqReview>) -> Response<Json<ResReview>> {
+        let the_review = self.find_own(user.id as i32, book_id, id).await?;
+        let req_review = req_review.into_inner();
+
+        let mut review_active: review::ActiveModel = the_review.into();
+        review_active.half_stars = Set(half_stars(req_review.rating));
+        review_active.text = Set(req_review.text);
+        review_active.spoiler = Set(req_review.spoiler);
+        review_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        let txn = self.db_pool.begin().await?;
+        let updated_review = review_active.update(&txn).await?;
+        Self::bump_book(&txn, book_id).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResReview::from(&updated_review)))))
+    }
+
+    async fn delete(&self, user: AuthenticatedUser, book_id: i32, id: i32) -> Response<String> {
+        let the_review = self.find_own(user.id as i32, book_id, id).await?;
+        let txn = self.db_pool.begin().await?;
+        the_review.delete(&txn).await?;
+        Self::bump_book(&txn, book_id).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Review deleted".to_string()
+        )))
+    }
+}
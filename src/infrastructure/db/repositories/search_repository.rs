@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, Order, QueryFilter, QueryOrder, QuerySelect};
+
+use crate::{domain::{entities::{author, book}, repositories::search_repository::SearchRepository, value_object::search::{ResSearchHit, ResSearchList, SearchHitKind}}, infrastructure::{db::query::search_query::{author_match, book_match, boolean_query, highlight, search_terms}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+const SNIPPET_CHARS: usize = 160;
+
+#[derive(FromQueryResult)]
+struct BookHit {
+    id: i32,
+    title: String,
+    score: f64,
+}
+
+#[derive(FromQueryResult)]
+struct AuthorHit {
+    id: i32,
+    firstname: String,
+    lastname: String,
+    bio: String,
+    score: f64,
+}
+
+pub struct SearchRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl SearchRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+}
+
+#[async_trait]
+impl SearchRepository for SearchRepositoryImplSql {
+    async fn search(&self, user: AuthenticatedUser, q: String, limit: u64) -> Response<Json<ResSearchList>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        let terms = search_terms(&q);
+        let search = boolean_query(&terms).ok_or_else(|| AppError::validation(
+            "Search query must contain at least one word",
+            None
+        ))?;
+
+        // best books of the user
+        let books = book::Entity::find()
+            .select_only()
+            .column(book::Column::Id)
+            .column(book::Column::Title)
+            .column_as(book_match(&search), "score")
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null())
+            .filter(book_match(&search))
+            .order_by(book_match(&search), Order::Desc)
+            .limit(limit)
+            .into_model::<BookHit>()
+            .all(&*conn)
+            .await?;
+
+        // best authors of the user
+        let authors = author::Entity::find()
+            .select_only()
+            .column(author::Column::Id)
+            .column(author::Column::Firstname)
+            .column(author::Column::Lastname)
+            .column(author::Column::Bio)
+            .column_as(author_match(&search), "score")
+            .filter(author::Column::UserId.eq(user.id as i32))
+            .filter(author::Column::DeletedAt.is_null())
+            .filter(author_match(&search))
+            .order_by(author_match(&search), Order::Desc)
+            .limit(limit)
+            .into_model::<AuthorHit>()
+            .all(&*conn)
+            .await?;
+
+        let mut results: Vec<ResSearchHit> = books
+            .into_iter()
+            .map(|hit| ResSearchHit {
+                kind: SearchHitKind::Book,
+                id: hit.id,
+                title_highlight: highlight(&hit.title, &terms, usize::MAX),
+                title: hit.title,
+                snippet: None,
+                score: hit.score,
+            })
+            .chain(authors.into_iter().map(|hit| {
+                let name = format!("{} {}", hit.firstname, hit.lastname);
+                ResSearchHit {
+                    kind: SearchHitKind::Author,
+                    id: hit.id,
+                    title_highlight: highlight(&name, &terms, usize::MAX),
+                    title: name,
+                    snippet: Some(highlight(&hit.bio, &terms, SNIPPET_CHARS)),
+                    score: hit.score,
+                }
+            }))
+            .collect();
+
+        // merge both lists by relevance
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit as usize);
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResSearchList {
+                total: results.len(),
+                results
+            })
+        )))
+    }
+}
@@ -0,0 +1,149 @@
+use std::{sync::Arc, time::SystemTime};
+
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{prelude::DateTimeUtc, sea_query::{Expr, NullOrdering}, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+
+use crate::{domain::{entities::{book, series}, repositories::series_repository::SeriesRepository, value_object::series::{duplicates, gaps, ReqSeries, ReqSeriesPatch, ReqSeriesQuery, ResSeries, ResSeriesBooks, ResSeriesList}}, infrastructure::{db::query::{contributor_query::res_books, pagination::{fetch_page, PageParams}}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+pub struct SeriesRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl SeriesRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // find a series among the user's own
+    async fn find_own(&self, user_id: i32, id: i32) -> Result<series::Model, AppError> {
+        series::Entity::find_by_id(id)
+            .filter(series::Column::UserId.eq(user_id))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Series"))
+    }
+}
+
+#[async_trait]
+impl SeriesRepository for SeriesRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser, query: ReqSeriesQuery) -> Response<Json<ResSeriesList>> {
+        let params = PageParams::new(query.page, query.per_page);
+
+        let select = series::Entity::find()
+            .filter(series::Column::UserId.eq(user.id as i32))
+            .order_by_asc(series::Column::Title)
+            .order_by_asc(series::Column::Id);
+        let page = fetch_page(select.paginate(&*self.db_pool, params.per_page), &params).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResSeriesList {
+                total: page.total as usize,
+                series: page.items.iter().map(ResSeries::from).collect(),
+                pagination: Some(page.pagination),
+            })
+        )))
+    }
+
+    async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeries>> {
+        let the_series = self.find_own(user.id as i32, id).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResSeries::from(&the_series)))))
+    }
+
+    async fn create(&self, user: AuthenticatedUser, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>> {
+        let req_series = req_series.into_inner();
+
+        let series = series::ActiveModel {
+            user_id: Set(user.id as i32),
+            title: Set(req_series.title),
+            description: Set(req_series.description),
+            ..Default::default()
+        };
+        let inserted_series = series.insert(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Created, Json(ResSeries::from(&inserted_series)))))
+    }
+
+    async fn update(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeries>) -> Response<Json<ResSeries>> {
+        let the_series = self.find_own(user.id as i32, id).await?;
+        let req_series = req_series.into_inner();
+
+        let mut series_active: series::ActiveModel = the_series.into();
+        series_active.title = Set(req_series.title);
+        series_active.description = Set(req_series.description);
+        series_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        let updated_series = series_active.update(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResSeries::from(&updated_series)))))
+    }
+
+    async fn patch(&self, user: AuthenticatedUser, id: i32, req_series: Json<ReqSeriesPatch>) -> Response<Json<ResSeries>> {
+        let the_series = self.find_own(user.id as i32, id).await?;
+        let req_series = req_series.into_inner();
+
+        let mut series_active: series::ActiveModel = the_series.into();
+        // only the provided fields are written
+        if let Some(title) = req_series.title {
+            series_active.title = Set(title);
+        }
+        if let Some(description) = req_series.description {
+            series_active.description = Set(description);
+        }
+        series_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        let updated_series = series_active.update(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResSeries::from(&updated_series)))))
+    }
+
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        let the_series = self.find_own(user.id as i32, id).await?;
+        let txn = self.db_pool.begin().await?;
+
+        // the books leave the series with their volume numbers, as a change of each book
+        book::Entity::update_many()
+            .col_expr(book::Column::SeriesId, Expr::value(Option::<i32>::None))
+            .col_expr(book::Column::SeriesIndex, Expr::value(Option::<i32>::None))
+            .col_expr(book::Column::Version, Expr::col(book::Column::Version).add(1))
+            .filter(book::Column::SeriesId.eq(the_series.id))
+            .exec(&txn)
+            .await?;
+
+        the_series.delete(&txn).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Series deleted".to_string()
+        )))
+    }
+
+    async fn books(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResSeriesBooks>> {
+        let the_series = self.find_own(user.id as i32, id).await?;
+
+        let result = book::Entity::find()
+            .filter(book::Column::SeriesId.eq(the_series.id))
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_null())
+            .order_by_with_nulls(book::Column::SeriesIndex, Order::Asc, NullOrdering::Last)
+            .order_by_asc(book::Column::Id)
+            .all(&*self.db_pool)
+            .await?;
+
+        let indexes: Vec<i32> = result.iter().filter_map(|b| b.series_index).collect();
+        let books = res_books(&*self.db_pool, &result).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResSeriesBooks {
+                series: ResSeries::from(&the_series),
+                total: books.len(),
+                books,
+                gaps: gaps(&indexes),
+                duplicates: duplicates(&indexes),
+            })
+        )))
+    }
+}
@@ -0,0 +1,160 @@
+use std::{sync::Arc, time::SystemTime};
+
+use rocket::{async_trait, http::Status, serde::json::{json, Json}};
+use sea_orm::{prelude::DateTimeUtc, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
+
+use crate::{domain::{entities::{book_shelf, shelf}, repositories::shelf_repository::ShelfRepository, value_object::shelf::{ReqShelf, ResShelf, ResShelfList}}, infrastructure::{db::query::book_query::find_book, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+pub struct ShelfRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl ShelfRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // find a shelf among the user's own
+    async fn find_own(&self, user_id: i32, id: i32) -> Result<shelf::Model, AppError> {
+        shelf::Entity::find_by_id(id)
+            .filter(shelf::Column::UserId.eq(user_id))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Shelf"))
+    }
+
+    async fn ensure_unique_name(&self, user_id: i32, name: &str, except: Option<i32>) -> Result<(), AppError> {
+        let mut query = shelf::Entity::find()
+            .filter(shelf::Column::UserId.eq(user_id))
+            .filter(shelf::Column::Name.eq(name.trim()));
+        if let Some(id) = except {
+            query = query.filter(shelf::Column::Id.ne(id));
+        }
+
+        match query.one(&*self.db_pool).await? {
+            Some(other) => Err(AppError::conflict(
+                "A shelf with this name already exists",
+                Some(json!({ "shelf_id": other.id })),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl ShelfRepository for ShelfRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser) -> Response<Json<ResShelfList>> {
+        let shelves = shelf::Entity::find()
+            .filter(shelf::Column::UserId.eq(user.id as i32))
+            .order_by_asc(shelf::Column::Position)
+            .order_by_asc(shelf::Column::Id)
+            .all(&*self.db_pool)
+            .await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResShelfList {
+                total: shelves.len(),
+                shelves: shelves.iter().map(ResShelf::from).collect(),
+            })
+        )))
+    }
+
+    async fn show(&self, user: AuthenticatedUser, id: i32) -> Response<Json<ResShelf>> {
+        let the_shelf = self.find_own(user.id as i32, id).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResShelf::from(&the_shelf)))))
+    }
+
+    async fn create(&self, user: AuthenticatedUser, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>> {
+        let req_shelf = req_shelf.into_inner();
+        self.ensure_unique_name(user.id as i32, &req_shelf.name, None).await?;
+
+        let position = match req_shelf.position {
+            Some(position) => position,
+            None => shelf::Entity::find()
+                .filter(shelf::Column::UserId.eq(user.id as i32))
+                .count(&*self.db_pool)
+                .await? as i32,
+        };
+
+        let shelf = shelf::ActiveModel {
+            user_id: Set(user.id as i32),
+            name: Set(req_shelf.name.trim().to_string()),
+            description: Set(req_shelf.description),
+            position: Set(position),
+            ..Default::default()
+        };
+        let inserted_shelf = shelf.insert(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Created, Json(ResShelf::from(&inserted_shelf)))))
+    }
+
+    async fn update(&self, user: AuthenticatedUser, id: i32, req_shelf: Json<ReqShelf>) -> Response<Json<ResShelf>> {
+        let the_shelf = self.find_own(user.id as i32, id).await?;
+        let req_shelf = req_shelf.into_inner();
+        self.ensure_unique_name(user.id as i32, &req_shelf.name, Some(id)).await?;
+
+        let mut shelf_active: shelf::ActiveModel = the_shelf.into();
+        shelf_active.name = Set(req_shelf.name.trim().to_string());
+        shelf_active.description = Set(req_shelf.description);
+        if let Some(position) = req_shelf.position {
+            shelf_active.position = Set(position);
+        }
+        shelf_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+        let updated_shelf = shelf_active.update(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResShelf::from(&updated_shelf)))))
+    }
+
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        let the_shelf = self.find_own(user.id as i32, id).await?;
+        the_shelf.delete(&*self.db_pool).await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Shelf deleted".to_string()
+        )))
+    }
+
+    async fn add_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String> {
+        let the_shelf = self.find_own(user.id as i32, id).await?;
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+
+        let shelved = book_shelf::Entity::find_by_id((the_shelf.id, the_book.id))
+            .one(&*self.db_pool)
+            .await?;
+        if shelved.is_none() {
+            book_shelf::Entity::insert(book_shelf::ActiveModel {
+                shelf_id: Set(the_shelf.id),
+                book_id: Set(the_book.id),
+                ..Default::default()
+            })
+            .exec_without_returning(&*self.db_pool)
+            .await?;
+        }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Book added to the shelf".to_string()
+        )))
+    }
+
+    async fn remove_book(&self, user: AuthenticatedUser, id: i32, book_id: i32) -> Response<String> {
+        let the_shelf = self.find_own(user.id as i32, id).await?;
+
+        let res = book_shelf::Entity::delete_by_id((the_shelf.id, book_id))
+            .exec(&*self.db_pool)
+            .await?;
+        if res.rows_affected == 0 {
+            return Err(AppError::not_found("Book"));
+        }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Book removed from the shelf".to_string()
+        )))
+    }
+}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{prelude::DateTimeUtc, sea_query::{Alias, Expr, Func, SimpleExpr}, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::{domain::{entities::{author, book, reading_goal, reading_session, reading_state, review}, repositories::stats_repository::StatsRepository, value_object::{reading::ReadingStatus, review::ResRating, stats::{check_year, ReqReadingGoal, ReqStatsQuery, ResAuthorCount, ResGoalProgress, ResMonthCount, ResReadingGoal, ResStats, ResYearCount, TOP_AUTHORS}}}, infrastructure::rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}};
+
+#[derive(FromQueryResult)]
+struct PeriodRow {
+    period: i32,
+    books: i64,
+}
+
+#[derive(FromQueryResult)]
+struct PagesRow {
+    pages: Option<i64>,
+    sessions: i64,
+}
+
+#[derive(FromQueryResult)]
+struct RatingRow {
+    half_stars_sum: Option<i64>,
+    count: i64,
+}
+
+#[derive(FromQueryResult)]
+struct AuthorRow {
+    author_id: i32,
+    firstname: String,
+    lastname: String,
+    books: i64,
+}
+
+// the first instant of the year and of the next one
+fn year_range(year: i32) -> (DateTimeUtc, DateTimeUtc) {
+    let start = |y: i32| NaiveDate::from_ymd_opt(y, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    (start(year), start(year + 1))
+}
+
+// a date function of MySQL over the finish date, e.g. YEAR or MONTH
+fn finished(function: &str) -> SimpleExpr {
+    Func::cust(Alias::new(function))
+        .arg(Expr::col((reading_state::Entity, reading_state::Column::FinishedAt)))
+        .into()
+}
+
+pub struct StatsRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl StatsRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // finished books of the user outside the trash, counted per YEAR or MONTH of the finish
+    async fn finished_per(&self, user_id: i32, function: &str, within: Option<(DateTimeUtc, DateTimeUtc)>) -> Result<Vec<PeriodRow>, AppError> {
+        let mut select = reading_state::Entity::find()
+            .select_only()
+            .column_as(finished(function), "period")
+            .column_as(reading_state::Column::Id.count(), "books")
+            .inner_join(book::Entity)
+            .filter(reading_state::Column::UserId.eq(user_id))
+            .filter(reading_state::Column::Status.eq(ReadingStatus::Finished.as_str()))
+            .filter(reading_state::Column::FinishedAt.is_not_null())
+            .filter(book::Column::DeletedAt.is_null());
+        if let Some((from, to)) = within {
+            select = select
+                .filter(reading_state::Column::FinishedAt.gte(from))
+                .filter(reading_state::Column::FinishedAt.lt(to));
+        }
+
+        Ok(select
+            .group_by(finished(function))
+            .order_by_asc(finished(function))
+            .into_model::<PeriodRow>()
+            .all(&*self.db_pool)
+            .await?)
+    }
+
+    async fn find_goal(&self, user_id: i32, year: i32) -> Result<Option<reading_goal::Model>, AppError> {
+        Ok(reading_goal::Entity::find()
+            .filter(reading_goal::Column::UserId.eq(user_id))
+            .filter(reading_goal::Column::Year.eq(year))
+            .one(&*self.db_pool)
+            .await?)
+    }
+}
+
+#[async_trait]
+impl StatsRepository for StatsRepositoryImplSql {
+    async fn stats(&self, user: AuthenticatedUser, query: ReqStatsQuery) -> Response<Json<ResStats>> {
+        let user_id = user.id as i32;
+        let today = Utc::now().date_naive();
+        let year = check_year(query.year.unwrap_or(today.year()))?;
+        let (from, to) = year_range(year);
+
+        let books_per_year: Vec<ResYearCount> = self
+            .finished_per(user_id, "YEAR", None)
+            .await?
+            .into_iter()
+            .map(|row| ResYearCount { year: row.period, books: row.books })
+            .collect();
+
+        // every month of the year, the ones without a finished book at zero
+        let per_month = self.finished_per(user_id, "MONTH", Some((from, to))).await?;
+        let books_per_month: Vec<ResMonthCount> = (1..=12)
+            .map(|month| ResMonthCount {
+                month,
+                books: per_month
+                    .iter()
+                    .find(|row| row.period == month as i32)
+                    .map_or(0, |row| row.books),
+            })
+            .collect();
+        let books_read: i64 = books_per_month.iter().map(|m| m.books).sum();
+
+        // pages of the sessions started in the year, summed as an integer like the ratings
+        let pages = reading_session::Entity::find()
+            .select_only()
+            .column_as(
+                Expr::expr(Func::sum(
+                    Expr::col((reading_session::Entity, reading_session::Column::EndPage))
+                        .sub(Expr::col((reading_session::Entity, reading_session::Column::StartPage))),
+                ))
+                .cast_as(Alias::new("SIGNED")),
+                "pages",
+            )
+            .column_as(reading_session::Column::Id.count(), "sessions")
+            .inner_join(book::Entity)
+            .filter(reading_session::Column::UserId.eq(user_id))
+            .filter(reading_session::Column::StartedAt.gte(from))
+            .filter(reading_session::Column::StartedAt.lt(to))
+            .filter(book::Column::DeletedAt.is_null())
+            .into_model::<PagesRow>()
+            .one(&*self.db_pool)
+            .await?;
+        let (pages_read, sessions) = pages.map_or((0, 0), |row| (row.pages.unwrap_or(0), row.sessions));
+
+        // the ratings the user gave in the year
+        let rating = review::Entity::find()
+            .select_only()
+            .column_as(Expr::col((review::Entity, review::Column::HalfStars)).sum().cast_as(Alias::new("SIGNED")), "half_stars_sum")
+            .column_as(review::Column::Id.count(), "count")
+            .inner_join(book::Entity)
+            .filter(review::Column::UserId.eq(user_id))
+            .filter(review::Column::CreatedAt.gte(from))
+            .filter(review::Column::CreatedAt.lt(to))
+            .filter(book::Column::DeletedAt.is_null())
+            .into_model::<RatingRow>()
+            .one(&*self.db_pool)
+            .await?
+            .map_or_else(ResRating::default, |row| ResRating::new(row.half_stars_sum.unwrap_or(0), row.count));
+
+        // the authors with the most books in the whole library
+        let top_authors: Vec<ResAuthorCount> = book::Entity::find()
+            .select_only()
+            .column_as(author::Column::Id, "author_id")
+            .column(author::Column::Firstname)
+            .column(author::Column::Lastname)
+            .column_as(book::Column::Id.count(), "books")
+            .inner_join(author::Entity)
+            .filter(book::Column::UserId.eq(user_id))
+            .filter(book::Column::DeletedAt.is_null())
+            .filter(author::Column::DeletedAt.is_null())
+            .group_by(author::Column::Id)
+            .group_by(author::Column::Firstname)
+            .group_by(author::Column::Lastname)
+            .order_by_desc(book::Column::Id.count())
+            .order_by_asc(author::Column::Lastname)
+            .limit(TOP_AUTHORS)
+            .into_model::<AuthorRow>()
+            .all(&*self.db_pool)
+            .await?
+            .into_iter()
+            .map(|row| ResAuthorCount {
+                author_id: row.author_id,
+                firstname: row.firstname,
+                lastname: row.lastname,
+                books: row.books,
+            })
+            .collect();
+
+        let goal = self
+            .find_goal(user_id, year)
+            .await?
+            .map(|goal| ResGoalProgress::new(&goal, books_read, pages_read, today));
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResStats {
+                year,
+                books_per_year,
+                books_per_month,
+                pages_read,
+                sessions,
+                rating,
+                top_authors,
+                goal,
+            }),
+        )))
+    }
+
+    async fn set_goal(&self, user: AuthenticatedUser, year: i32, req_goal: Json<ReqReadingGoal>) -> Response<Json<ResReadingGoal>> {
+        let user_id = user.id as i32;
+        let year = check_year(year)?;
+        let now = Utc::now();
+
+        let saved = match self.find_goal(user_id, year).await? {
+            Some(existing) => {
+                let mut goal: reading_goal::ActiveModel = existing.into();
+                goal.target_books = Set(req_goal.target_books);
+                goal.target_pages = Set(req_goal.target_pages);
+                goal.updated_at = Set(Some(now));
+                goal.update(&*self.db_pool).await?
+            }
+            None => {
+                reading_goal::ActiveModel {
+                    user_id: Set(user_id),
+                    year: Set(year),
+                    target_books: Set(req_goal.target_books),
+                    target_pages: Set(req_goal.target_pages),
+                    created_at: Set(Some(now)),
+                    updated_at: Set(Some(now)),
+                    ..Default::default()
+                }
+                .insert(&*self.db_pool)
+                .await?
+            }
+        };
+
+        Ok(SuccessResponse((Status::Ok, Json(ResReadingGoal::from(&saved)))))
+    }
+
+    async fn delete_goal(&self, user: AuthenticatedUser, year: i32) -> Response<String> {
+        let res = reading_goal::Entity::delete_many()
+            .filter(reading_goal::Column::UserId.eq(user.id as i32))
+            .filter(reading_goal::Column::Year.eq(year))
+            .exec(&*self.db_pool)
+            .await?;
+        if res.rows_affected == 0 {
+            return Err(AppError::not_found("Reading goal"));
+        }
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Reading goal deleted".to_string()
+        )))
+    }
+}
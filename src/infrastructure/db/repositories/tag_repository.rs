@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use rocket::{async_trait, http::Status, serde::json::{json, Json}};
+use sea_orm::{sea_query::{Expr, IntoCondition, LikeExpr, Query}, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult, JoinType, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait};
+
+use crate::{domain::{entities::{book, book_tag, tag}, repositories::tag_repository::TagRepository, value_object::tag::{normalize_tag, normalize_tags, ReqBookTags, ReqTag, ReqTagQuery, ResBookTags, ResTag, ResTagList, TAG_SUGGESTIONS_DEFAULT, TAG_SUGGESTIONS_MAX}}, infrastructure::{db::query::book_query::{bump_versions, find_book}, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+#[derive(FromQueryResult)]
+struct TagUsage {
+    id: i32,
+    name: String,
+    books: i64,
+}
+
+// a LIKE pattern matching names that start with `prefix`
+fn prefix_pattern(prefix: &str) -> LikeExpr {
+    let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    LikeExpr::new(format!("{}%", escaped)).escape('\\')
+}
+
+pub struct TagRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl TagRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+
+    // find a tag among the user's own
+    async fn find_own(&self, user_id: i32, id: i32) -> Result<tag::Model, AppError> {
+        tag::Entity::find_by_id(id)
+            .filter(tag::Column::UserId.eq(user_id))
+            .one(&*self.db_pool)
+            .await?
+            .ok_or_else(|| AppError::not_found("Tag"))
+    }
+
+    // the books answer with their tag names, so a change to the tag is a new version of them
+    async fn bump_tagged_books<C: ConnectionTrait>(db: &C, tag_id: i32) -> Result<(), DbErr> {
+        bump_versions()
+            .filter(
+                book::Column::Id.in_subquery(
+                    Query::select()
+                        .column(book_tag::Column::BookId)
+                        .from(book_tag::Entity)
+                        .and_where(book_tag::Column::TagId.eq(tag_id))
+                        .to_owned(),
+                ),
+            )
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TagRepository for TagRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser, query: ReqTagQuery) -> Response<Json<ResTagList>> {
+        let limit = query.limit.unwrap_or(TAG_SUGGESTIONS_DEFAULT).clamp(1, TAG_SUGGESTIONS_MAX);
+
+        // books in the trash do not count
+        let mut select = tag::Entity::find()
+            .select_only()
+            .column(tag::Column::Id)
+            .column(tag::Column::Name)
+            .column_as(book::Column::Id.count(), "books")
+            .join(JoinType::LeftJoin, tag::Relation::BookTag.def())
+            .join(
+                JoinType::LeftJoin,
+                book_tag::Relation::Book
+                    .def()
+                    .on_condition(|_, book_table| Expr::col((book_table, book::Column::DeletedAt)).is_null().into_condition()),
+            )
+            .filter(tag::Column::UserId.eq(user.id as i32));
+        if let Some(q) = query.q.as_deref().map(normalize_tag).filter(|q| !q.is_empty()) {
+            select = select.filter(Expr::col((tag::Entity, tag::Column::Name)).like(prefix_pattern(&q)));
+        }
+
+        let tags = select
+            .group_by(tag::Column::Id)
+            .group_by(tag::Column::Name)
+            .order_by(book::Column::Id.count(), Order::Desc)
+            .order_by_asc(tag::Column::Name)
+            .limit(limit)
+            .into_model::<TagUsage>()
+            .all(&*self.db_pool)
+            .await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResTagList {
+                total: tags.len(),
+                tags: tags.into_iter().map(|t| ResTag { id: t.id, name: t.name, books: t.books }).collect(),
+            })
+        )))
+    }
+
+    async fn rename(&self, user: AuthenticatedUser, id: i32, req_tag: Json<ReqTag>) -> Response<Json<ResTag>> {
+        let the_tag = self.find_own(user.id as i32, id).await?;
+        let name = normalize_tag(&req_tag.name);
+
+        let other = tag::Entity::find()
+            .filter(tag::Column::UserId.eq(user.id as i32))
+            .filter(tag::Column::Name.eq(name.as_str()))
+            .filter(tag::Column::Id.ne(id))
+            .one(&*self.db_pool)
+            .await?;
+        if let Some(other) = other {
+            return Err(AppError::conflict(
+                "A tag with this name already exists",
+                Some(json!({ "tag_id": other.id })),
+            ));
+        }
+
+        let mut tag_active: tag::ActiveModel = the_tag.into();
+        tag_active.name = Set(name);
+        let txn = self.db_pool.begin().await?;
+        let renamed_tag = tag_active.update(&txn).await?;
+        Self::bump_tagged_books(&txn, renamed_tag.id).await?;
+        txn.commit().await?;
+
+        let books = book_tag::Entity::find()
+            .inner_join(book::Entity)
+            .filter(book_tag::Column::TagId.eq(renamed_tag.id))
+            .filter(book::Column::DeletedAt.is_null())
+            .count(&*self.db_pool)
+            .await? as i64;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResTag { id: renamed_tag.id, name: renamed_tag.name, books })
+        )))
+    }
+
+    async fn delete(&self, user: AuthenticatedUser, id: i32) -> Response<String> {
+        let the_tag = self.find_own(user.id as i32, id).await?;
+        // bumped while the links to the tag are still there
+        let txn = self.db_pool.begin().await?;
+        Self::bump_tagged_books(&txn, the_tag.id).await?;
+        the_tag.delete(&txn).await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            "Tag deleted".to_string()
+        )))
+    }
+
+    async fn set_book_tags(&self, user: AuthenticatedUser, book_id: i32, req_tags: Json<ReqBookTags>) -> Response<Json<ResBookTags>> {
+        let the_book = find_book(&*self.db_pool, user.id as i32, book_id).await?;
+        let mut names = normalize_tags(&req_tags.tags);
+        names.sort();
+
+        let txn = self.db_pool.begin().await?;
+
+        let mut tags = Vec::new();
+        if !names.is_empty() {
+            let find_tags = tag::Entity::find()
+                .filter(tag::Column::UserId.eq(user.id as i32))
+                .filter(tag::Column::Name.is_in(names.iter().cloned()));
+            tags = find_tags.clone().all(&txn).await?;
+
+            let missing: Vec<tag::ActiveModel> = names
+                .iter()
+                .filter(|name| !tags.iter().any(|t| &t.name == *name))
+                .map(|name| tag::ActiveModel {
+                    user_id: Set(user.id as i32),
+                    name: Set(name.to_owned()),
+                    ..Default::default()
+                })
+                .collect();
+            if !missing.is_empty() {
+                tag::Entity::insert_many(missing).exec(&txn).await?;
+                tags = find_tags.all(&txn).await?;
+            }
+        }
+
+        book_tag::Entity::delete_many()
+            .filter(book_tag::Column::BookId.eq(the_book.id))
+            .exec(&txn)
+            .await?;
+        if !tags.is_empty() {
+            book_tag::Entity::insert_many(tags.iter().map(|t| book_tag::ActiveModel {
+                book_id: Set(the_book.id),
+                tag_id: Set(t.id),
+            }))
+            .exec_without_returning(&txn)
+            .await?;
+        }
+        bump_versions()
+            .filter(book::Column::Id.eq(the_book.id))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResBookTags {
+                book_id: the_book.id,
+                tags: names,
+            })
+        )))
+    }
+}
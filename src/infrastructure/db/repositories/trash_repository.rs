@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{prelude::DateTimeUtc, sea_query::Query, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, TransactionTrait};
+
+use crate::{domain::{entities::{author, book}, repositories::trash_repository::TrashRepository, value_object::{author::ResAuthor, cover::CoverRef, trash::{PurgeCount, Purged, ResTrash, ResTrashedAuthor, ResTrashedBook}}}, infrastructure::{db::query::contributor_query::res_books, rocket_http::{middleware::jwt_auth::AuthenticatedUser, response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}}}};
+
+pub struct TrashRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl TrashRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+}
+
+#[async_trait]
+impl TrashRepository for TrashRepositoryImplSql {
+    async fn index(&self, user: AuthenticatedUser, retention_days: u64) -> Response<Json<ResTrash>> {
+        let conn = Arc::clone(&self.db_pool);
+        let retention = Duration::days(retention_days as i64);
+
+        let books = book::Entity::find()
+            .filter(book::Column::UserId.eq(user.id as i32))
+            .filter(book::Column::DeletedAt.is_not_null())
+            .order_by_desc(book::Column::DeletedAt)
+            .all(&*conn)
+            .await?;
+
+        let authors = author::Entity::find()
+            .filter(author::Column::UserId.eq(user.id as i32))
+            .filter(author::Column::DeletedAt.is_not_null())
+            .order_by_desc(author::Column::DeletedAt)
+            .all(&*conn)
+            .await?;
+
+        let books = res_books(&*conn, &books)
+            .await?
+            .into_iter()
+            .zip(books.iter())
+            .filter_map(|(book, b)| b.deleted_at.map(|deleted_at| ResTrashedBook {
+                book,
+                deleted_at,
+                purge_at: deleted_at + retention,
+            }))
+            .collect();
+
+        let authors = authors
+            .iter()
+            .filter_map(|a| a.deleted_at.map(|deleted_at| ResTrashedAuthor {
+                author: ResAuthor::from(a),
+                deleted_at,
+                purge_at: deleted_at + retention,
+            }))
+            .collect();
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResTrash { books, authors })
+        )))
+    }
+
+    async fn purge(&self, before: DateTimeUtc) -> Result<Purged, AppError> {
+        let txn = self.db_pool.begin().await?;
+
+        // the uploaded covers are only known from the rows about to go
+        let covers = book::Entity::find()
+            .filter(book::Column::DeletedAt.lt(before))
+            .filter(book::Column::CoverBlob.is_not_null())
+            .all(&txn)
+            .await?
+            .into_iter()
+            .filter_map(|b| b.cover_blob.zip(b.cover_type).map(|(hash, content_type)| (b.id, CoverRef { hash, content_type })))
+            .collect();
+
+        // books first, an author is only purged once none of its books remain
+        let books = book::Entity::delete_many()
+            .filter(book::Column::DeletedAt.lt(before))
+            .exec(&txn)
+            .await?;
+
+        let authors = author::Entity::delete_many()
+            .filter(author::Column::DeletedAt.lt(before))
+            .filter(author::Column::Id.not_in_subquery(
+                Query::select()
+                    .column(book::Column::AuthorId)
+                    .from(book::Entity)
+                    .to_owned()
+            ))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        Ok(Purged {
+            count: PurgeCount {
+                books: books.rows_affected,
+                authors: authors.rows_affected,
+            },
+            covers,
+        })
+    }
+}
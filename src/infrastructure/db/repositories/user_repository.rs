@@ -0,0 +1,68 @@
+use std::{sync::Arc, time::SystemTime};
+
+use rocket::{async_trait, http::Status, serde::json::Json};
+use sea_orm::{prelude::DateTimeUtc, sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+
+use crate::{domain::{entities::{refresh_token, user}, repositories::user_repository::UserRepository, value_object::user::{ReqRole, ResUser, ResUserList}}, infrastructure::rocket_http::response_type::{app_error::AppError, custom_response::{Response, SuccessResponse}}};
+
+use crate::domain::entities::user::Entity as User;
+
+pub struct UserRepositoryImplSql {
+    pub db_pool: Arc<DatabaseConnection>
+}
+
+impl UserRepositoryImplSql {
+    pub fn new(db_pool: Arc<DatabaseConnection>) -> Self {
+        Self {
+            db_pool
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImplSql {
+    // get all users
+    async fn index(&self) -> Response<Json<ResUserList>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        let result = User::find().order_by_asc(user::Column::Id).all(&*conn).await?;
+        let users: Vec<ResUser> = result.iter().map(ResUser::from).collect();
+
+        Ok(SuccessResponse((
+            Status::Ok,
+            Json(ResUserList {
+                total: users.len(),
+                users
+            })
+        )))
+    }
+
+    async fn update_role(&self, id: i32, req_role: Json<ReqRole>) -> Response<Json<ResUser>> {
+        let conn = Arc::clone(&self.db_pool);
+
+        let the_user = User::find_by_id(id)
+            .one(&*conn)
+            .await?
+            .ok_or_else(|| AppError::not_found("User"))?;
+
+        let role_changed = the_user.role != req_role.role.as_str();
+        let mut user_active: user::ActiveModel = the_user.into();
+        user_active.role = Set(req_role.role.to_string());
+        user_active.updated_at = Set(Some(DateTimeUtc::from(SystemTime::now())));
+
+        // access tokens carry the role, the user signs in again to get the new one
+        let txn = conn.begin().await?;
+        let updated_user = user_active.update(&txn).await?;
+        if role_changed {
+            refresh_token::Entity::update_many()
+                .col_expr(refresh_token::Column::RevokedAt, Expr::value(DateTimeUtc::from(SystemTime::now())))
+                .filter(refresh_token::Column::UserId.eq(id))
+                .filter(refresh_token::Column::RevokedAt.is_null())
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+
+        Ok(SuccessResponse((Status::Ok, Json(ResUser::from(&updated_user)))))
+    }
+}
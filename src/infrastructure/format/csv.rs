@@ -0,0 +1,83 @@
+// RFC 4180 records with the line each one starts on: comma separated,
+// fields may be quoted with `"`, a quote inside a quoted field is doubled,
+// records end with LF or CRLF and blank lines are left out
+pub fn parse(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    // a quoted field is over, only a separator may follow
+    let mut closed = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => {
+                    quoted = false;
+                    closed = true;
+                }
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                closed = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                if !field.is_empty() || !record.is_empty() || closed {
+                    record.push(std::mem::take(&mut field));
+                    records.push((start, std::mem::take(&mut record)));
+                }
+                closed = false;
+                line += 1;
+                start = line;
+            }
+            '"' if field.is_empty() && !closed => quoted = true,
+            _ if closed => return Err(format!("unexpected character after a quoted field on line {}", line)),
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(format!("unterminated quoted field starting on line {}", start));
+    }
+    if !field.is_empty() || !record.is_empty() || closed {
+        record.push(field);
+        records.push((start, record));
+    }
+
+    Ok(records)
+}
+
+// a field quoted only when it has to be
+pub fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// one record with its line ending
+pub fn record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields.iter().map(|f| escape(f.as_ref())).collect::<Vec<_>>().join(",");
+    line.push_str("\r\n");
+    line
+}
@@ -0,0 +1,66 @@
+use rocket::{futures::{stream::BoxStream, StreamExt}, http::ContentType, serde::json};
+
+use crate::{domain::value_object::export::{ExportFormat, ResExportRow}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::csv;
+
+pub fn content_type(format: ExportFormat) -> ContentType {
+    match format {
+        ExportFormat::Csv => ContentType::CSV,
+        ExportFormat::Jsonl => ContentType::new("application", "x-ndjson"),
+        ExportFormat::Json => ContentType::JSON,
+    }
+}
+
+// first field of the CSV record that replaces the rest of a failed export
+pub const CSV_ERROR_MARKER: &str = "#error";
+
+// the record that ends a failed export, so a client can tell it from a complete one
+fn error_record(format: ExportFormat, e: &AppError) -> String {
+    let body = e.to_body();
+    match format {
+        ExportFormat::Csv => csv::record(&[CSV_ERROR_MARKER, body.code, &body.message]),
+        // after the closed array for the JSON form, which no longer parses as one document
+        ExportFormat::Jsonl | ExportFormat::Json => format!("{}\n", json::json!({ "error": body })),
+    }
+}
+
+// the rows rendered chunk by chunk, a failure is logged and ends the output with an error record
+pub fn render(format: ExportFormat, rows: BoxStream<'static, Result<ResExportRow, AppError>>) -> BoxStream<'static, String> {
+    async_stream::stream! {
+        let mut rows = rows;
+        let mut first = true;
+
+        match format {
+            ExportFormat::Csv => yield csv::record(&ResExportRow::COLUMNS),
+            ExportFormat::Json => yield "[".to_string(),
+            ExportFormat::Jsonl => {}
+        }
+
+        while let Some(row) = rows.next().await {
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    e.log();
+                    if format == ExportFormat::Json {
+                        yield "\n]\n".to_string();
+                    }
+                    yield error_record(format, &e);
+                    return;
+                }
+            };
+
+            yield match format {
+                ExportFormat::Csv => csv::record(&row.fields()),
+                ExportFormat::Jsonl => format!("{}\n", json::to_string(&row).unwrap_or_default()),
+                ExportFormat::Json => format!("{}{}", if first { "\n" } else { ",\n" }, json::to_string(&row).unwrap_or_default()),
+            };
+            first = false;
+        }
+
+        if format == ExportFormat::Json {
+            yield "\n]\n".to_string();
+        }
+    }
+    .boxed()
+}
@@ -0,0 +1,78 @@
+use rocket::serde::json::{self, serde_json::Map, Value};
+
+use crate::{domain::value_object::import::{ImportFormat, ImportRow, ReqImportRow, IMPORT_MAX_ROWS}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::csv;
+
+const REQUIRED_COLUMNS: [&str; 3] = ["title", "author_firstname", "author_lastname"];
+// an empty cell of these columns means no value, `year` is the older name of `published_on`
+const OPTIONAL_COLUMNS: [&str; 5] = ["published_on", "year", "cover", "isbn", "author_bio"];
+
+// the rows of an uploaded file, a broken file fails as a whole
+// while a broken row only fails itself
+pub fn read_rows(format: ImportFormat, text: &str) -> Result<Vec<ImportRow>, AppError> {
+    let rows = match format {
+        ImportFormat::Csv => csv_rows(text)?,
+        ImportFormat::Json => json_rows(text)?,
+    };
+
+    if rows.is_empty() || rows.len() > IMPORT_MAX_ROWS {
+        return Err(AppError::invalid_field("file", "must hold between 1 and 1000 rows"));
+    }
+    Ok(rows)
+}
+
+fn csv_rows(text: &str) -> Result<Vec<ImportRow>, AppError> {
+    let mut records = csv::parse(text)
+        .map_err(|e| AppError::invalid_field("file", &format!("is not valid CSV: {}", e)))?
+        .into_iter();
+
+    let header: Vec<String> = match records.next() {
+        Some((_, header)) => header.iter().map(|name| name.trim().to_lowercase()).collect(),
+        None => return Ok(Vec::new()),
+    };
+    if let Some(missing) = REQUIRED_COLUMNS.iter().find(|c| !header.iter().any(|h| h == *c)) {
+        return Err(AppError::invalid_field("file", &format!("is missing the `{}` column", missing)));
+    }
+
+    Ok(records
+        .map(|(row, fields)| {
+            if fields.len() != header.len() {
+                let message = format!("Expected {} fields, found {}", header.len(), fields.len());
+                return ImportRow { row, fields: Err(AppError::validation(message, None)) };
+            }
+
+            let object: Map<String, Value> = header
+                .iter()
+                .zip(fields)
+                .filter(|(name, value)| !(OPTIONAL_COLUMNS.contains(&name.as_str()) && value.trim().is_empty()))
+                .map(|(name, value)| (name.to_owned(), Value::String(value)))
+                .collect();
+
+            ImportRow { row, fields: to_row(Value::Object(object)) }
+        })
+        .collect())
+}
+
+fn json_rows(text: &str) -> Result<Vec<ImportRow>, AppError> {
+    let items: Vec<Value> = json::from_str(text)
+        .map_err(|_| AppError::invalid_field("file", "must be a JSON array of objects"))?;
+
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut item)| {
+            // dates are text, but a spreadsheet export may write a year as a number
+            for name in ["published_on", "year"] {
+                if let Some(year) = item.get_mut(name).filter(|y| y.is_number()) {
+                    *year = Value::String(year.to_string());
+                }
+            }
+            ImportRow { row: index + 1, fields: to_row(item) }
+        })
+        .collect())
+}
+
+fn to_row(value: Value) -> Result<ReqImportRow, AppError> {
+    json::from_value(value).map_err(|e| AppError::validation(format!("Invalid row: {}", e), None))
+}
@@ -0,0 +1,32 @@
+use std::{fs, io};
+
+use rocket::{async_trait, serde::json::{self, Value}};
+
+use crate::{domain::{repositories::metadata_provider::MetadataProvider, value_object::{isbn::Isbn, metadata::BookMetadata}}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+use super::open_library::{find_record, to_metadata};
+
+// lookups answered from a file shaped like an Open Library answer,
+// `{"ISBN:9780306406157": {"title": ...}}`, for tests and offline setups
+pub struct FixtureMetadataProvider {
+    document: Value,
+}
+
+impl FixtureMetadataProvider {
+    pub fn new(document: Value) -> Self {
+        Self { document }
+    }
+
+    pub fn from_file(path: &str) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let document = json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self::new(document))
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for FixtureMetadataProvider {
+    async fn lookup(&self, isbn: &Isbn) -> Result<Option<BookMetadata>, AppError> {
+        Ok(find_record(&self.document, isbn).map(|record| to_metadata(record, isbn)))
+    }
+}
@@ -0,0 +1,19 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{config::metadata_config::MetadataConfig, domain::repositories::metadata_provider::MetadataProvider};
+
+use self::{fixture_provider::FixtureMetadataProvider, open_library::OpenLibraryProvider};
+
+pub mod fixture_provider;
+pub mod open_library;
+
+// the fixtures when configured, Open Library otherwise
+pub fn metadata_provider(config: &MetadataConfig) -> Arc<dyn MetadataProvider + Send + Sync> {
+    match &config.fixtures {
+        Some(path) => Arc::new(
+            FixtureMetadataProvider::from_file(path)
+                .unwrap_or_else(|e| panic!("cannot load metadata fixtures {}: {}", path, e)),
+        ),
+        None => Arc::new(OpenLibraryProvider::new(&config.base_url, Duration::from_secs(config.timeout))),
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use hyper::{body, client::HttpConnector, header::LOCATION, Client, Uri};
+use hyper_tls::HttpsConnector;
+use rocket::{async_trait, serde::json::{self, Value}, tokio::time};
+
+use crate::{domain::{repositories::metadata_provider::MetadataProvider, value_object::{isbn::Isbn, metadata::{AuthorMetadata, BookMetadata}, published_on::PublishedOn}}, infrastructure::rocket_http::response_type::app_error::AppError};
+
+// the record of an edition in a `/api/books?jscmd=data` answer, keyed by either ISBN form
+pub fn find_record<'a>(document: &'a Value, isbn: &Isbn) -> Option<&'a Value> {
+    document
+        .get(format!("ISBN:{}", isbn.isbn13()))
+        .or_else(|| isbn.isbn10().and_then(|isbn10| document.get(format!("ISBN:{}", isbn10))))
+}
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+// a date like "1968-03-01", "March 1968" or "March 1, 1968",
+// the first four digit number alone when the rest is not understood
+fn published_on_of(date: &str) -> Option<PublishedOn> {
+    if let Some(date) = PublishedOn::parse(date) {
+        return Some(date);
+    }
+
+    let words: Vec<String> = date
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let number = |len: std::ops::RangeInclusive<usize>| {
+        words
+            .iter()
+            .find(|word| len.contains(&word.len()) && word.chars().all(|c| c.is_ascii_digit()))
+            .map(String::as_str)
+    };
+
+    let year = number(4..=4)?.parse().ok()?;
+    let month = words
+        .iter()
+        .find_map(|word| MONTHS.iter().position(|m| word.len() >= 3 && m.starts_with(word.as_str())))
+        .map(|index| index as u8 + 1);
+    let day = month.and(number(1..=2)).and_then(|day| day.parse().ok());
+
+    PublishedOn::new(year, month, day, None)
+        .or_else(|_| PublishedOn::new(year, None, None, None))
+        .ok()
+}
+
+// the last word is taken as the last name, names are not parsed any further
+fn author_of(name: &str) -> Option<AuthorMetadata> {
+    let name = name.trim();
+    match name.rsplit_once(' ') {
+        Some((firstname, lastname)) => Some(AuthorMetadata {
+            firstname: firstname.trim().to_string(),
+            lastname: lastname.to_string(),
+        }),
+        None if !name.is_empty() => Some(AuthorMetadata {
+            firstname: String::new(),
+            lastname: name.to_string(),
+        }),
+        None => None,
+    }
+}
+
+pub fn to_metadata(record: &Value, isbn: &Isbn) -> BookMetadata {
+    let text = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    let cover = record.get("cover").and_then(|cover| {
+        text(cover.get("large"))
+            .or_else(|| text(cover.get("medium")))
+            .or_else(|| text(cover.get("small")))
+    });
+
+    let authors = record
+        .get("authors")
+        .and_then(Value::as_array)
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|author| author.get("name").and_then(Value::as_str))
+                .filter_map(author_of)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    BookMetadata {
+        isbn10: isbn.isbn10(),
+        isbn13: isbn.isbn13().to_string(),
+        title: text(record.get("title")),
+        published_on: text(record.get("publish_date")).as_deref().and_then(published_on_of),
+        cover,
+        authors,
+    }
+}
+
+// redirects followed before a lookup is given up
+const MAX_REDIRECTS: usize = 5;
+
+// where a redirect points, a relative `Location` is taken on the same host
+fn redirect_target(from: &Uri, location: &str) -> Option<Uri> {
+    let target: Uri = location.parse().ok()?;
+    if target.scheme().is_some() {
+        return Some(target);
+    }
+
+    let mut parts = target.into_parts();
+    parts.scheme = from.scheme().cloned();
+    parts.authority = from.authority().cloned();
+    Uri::from_parts(parts).ok()
+}
+
+// Open Library's books API, or a stand-in at another base URL
+pub struct OpenLibraryProvider {
+    base_url: String,
+    timeout: Duration,
+    // speaks both HTTP and HTTPS
+    client: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl OpenLibraryProvider {
+    pub fn new(base_url: &str, timeout: Duration) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            timeout,
+            client: Client::builder().build(HttpsConnector::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for OpenLibraryProvider {
+    async fn lookup(&self, isbn: &Isbn) -> Result<Option<BookMetadata>, AppError> {
+        let uri: Uri = format!("{}/api/books?bibkeys=ISBN:{}&format=json&jscmd=data", self.base_url, isbn.isbn13())
+            .parse()
+            .map_err(|e| AppError::Internal(format!("invalid metadata URL: {}", e)))?;
+
+        // redirects are followed, e.g. from HTTP to HTTPS
+        let fetch = async {
+            let mut uri = uri;
+            for _ in 0..=MAX_REDIRECTS {
+                let res = self.client.get(uri.clone()).await.map_err(|e| format!("metadata lookup failed: {}", e))?;
+                let status = res.status();
+                if !status.is_redirection() {
+                    return body::to_bytes(res.into_body())
+                        .await
+                        .map(|bytes| (status, bytes))
+                        .map_err(|e| format!("metadata lookup failed: {}", e));
+                }
+
+                let location = res.headers().get(LOCATION).and_then(|l| l.to_str().ok()).unwrap_or_default();
+                uri = redirect_target(&uri, location)
+                    .ok_or_else(|| format!("metadata lookup answered {} to '{}'", status, location))?;
+            }
+            Err(format!("metadata lookup was redirected more than {} times", MAX_REDIRECTS))
+        };
+        let (status, bytes) = time::timeout(self.timeout, fetch)
+            .await
+            .map_err(|_| AppError::Upstream("metadata lookup timed out".to_string()))?
+            .map_err(AppError::Upstream)?;
+
+        if !status.is_success() {
+            return Err(AppError::Upstream(format!("metadata lookup answered {}", status)));
+        }
+
+        let document: Value = json::from_slice(&bytes)
+            .map_err(|e| AppError::Upstream(format!("metadata lookup sent invalid JSON: {}", e)))?;
+
+        Ok(find_record(&document, isbn).map(|record| to_metadata(record, isbn)))
+    }
+}
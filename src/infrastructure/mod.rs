@@ -1,2 +1,6 @@
 pub mod rocket_http;
-pub mod db;
\ No newline at end of file
+pub mod db;
+pub mod format;
+pub mod metadata;
+pub mod blob;
+pub mod thumbnail;
@@ -0,0 +1,55 @@
+use rocket::{
+    catch, catchers,
+    http::Status,
+    request::{self, Request},
+    serde::json::Json,
+    Catcher,
+};
+
+use super::response_type::app_error::{AppError, ResError};
+
+// body of a failed request or data guard, read back by the catcher
+struct GuardFailure(Option<ResError>);
+
+// keep the error body of a failing guard for the catcher
+pub fn remember_error(req: &Request<'_>, error: &AppError) {
+    req.local_cache(|| GuardFailure(Some(error.to_body())));
+}
+
+// fail a request guard, keeping the error for the response body
+pub fn guard_error<T>(req: &Request<'_>, error: AppError) -> request::Outcome<T, AppError> {
+    remember_error(req, &error);
+    request::Outcome::Error((error.status(), error))
+}
+
+fn describe(status: Status) -> (&'static str, &'static str) {
+    match status.code {
+        400 => ("bad_request", "The request could not be parsed"),
+        401 => ("unauthorized", "Authentication is required"),
+        403 => ("forbidden", "You are not allowed to perform this action"),
+        404 => ("not_found", "The requested resource was not found"),
+        409 => ("conflict", "The request conflicts with the current state"),
+        412 => ("precondition_failed", "The resource has been modified"),
+        413 => ("payload_too_large", "The request body is too large"),
+        415 => ("unsupported_media_type", "The content type is not supported"),
+        422 => ("validation_failed", "The request body or parameters are invalid"),
+        500 => ("internal_error", "An internal error occurred"),
+        code if code < 500 => ("client_error", "The request could not be processed"),
+        _ => ("server_error", "The server could not process the request"),
+    }
+}
+
+// errors raised by Rocket itself: guards, body parsing and unmatched routes
+#[catch(default)]
+fn default_catcher(status: Status, req: &Request<'_>) -> (Status, Json<ResError>) {
+    let body = req.local_cache(|| GuardFailure(None)).0.clone().unwrap_or_else(|| {
+        let (code, message) = describe(status);
+        ResError { code, message: message.to_string(), details: None }
+    });
+
+    (status, Json(body))
+}
+
+pub fn json_catchers() -> Vec<Catcher> {
+    catchers![default_catcher]
+}
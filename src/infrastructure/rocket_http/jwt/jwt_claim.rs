@@ -5,5 +5,7 @@ use rocket::serde::{Deserialize, Serialize};
 pub struct Claims {
     pub sub: i32,
     pub role: String,
+    // refresh token family the access token was issued for
+    pub sid: String,
     pub exp: u64
-}
\ No newline at end of file
+}
@@ -1 +1,2 @@
-pub mod jwt_claim;
\ No newline at end of file
+pub mod jwt_claim;
+pub mod refresh_token;
\ No newline at end of file
@@ -0,0 +1,20 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+// random opaque value handed to the client, never stored as is
+pub fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+// id shared by every token issued from the same sign in
+pub fn generate_family_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,19 +1,21 @@
+use std::sync::Arc;
+
 use jsonwebtoken::{decode, DecodingKey, Validation};
-use rocket::{
-    http::Status,
-    request::{self, FromRequest, Request}
-};
+use rocket::request::{self, FromRequest, Request};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 
-use crate::{config::jwt_config::JwtSecret, infrastructure::rocket_http::jwt::jwt_claim::Claims};
+use crate::{config::jwt_config::JwtSecret, domain::{entities::{prelude::RefreshToken, refresh_token}, value_object::user::Role}, infrastructure::rocket_http::{catchers::guard_error, jwt::jwt_claim::Claims, response_type::app_error::AppError}};
 
 
+#[derive(Clone)]
 pub struct AuthenticatedUser {
     pub id: u32,
+    pub role: Role,
 }
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for AuthenticatedUser {
-    type Error = String;
+    type Error = AppError;
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
         if let Some(token) = req.headers().get_one("token") {
@@ -27,13 +29,31 @@ impl<'r> FromRequest<'r> for AuthenticatedUser {
 
             let claims = match data {
                 Ok(p) => p.claims,
-                Err(_) => {
-                    return request::Outcome::Error((Status::Unauthorized, "Invalid token".to_string()))
-                }
+                Err(e) => return guard_error(req, e.into()),
             };
 
-            request::Outcome::Success(AuthenticatedUser { id: claims.sub as u32 })
+            // reject tokens whose session was signed out or revoked
+            if let Some(db) = req.rocket().state::<Arc<DatabaseConnection>>() {
+                let revoked = RefreshToken::find()
+                    .filter(refresh_token::Column::FamilyId.eq(&claims.sid))
+                    .filter(refresh_token::Column::RevokedAt.is_not_null())
+                    .one(&**db)
+                    .await;
+
+                match revoked {
+                    Ok(None) => {}
+                    Ok(Some(_)) => {
+                        return guard_error(req, AppError::Unauthorized("Session revoked".to_string()))
+                    }
+                    Err(e) => return guard_error(req, e.into()),
+                }
+            }
+
+            // an unknown role gets the least privilege
+            let role = claims.role.parse().unwrap_or(Role::User);
+
+            request::Outcome::Success(AuthenticatedUser { id: claims.sub as u32, role })
         } else {
-            request::Outcome::Error((Status::Unauthorized, "Token absent".to_string()))
+            guard_error(req, AppError::Unauthorized("Token absent".to_string()))
         }
-    }}
\ No newline at end of file
+    }}
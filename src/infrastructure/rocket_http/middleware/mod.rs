@@ -1,2 +1,5 @@
 pub mod cors;
-pub mod jwt_auth;
\ No newline at end of file
+pub mod jwt_auth;
+pub mod role_guard;
+pub mod validated;
+pub mod preconditions;
\ No newline at end of file
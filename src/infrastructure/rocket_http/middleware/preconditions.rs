@@ -0,0 +1,52 @@
+use std::convert::Infallible;
+
+use rocket::request::{self, FromRequest, Request};
+
+use crate::infrastructure::rocket_http::response_type::app_error::AppError;
+
+// the `If-Match` and `If-None-Match` headers of a request
+pub struct Preconditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+}
+
+// whether a comma separated tag list, or `*`, contains `etag`
+fn lists(header: &str, etag: &str, weak: bool) -> bool {
+    header.trim() == "*"
+        || header.split(',').map(str::trim).any(|tag| {
+            tag == etag || (weak && tag.strip_prefix("W/") == Some(etag))
+        })
+}
+
+impl Preconditions {
+    // fails with 412 when `If-Match` is sent and does not list `etag`
+    pub fn check_match(&self, etag: &str) -> Result<(), AppError> {
+        match &self.if_match {
+            Some(header) if !lists(header, etag, false) => Err(AppError::PreconditionFailed(
+                "The resource has been modified".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // true when `If-None-Match` lists `etag`, the client copy is fresh
+    pub fn not_modified(&self, etag: &str) -> bool {
+        self.if_none_match
+            .as_deref()
+            .is_some_and(|header| lists(header, etag, true))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Preconditions {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let header = |name| req.headers().get_one(name).map(str::to_string);
+
+        request::Outcome::Success(Preconditions {
+            if_match: header("If-Match"),
+            if_none_match: header("If-None-Match"),
+        })
+    }
+}
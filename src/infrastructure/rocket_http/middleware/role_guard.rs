@@ -0,0 +1,52 @@
+use std::marker::PhantomData;
+
+use rocket::{
+    outcome::try_outcome,
+    request::{self, FromRequest, Request}
+};
+
+use crate::{domain::value_object::user::Role, infrastructure::rocket_http::{catchers::guard_error, response_type::app_error::AppError}};
+
+use super::jwt_auth::AuthenticatedUser;
+
+
+// marker for the minimum role a route requires
+pub trait RoleRequirement: Send + Sync {
+    const ROLE: Role;
+}
+
+pub struct ModeratorRole;
+
+impl RoleRequirement for ModeratorRole {
+    const ROLE: Role = Role::Moderator;
+}
+
+pub struct AdminRole;
+
+impl RoleRequirement for AdminRole {
+    const ROLE: Role = Role::Admin;
+}
+
+// authenticated user holding at least the role `R`
+pub struct RequireRole<R: RoleRequirement> {
+    pub user: AuthenticatedUser,
+    _role: PhantomData<R>,
+}
+
+pub type ModeratorUser = RequireRole<ModeratorRole>;
+pub type AdminUser = RequireRole<AdminRole>;
+
+#[rocket::async_trait]
+impl<'r, R: RoleRequirement> FromRequest<'r> for RequireRole<R> {
+    type Error = AppError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let user = try_outcome!(req.guard::<AuthenticatedUser>().await);
+
+        if user.role >= R::ROLE {
+            request::Outcome::Success(RequireRole { user, _role: PhantomData })
+        } else {
+            guard_error(req, AppError::Forbidden("Insufficient role".to_string()))
+        }
+    }
+}
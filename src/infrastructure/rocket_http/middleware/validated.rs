@@ -0,0 +1,56 @@
+use rocket::{
+    data::{self, Data, FromData},
+    http::Status,
+    outcome::Outcome,
+    request::Request,
+    serde::{json::{self, Json}, Deserialize},
+};
+use validator::Validate;
+
+use crate::infrastructure::rocket_http::{catchers::remember_error, response_type::app_error::AppError};
+
+// a JSON body that passed its `Validate` rules
+pub struct Validated<T>(pub Json<T>);
+
+impl<T> Validated<T> {
+    pub fn into_inner(self) -> Json<T> {
+        self.0
+    }
+}
+
+fn data_error<'r, T>(req: &Request<'_>, status: Status, error: AppError) -> data::Outcome<'r, T, AppError> {
+    remember_error(req, &error);
+    Outcome::Error((status, error))
+}
+
+#[rocket::async_trait]
+impl<'r, T: Deserialize<'r> + Validate> FromData<'r> for Validated<T> {
+    type Error = AppError;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let body = match Json::<T>::from_data(req, data).await {
+            Outcome::Success(body) => body,
+            Outcome::Forward(f) => return Outcome::Forward(f),
+            // well-formed JSON of the wrong shape, e.g. a missing field
+            Outcome::Error((status, json::Error::Parse(_, e))) if status == Status::UnprocessableEntity => {
+                let error = AppError::validation(e.to_string(), None);
+                return data_error(req, error.status(), error);
+            }
+            Outcome::Error((status, json::Error::Parse(_, e))) => {
+                return data_error(req, status, AppError::BadRequest(e.to_string()));
+            }
+            // unreadable or oversized body, described by the catcher
+            Outcome::Error((status, json::Error::Io(e))) => {
+                return Outcome::Error((status, AppError::BadRequest(e.to_string())));
+            }
+        };
+
+        match body.validate() {
+            Ok(()) => Outcome::Success(Validated(body)),
+            Err(errors) => {
+                let error = AppError::from(errors);
+                data_error(req, error.status(), error)
+            }
+        }
+    }
+}
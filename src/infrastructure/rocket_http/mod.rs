@@ -2,4 +2,4 @@ pub mod response_type;
 pub mod routes;
 pub mod middleware;
 pub mod jwt;
-
+pub mod catchers;
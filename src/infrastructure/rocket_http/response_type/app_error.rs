@@ -0,0 +1,206 @@
+use std::{collections::BTreeMap, fmt};
+
+use rocket::{
+    http::Status,
+    request::Request,
+    response::{self, Responder},
+    serde::{json::{json, Json, Value}, Serialize},
+};
+use sea_orm::{DbErr, SqlErr};
+use validator::ValidationErrors;
+
+// every error a handler can return, rendered as `ResError`
+#[derive(Debug)]
+pub enum AppError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    PreconditionFailed(String),
+    PayloadTooLarge(String),
+    UnsupportedMediaType(String),
+    Conflict { message: String, details: Option<Value> },
+    Validation { message: String, details: Option<Value> },
+    Database(DbErr),
+    Internal(String),
+    // a service we depend on failed or timed out
+    Upstream(String),
+}
+
+// body of every error response
+#[derive(Clone, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct ResError {
+    // stable, machine readable
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl AppError {
+    pub fn not_found(what: &str) -> Self {
+        AppError::NotFound(format!("{} not found", what))
+    }
+
+    pub fn conflict(message: impl Into<String>, details: Option<Value>) -> Self {
+        AppError::Conflict { message: message.into(), details }
+    }
+
+    pub fn validation(message: impl Into<String>, details: Option<Value>) -> Self {
+        AppError::Validation { message: message.into(), details }
+    }
+
+    // a validation error on a single field, shaped like the derived ones
+    pub fn invalid_field(field: &str, message: &str) -> Self {
+        AppError::validation("Validation failed", Some(json!({ field: [message] })))
+    }
+
+    // internals never reach the client, keep them in the log
+    pub fn log(&self) {
+        if let AppError::Database(_) | AppError::Internal(_) | AppError::Upstream(_) = self {
+            rocket::error!("{}", self);
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match self {
+            AppError::BadRequest(_) => Status::BadRequest,
+            AppError::Unauthorized(_) => Status::Unauthorized,
+            AppError::Forbidden(_) => Status::Forbidden,
+            AppError::NotFound(_) => Status::NotFound,
+            AppError::PreconditionFailed(_) => Status::PreconditionFailed,
+            AppError::PayloadTooLarge(_) => Status::PayloadTooLarge,
+            AppError::UnsupportedMediaType(_) => Status::UnsupportedMediaType,
+            AppError::Conflict { .. } => Status::Conflict,
+            AppError::Validation { .. } => Status::UnprocessableEntity,
+            AppError::Database(_) | AppError::Internal(_) => Status::InternalServerError,
+            AppError::Upstream(_) => Status::BadGateway,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::PreconditionFailed(_) => "precondition_failed",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::Conflict { .. } => "conflict",
+            AppError::Validation { .. } => "validation_failed",
+            AppError::Database(_) => "database_error",
+            AppError::Internal(_) => "internal_error",
+            AppError::Upstream(_) => "upstream_error",
+        }
+    }
+
+    pub fn to_body(&self) -> ResError {
+        let (message, details) = match self {
+            AppError::BadRequest(message)
+            | AppError::Unauthorized(message)
+            | AppError::Forbidden(message)
+            | AppError::NotFound(message)
+            | AppError::PreconditionFailed(message)
+            | AppError::PayloadTooLarge(message)
+            | AppError::UnsupportedMediaType(message) => (message.to_owned(), None),
+            AppError::Conflict { message, details }
+            | AppError::Validation { message, details } => (message.to_owned(), details.to_owned()),
+            // internals are logged, never sent to the client
+            AppError::Database(_) => ("A database error occurred".to_string(), None),
+            AppError::Internal(_) => ("An internal error occurred".to_string(), None),
+            AppError::Upstream(_) => ("An upstream service is unavailable".to_string(), None),
+        };
+
+        ResError {
+            code: self.code(),
+            message,
+            details,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Database(e) => write!(f, "database error: {}", e),
+            AppError::Internal(e) => write!(f, "internal error: {}", e),
+            AppError::Upstream(e) => write!(f, "upstream error: {}", e),
+            other => write!(f, "{}: {}", other.code(), other.to_body().message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl<'r> Responder<'r, 'static> for AppError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        self.log();
+
+        let status = self.status();
+        response::Response::build_from(Json(self.to_body()).respond_to(req)?)
+            .status(status)
+            .ok()
+    }
+}
+
+impl From<DbErr> for AppError {
+    fn from(e: DbErr) -> Self {
+        match e.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                AppError::conflict("A record with the same values already exists", None)
+            }
+            Some(SqlErr::ForeignKeyConstraintViolation(_)) => {
+                AppError::conflict("The record is referenced by or references another record", None)
+            }
+            _ => match e {
+                DbErr::RecordNotFound(what) => AppError::NotFound(what),
+                other => AppError::Database(other),
+            },
+        }
+    }
+}
+
+// per-field messages, e.g. `{"email": ["must be a valid email address"]}`
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let fields: BTreeMap<String, Vec<String>> = errors
+            .field_errors()
+            .into_iter()
+            .map(|(field, errors)| {
+                let messages = errors
+                    .iter()
+                    .map(|e| e.message.as_ref().unwrap_or(&e.code).to_string())
+                    .collect();
+                (field.to_string(), messages)
+            })
+            .collect();
+
+        AppError::validation("Validation failed", Some(json!(fields)))
+    }
+}
+
+impl From<bcrypt::BcryptError> for AppError {
+    fn from(e: bcrypt::BcryptError) -> Self {
+        AppError::Internal(format!("password hashing error: {}", e))
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for AppError {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        use jsonwebtoken::errors::ErrorKind;
+
+        match e.kind() {
+            ErrorKind::ExpiredSignature => AppError::Unauthorized("Token expired".to_string()),
+            ErrorKind::InvalidToken
+            | ErrorKind::InvalidSignature
+            | ErrorKind::InvalidAlgorithm
+            | ErrorKind::ImmatureSignature
+            | ErrorKind::Base64(_)
+            | ErrorKind::Json(_)
+            | ErrorKind::Utf8(_) => AppError::Unauthorized("Invalid token".to_string()),
+            _ => AppError::Internal(format!("token error: {}", e)),
+        }
+    }
+}
@@ -1,10 +1,56 @@
-use rocket::{http::Status, Responder};
+use rocket::{http::{ContentType, Status}, request::Request, response::{self, Responder}};
+
+use super::app_error::AppError;
 
 
 #[derive(Responder)]
 pub struct SuccessResponse<T>(pub (Status, T));
 
-#[derive(Responder)]
-pub struct ErrorResponse(pub (Status, String));
+pub type Response<T> = Result<SuccessResponse<T>, AppError>;
+
+// a body sent with its `ETag`, without body for 304 Not Modified
+pub struct Tagged<T> {
+    pub etag: String,
+    pub body: Option<T>,
+}
+
+impl<'r, 'o: 'r, T: Responder<'r, 'o>> Responder<'r, 'o> for Tagged<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = match self.body {
+            Some(body) => body.respond_to(req)?,
+            None => response::Response::new(),
+        };
+        res.set_raw_header("ETag", self.etag);
+        Ok(res)
+    }
+}
+
+// a body the client should save as `filename`
+pub struct Download<T> {
+    pub filename: String,
+    pub content_type: ContentType,
+    pub body: T,
+}
+
+impl<'r, 'o: 'r, T: Responder<'r, 'o>> Responder<'r, 'o> for Download<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.body.respond_to(req)?;
+        res.set_header(self.content_type);
+        res.set_raw_header("Content-Disposition", format!("attachment; filename=\"{}\"", self.filename));
+        Ok(res)
+    }
+}
+
+// a body caches may keep for a while, with its `Cache-Control`
+pub struct Cached<T> {
+    pub cache_control: String,
+    pub body: T,
+}
 
-pub type Response<T> = Result<SuccessResponse<T>, ErrorResponse>;
\ No newline at end of file
+impl<'r, 'o: 'r, T: Responder<'r, 'o>> Responder<'r, 'o> for Cached<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut res = self.body.respond_to(req)?;
+        res.set_raw_header("Cache-Control", self.cache_control);
+        Ok(res)
+    }
+}
@@ -1 +1,2 @@
-pub mod custom_response;
\ No newline at end of file
+pub mod custom_response;
+pub mod app_error;
\ No newline at end of file
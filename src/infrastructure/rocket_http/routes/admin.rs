@@ -0,0 +1,12 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::admin;
+
+pub fn admin_routes() -> Vec<Route> {
+    routes![
+        admin::users,
+        admin::update_role,
+        admin::catalog,
+        admin::moderate_book
+    ]
+}
@@ -6,6 +6,9 @@ pub fn auth_routes() -> Vec<Route> {
     routes![
         auth::sign_in,
         auth::sign_up,
-        auth::me
+        auth::me,
+        auth::update_me,
+        auth::refresh,
+        auth::sign_out
     ]
 }
\ No newline at end of file
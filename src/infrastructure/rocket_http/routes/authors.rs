@@ -8,7 +8,9 @@ pub fn authors_routes() -> Vec<Route> {
         authors::create,
         authors::show,
         authors::update,
+        authors::patch,
         authors::delete,
+        authors::restore,
         authors::get_books
     ]
 }
\ No newline at end of file
@@ -6,8 +6,16 @@ pub fn books_routes() -> Vec<Route> {
     routes![
         books::index,
         books::create,
+        books::create_enriched,
+        books::lookup,
+        books::batch,
         books::show,
+        books::by_isbn,
         books::update,
-        books::delete
+        books::patch,
+        books::delete,
+        books::restore,
+        books::upload_cover,
+        books::cover
     ]
 }
\ No newline at end of file
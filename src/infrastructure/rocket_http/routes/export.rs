@@ -0,0 +1,9 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::export;
+
+pub fn export_routes() -> Vec<Route> {
+    routes![
+        export::export
+    ]
+}
@@ -0,0 +1,9 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::import;
+
+pub fn import_routes() -> Vec<Route> {
+    routes![
+        import::import
+    ]
+}
@@ -1,6 +1,8 @@
 use rocket::fairing::AdHoc;
 
-use super::{auth::auth_routes, authors:: authors_routes, books::books_routes};
+use crate::infrastructure::rocket_http::catchers::json_catchers;
+
+use super::{admin::admin_routes, auth::auth_routes, authors:: authors_routes, books::books_routes, export::export_routes, import::import_routes, loans::{book_loans_routes, loans_routes}, reading::{book_reading_routes, reading_routes}, reviews::reviews_routes, search::search_routes, series::series_routes, shelves::shelves_routes, stats::stats_routes, tags::{book_tags_routes, tags_routes}, trash::trash_routes};
 
 
 pub fn init_routes_setup() -> AdHoc {
@@ -9,5 +11,21 @@ pub fn init_routes_setup() -> AdHoc {
             .mount("/auth", auth_routes())
             .mount("/authors", authors_routes())
             .mount("/books", books_routes())
+            .mount("/books", book_reading_routes())
+            .mount("/books", reviews_routes())
+            .mount("/books", book_tags_routes())
+            .mount("/books", book_loans_routes())
+            .mount("/admin", admin_routes())
+            .mount("/search", search_routes())
+            .mount("/trash", trash_routes())
+            .mount("/import", import_routes())
+            .mount("/export", export_routes())
+            .mount("/series", series_routes())
+            .mount("/reading", reading_routes())
+            .mount("/shelves", shelves_routes())
+            .mount("/tags", tags_routes())
+            .mount("/loans", loans_routes())
+            .mount("/stats", stats_routes())
+            .register("/", json_catchers())
     })
 }
\ No newline at end of file
@@ -0,0 +1,18 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::loans;
+
+pub fn loans_routes() -> Vec<Route> {
+    routes![
+        loans::index,
+        loans::return_loan
+    ]
+}
+
+// mounted under /books, the loans of one book
+pub fn book_loans_routes() -> Vec<Route> {
+    routes![
+        loans::history,
+        loans::lend
+    ]
+}
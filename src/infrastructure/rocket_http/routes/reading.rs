@@ -0,0 +1,17 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::reading;
+
+// mounted under /books, next to the book routes
+pub fn book_reading_routes() -> Vec<Route> {
+    routes![
+        reading::set_status,
+        reading::log_session
+    ]
+}
+
+pub fn reading_routes() -> Vec<Route> {
+    routes![
+        reading::current
+    ]
+}
@@ -0,0 +1,14 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::reviews;
+
+// mounted under /books, a review belongs to its book
+pub fn reviews_routes() -> Vec<Route> {
+    routes![
+        reviews::index,
+        reviews::create,
+        reviews::show,
+        reviews::update,
+        reviews::delete
+    ]
+}
@@ -0,0 +1,9 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::search;
+
+pub fn search_routes() -> Vec<Route> {
+    routes![
+        search::search
+    ]
+}
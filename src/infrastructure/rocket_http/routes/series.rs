@@ -0,0 +1,15 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::series;
+
+pub fn series_routes() -> Vec<Route> {
+    routes![
+        series::index,
+        series::create,
+        series::show,
+        series::update,
+        series::patch,
+        series::delete,
+        series::books
+    ]
+}
@@ -0,0 +1,15 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::shelves;
+
+pub fn shelves_routes() -> Vec<Route> {
+    routes![
+        shelves::index,
+        shelves::create,
+        shelves::show,
+        shelves::update,
+        shelves::delete,
+        shelves::add_book,
+        shelves::remove_book
+    ]
+}
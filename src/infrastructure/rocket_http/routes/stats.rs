@@ -0,0 +1,11 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::stats;
+
+pub fn stats_routes() -> Vec<Route> {
+    routes![
+        stats::stats,
+        stats::set_goal,
+        stats::delete_goal
+    ]
+}
@@ -0,0 +1,18 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::tags;
+
+pub fn tags_routes() -> Vec<Route> {
+    routes![
+        tags::index,
+        tags::rename,
+        tags::delete
+    ]
+}
+
+// mounted under /books, next to the book routes
+pub fn book_tags_routes() -> Vec<Route> {
+    routes![
+        tags::set_book_tags
+    ]
+}
@@ -0,0 +1,9 @@
+use rocket::{routes, Route};
+
+use crate::application::controller::trash;
+
+pub fn trash_routes() -> Vec<Route> {
+    routes![
+        trash::index
+    ]
+}
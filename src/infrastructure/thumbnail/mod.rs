@@ -0,0 +1,58 @@
+use std::process::Stdio;
+
+use rocket::{async_trait, tokio::io::AsyncWriteExt};
+use tokio::process::Command;
+
+use crate::{domain::repositories::thumbnailer::Thumbnailer, infrastructure::rocket_http::response_type::app_error::AppError};
+
+// runs an image tool such as ImageMagick, the image goes through stdin and stdout
+pub struct CommandThumbnailer {
+    command: String,
+}
+
+impl CommandThumbnailer {
+    pub fn new(command: &str) -> Self {
+        Self { command: command.to_string() }
+    }
+}
+
+#[async_trait]
+impl Thumbnailer for CommandThumbnailer {
+    async fn thumbnail(&self, image: &[u8], max_side: u32) -> Result<Vec<u8>, AppError> {
+        let size = max_side.to_string();
+        let mut words = self.command.split_whitespace().map(|w| w.replace("{size}", &size));
+        let program = words.next().ok_or_else(|| AppError::Internal("empty thumbnail command".to_string()))?;
+
+        let mut child = Command::new(&program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AppError::Internal(format!("cannot run {}: {}", program, e)))?;
+
+        // written while the output is read, a large image would fill the pipes otherwise
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = image.to_vec();
+        let writer = rocket::tokio::spawn(async move {
+            let written = stdin.write_all(&input).await;
+            drop(stdin);
+            written
+        });
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| AppError::Internal(format!("{} failed: {}", program, e)))?;
+        let _ = writer.await;
+
+        if !output.status.success() || output.stdout.is_empty() {
+            return Err(AppError::validation(
+                "The cover image could not be read",
+                None,
+            ));
+        }
+        Ok(output.stdout)
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use rocket::http::Status;
 use sea_orm_migration::MigratorTrait;
-use udemy_mini_book::{ application::service::init_service_setup::init_service_setup, config::db_config::DatabaseConfig, domain::migrator::Migrator, infrastructure::{db::mysql_connection, rocket_http::{middleware::{cors::CORS, jwt_auth::AuthenticatedUser}, response_type::custom_response::{Response, SuccessResponse}, routes::init_route_setup::init_routes_setup}}};
+use udemy_mini_book::{ application::{job::trash_purge::trash_purge, service::init_service_setup::init_service_setup}, config::{cover_config::CoverConfig, db_config::DatabaseConfig}, domain::migrator::Migrator, infrastructure::{db::mysql_connection, rocket_http::{middleware::{cors::CORS, jwt_auth::AuthenticatedUser}, response_type::custom_response::{Response, SuccessResponse}, routes::init_route_setup::init_routes_setup}}};
 
 #[macro_use] extern crate rocket;
 
@@ -23,6 +23,7 @@ pub async fn me(user: AuthenticatedUser) -> Response<String> {
 
 
 #[rocket::main]
+#[allow(clippy::result_large_err)]
 async fn main() -> Result<(), rocket::Error> {
 
     // load database config
@@ -32,11 +33,17 @@ async fn main() -> Result<(), rocket::Error> {
     // running migrator
     Migrator::up(&db, None).await.unwrap();
     let db_arc = Arc::new(db);
-    rocket::build()
+    // cover uploads may be larger than Rocket's default form limits
+    let covers = CoverConfig::default();
+    let figment = rocket::Config::figment()
+        .merge(("limits.file", covers.max_bytes))
+        .merge(("limits.data-form", covers.max_bytes + 64 * 1024));
+    rocket::custom(figment)
         .attach(CORS)
         .attach(init_service_setup(Arc::clone(&db_arc)))
         .mount("/", routes![index,me])
         .attach(init_routes_setup())
+        .attach(trash_purge())
         .ignite().await?
         .launch().await?;
 
@@ -0,0 +1,110 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, no_contributors, no_ratings, no_tags, request, token_with_role};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::{book, user};
+
+fn user(role: &str) -> user::Model {
+    user::Model {
+        id: 7,
+        email: "ged@example.com".to_string(),
+        password: "hash".to_string(),
+        firstname: None,
+        lastname: None,
+        role: role.to_string(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 2,
+        author_id: 10,
+        title: "The Left Hand of Darkness".to_string(),
+        cover: "https://example.com/left-hand.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1969),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected }
+}
+
+#[rocket::async_test]
+async fn demoting_revokes_the_users_sessions() {
+    let admin = token_with_role(1, "admin");
+    let db = db()
+        .append_query_results([vec![user("admin")]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![user("user")]])
+        .append_exec_results([rows(2)]);
+    let reply = request(db, 1, Method::Put, "/admin/users/7/role", &[("token", &admin)], Some(r#"{"role":"user"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["role"], "user");
+    let revoke = reply.statements.last().unwrap();
+    assert!(revoke.sql.starts_with("UPDATE `refresh_token` SET `revoked_at` = ?"), "{}", revoke.sql);
+    assert!(revoke.sql.contains("`refresh_token`.`user_id` = ?"));
+    assert!(revoke.sql.ends_with("`refresh_token`.`revoked_at` IS NULL"));
+}
+
+#[rocket::async_test]
+async fn unchanged_role_keeps_the_sessions() {
+    let admin = token_with_role(1, "admin");
+    let db = db()
+        .append_query_results([vec![user("moderator")]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![user("moderator")]]);
+    let reply = request(db, 1, Method::Put, "/admin/users/7/role", &[("token", &admin)], Some(r#"{"role":"moderator"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(!reply.statements.iter().any(|s| s.sql.starts_with("UPDATE `refresh_token`")));
+}
+
+#[rocket::async_test]
+async fn catalog_is_paginated() {
+    let admin = token_with_role(1, "admin");
+    let db = db()
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(45)))])]])
+        .append_query_results([vec![book()]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/admin/books?page=3&per_page=20", &[("token", &admin)], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 45);
+    assert_eq!(body["pagination"]["total_pages"], 3);
+    assert_eq!(body["pagination"]["prev_page"], 2);
+    let page = &reply.statements[1].sql;
+    assert!(page.ends_with("LIMIT ? OFFSET ?"), "{}", page);
+}
+
+#[rocket::async_test]
+async fn catalog_is_for_admins() {
+    let reply = request(db(), 1, Method::Get, "/admin/books", &[], None).await;
+
+    assert_eq!(reply.status, Status::Forbidden);
+    assert!(reply.statements.is_empty());
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::author;
+
+fn author(id: i32) -> author::Model {
+    author::Model {
+        id,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book_ids(ids: &[i32]) -> Vec<BTreeMap<&'static str, Value>> {
+    ids.iter().map(|id| BTreeMap::from([("id", Value::Int(Some(*id)))])).collect()
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected }
+}
+
+fn writes(statements: &[sea_orm::Statement]) -> Vec<&str> {
+    statements
+        .iter()
+        .map(|s| s.sql.as_str())
+        .filter(|sql| !sql.starts_with("SELECT"))
+        .collect()
+}
+
+#[rocket::async_test]
+async fn reject_is_the_default_and_lists_blocking_books() {
+    let db = db()
+        .append_query_results([vec![author(10)]])
+        .append_query_results([book_ids(&[20, 21])]);
+    let reply = request(db, 1, Method::Delete, "/authors/10", &[], None).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    let body = reply.body.unwrap();
+    assert_eq!(body["code"], "conflict");
+    assert_eq!(body["details"]["book_ids"], rocket::serde::json::json!([20, 21]));
+    assert!(writes(&reply.statements).is_empty());
+}
+
+#[rocket::async_test]
+async fn reject_trashes_an_author_without_books() {
+    let db = db()
+        .append_query_results([vec![author(10)]])
+        .append_query_results([book_ids(&[])])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![author(10)]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10?on_books=reject", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let writes = writes(&reply.statements);
+    assert_eq!(writes.len(), 1);
+    assert!(writes[0].starts_with("UPDATE `author` SET"));
+}
+
+#[rocket::async_test]
+async fn reassign_requires_a_target() {
+    let db = db().append_query_results([vec![author(10)]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10?on_books=reassign", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["to"].is_array());
+}
+
+#[rocket::async_test]
+async fn reassign_rejects_unknown_target() {
+    let db = db()
+        .append_query_results([vec![author(10)]])
+        .append_query_results([Vec::<author::Model>::new()]);
+    let reply = request(db, 1, Method::Delete, "/authors/10?on_books=reassign&to=11", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["to"][0], "Author not found");
+    assert!(writes(&reply.statements).is_empty());
+}
+
+#[rocket::async_test]
+async fn reassign_moves_books_then_trashes_author() {
+    let db = db()
+        .append_query_results([vec![author(10)]])
+        .append_query_results([vec![author(11)]])
+        .append_exec_results([rows(0), rows(2), rows(2), rows(1)])
+        .append_query_results([vec![author(10)]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10?on_books=reassign&to=11", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    // the primary author rows of the moved books follow them
+    let contributors = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `book_contributor`")).unwrap();
+    assert!(contributors.sql.contains("`book_contributor`.`position` = ?"), "{}", contributors.sql);
+    assert_eq!(contributors.values.as_ref().unwrap().0[0], Value::Int(Some(11)));
+    let moved = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `book`")).unwrap();
+    assert!(moved.sql.contains("`author_id` = ?"));
+    assert_eq!(moved.values.as_ref().unwrap().0[1], Value::Int(Some(11)));
+    assert!(!moved.sql.contains("`deleted_at` = ?"));
+}
+
+#[rocket::async_test]
+async fn unknown_policy_is_rejected() {
+    let reply = request(db(), 1, Method::Delete, "/authors/10?on_books=shred", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
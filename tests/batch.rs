@@ -0,0 +1,122 @@
+mod common;
+
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::MockExecResult;
+use udemy_mini_book::domain::entities::{author, book};
+
+fn author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 21,
+        user_id: 1,
+        author_id: 10,
+        title: "The Tombs of Atuan".to_string(),
+        cover: "https://example.com/tombs.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1971),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+const CREATE_THEN_DELETE_MISSING: &str = r#"[
+    {"op":"create","book":{"author_id":10,"title":"The Tombs of Atuan","published_on":{"year":1971},"cover":"https://example.com/tombs.jpg"}},
+    {"op":"delete","id":99}
+]"#;
+
+fn create_then_missing() -> sea_orm::MockDatabase {
+    db()
+        .append_query_results([vec![author()]])
+        .append_exec_results([MockExecResult { last_insert_id: 21, rows_affected: 1 }])
+        .append_query_results([vec![book()]])
+        .append_exec_results([MockExecResult { last_insert_id: 1, rows_affected: 1 }])
+        .append_query_results([Vec::<book::Model>::new()])
+}
+
+fn batch(atomic: bool, operations: &str) -> String {
+    format!(r#"{{"atomic":{},"operations":{}}}"#, atomic, operations)
+}
+
+#[rocket::async_test]
+async fn atomic_batch_rolls_back_on_first_failure() {
+    let body = batch(true, CREATE_THEN_DELETE_MISSING);
+    let reply = request(create_then_missing(), 1, Method::Post, "/books/batch", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    let body = reply.body.unwrap();
+    assert_eq!(body["atomic"], true);
+    assert_eq!(body["succeeded"], 0);
+    assert_eq!(body["results"][0]["status"], 424);
+    assert_eq!(body["results"][0]["error"]["code"], "not_applied");
+    assert_eq!(body["results"][1]["status"], 404);
+    assert_eq!(body["results"][1]["error"]["code"], "not_found");
+}
+
+#[rocket::async_test]
+async fn best_effort_batch_reports_each_operation() {
+    let body = batch(false, CREATE_THEN_DELETE_MISSING);
+    let reply = request(create_then_missing(), 1, Method::Post, "/books/batch", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["succeeded"], 1);
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["results"][0]["status"], 201);
+    assert_eq!(body["results"][0]["id"], 21);
+    assert_eq!(body["results"][1]["status"], 404);
+}
+
+#[rocket::async_test]
+async fn invalid_operation_fails_without_touching_the_database() {
+    let body = batch(false, r#"[{"op":"update","id":21,"book":{"title":"  "}}]"#);
+    let reply = request(db(), 1, Method::Post, "/books/batch", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["results"][0]["status"], 422);
+    assert!(body["results"][0]["error"]["details"]["title"].is_array());
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn empty_batch_is_rejected() {
+    let reply = request(db(), 1, Method::Post, "/books/batch", &[], Some(&batch(true, "[]"))).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["operations"].is_array());
+}
+
+#[rocket::async_test]
+async fn oversized_batch_is_rejected() {
+    let operations = format!("[{}]", vec![r#"{"op":"delete","id":1}"#; 101].join(","));
+    let reply = request(db(), 1, Method::Post, "/books/batch", &[], Some(&batch(true, &operations))).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
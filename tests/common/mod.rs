@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+
+use std::{collections::BTreeMap, sync::Arc, time::SystemTime};
+
+use jsonwebtoken::{encode, EncodingKey, Header as JwtHeader};
+use rocket::{
+    http::{ContentType, Header, Method, Status},
+    local::asynchronous::Client,
+    serde::json::Value,
+};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase, Statement};
+use udemy_mini_book::{
+    application::service::init_service_setup::init_service_setup,
+    config::jwt_config::JwtSecret,
+    domain::entities::{book_contributor, book_tag, refresh_token},
+    infrastructure::rocket_http::{jwt::jwt_claim::Claims, routes::init_route_setup::init_routes_setup},
+};
+
+pub fn token_for(user_id: i32) -> String {
+    token_with_role(user_id, "user")
+}
+
+/// Send it as a `token` header to act with another role.
+pub fn token_with_role(user_id: i32, role: &str) -> String {
+    let claims = Claims {
+        sub: user_id,
+        role: role.to_string(),
+        sid: "test-session".to_string(),
+        exp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 60 * 60,
+    };
+    encode(
+        &JwtHeader::default(),
+        &claims,
+        &EncodingKey::from_secret(JwtSecret::default().jwt_secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Contributor lookup of the answered books, when none are found.
+pub fn no_contributors() -> Vec<book_contributor::Model> {
+    Vec::new()
+}
+
+/// Rating aggregate of the answered books, when none have reviews.
+pub fn no_ratings() -> Vec<BTreeMap<&'static str, sea_orm::Value>> {
+    Vec::new()
+}
+
+/// Tag lookup of the answered books, when none are tagged.
+pub fn no_tags() -> Vec<book_tag::Model> {
+    Vec::new()
+}
+
+/// Mock database primed with the auth guard's session lookup.
+pub fn db() -> MockDatabase {
+    MockDatabase::new(DatabaseBackend::MySql).append_query_results([Vec::<refresh_token::Model>::new()])
+}
+
+pub struct Reply {
+    pub status: Status,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+    pub text: Option<String>,
+    /// Statements sent to the database, without the session lookup
+    /// and transaction control.
+    pub statements: Vec<Statement>,
+}
+
+impl Reply {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Runs a single authenticated request against the app backed by `db`.
+pub async fn request(
+    db: MockDatabase,
+    user_id: i32,
+    method: Method,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Reply {
+    request_bytes(db, user_id, method, uri, headers, body.map(str::as_bytes)).await
+}
+
+/// Same as `request`, for bodies that are not text.
+pub async fn request_bytes(
+    db: MockDatabase,
+    user_id: i32,
+    method: Method,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<&[u8]>,
+) -> Reply {
+    let db = Arc::new(db.into_connection());
+    let rocket = rocket::build()
+        .attach(init_service_setup(Arc::clone(&db)))
+        .attach(init_routes_setup());
+    let client = Client::tracked(rocket).await.unwrap();
+
+    let mut req = client.req(method, uri.to_string());
+    if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("token")) {
+        req = req.header(Header::new("token", token_for(user_id)));
+    }
+    for (name, value) in headers {
+        req = req.header(Header::new(name.to_string(), value.to_string()));
+    }
+    if let Some(body) = body {
+        if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+            req = req.header(ContentType::JSON);
+        }
+        req = req.body(body);
+    }
+    let res = req.dispatch().await;
+    let status = res.status();
+    let headers = res
+        .headers()
+        .iter()
+        .map(|h| (h.name().to_string(), h.value().to_string()))
+        .collect();
+    let text = res.into_string().await;
+    let body = text.as_deref().and_then(|s| rocket::serde::json::from_str(s).ok());
+
+    drop(client);
+    let conn: DatabaseConnection = Arc::try_unwrap(db).ok().unwrap();
+    let statements = conn
+        .into_transaction_log()
+        .iter()
+        .flat_map(|t| t.statements().to_vec())
+        .filter(|stmt| !stmt.sql.contains("FROM `refresh_token`"))
+        .filter(|stmt| !matches!(stmt.sql.as_str(), "BEGIN" | "COMMIT" | "ROLLBACK"))
+        .collect();
+
+    Reply { status, headers, body, text, statements }
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::{author, book, book_contributor};
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "The Left Hand of Darkness".to_string(),
+        cover: "https://example.com/left-hand.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1969),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn author(id: i32, firstname: &str, lastname: &str) -> author::Model {
+    author::Model {
+        id,
+        user_id: 1,
+        firstname: firstname.to_string(),
+        lastname: lastname.to_string(),
+        bio: String::new(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn contributor(author_id: i32, role: &str, position: i32) -> book_contributor::Model {
+    book_contributor::Model { id: position + 1, book_id: 20, author_id, role: role.to_string(), position }
+}
+
+fn inserted(id: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: id, rows_affected: 1 }
+}
+
+#[rocket::async_test]
+async fn book_lists_its_contributors_in_order() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![
+            (contributor(10, "author", 0), author(10, "Ursula", "Le Guin")),
+            (contributor(12, "illustrator", 1), author(12, "Margaret", "Chodos-Irvine")),
+        ]])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let contributors = &reply.body.unwrap()["contributors"];
+    assert_eq!(contributors[0]["author_id"], 10);
+    assert_eq!(contributors[0]["role"], "author");
+    assert_eq!(contributors[1]["lastname"], "Chodos-Irvine");
+    assert_eq!(contributors[1]["role"], "illustrator");
+    assert_eq!(contributors[1]["position"], 1);
+
+    let lookup = &reply.statements[1].sql;
+    assert!(lookup.contains("`author`.`deleted_at` IS NULL"), "{}", lookup);
+    assert!(lookup.ends_with("ORDER BY `book_contributor`.`book_id` ASC, `book_contributor`.`position` ASC"), "{}", lookup);
+}
+
+#[rocket::async_test]
+async fn create_stores_the_author_first_then_the_others() {
+    let body = r#"{"author_id":10,"title":"The Left Hand of Darkness","cover":"https://example.com/left-hand.jpg",
+        "contributors":[{"author_id":12,"role":"translator"},{"author_id":10}]}"#;
+    let db = db()
+        .append_query_results([vec![author(10, "Ursula", "Le Guin")]])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(1)))])]])
+        .append_exec_results([inserted(20)])
+        .append_query_results([vec![book()]])
+        .append_exec_results([inserted(1)])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Post, "/books", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    // both authors are checked at once
+    assert!(reply.statements[1].sql.contains("`author`.`id` IN (?)"), "{}", reply.statements[1].sql);
+
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `book_contributor`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert_eq!(
+        values[..8],
+        [
+            Value::Int(Some(20)), Value::Int(Some(10)), Value::from("author"), Value::Int(Some(0)),
+            Value::Int(Some(20)), Value::Int(Some(12)), Value::from("translator"), Value::Int(Some(1)),
+        ]
+    );
+    assert_eq!(values.len(), 8);
+}
+
+#[rocket::async_test]
+async fn foreign_contributor_is_rejected_before_writing() {
+    let body = r#"{"author_id":10,"title":"Tehanu","cover":"https://example.com/tehanu.jpg","contributors":[{"author_id":99,"role":"editor"}]}"#;
+    let db = db()
+        .append_query_results([vec![author(10, "Ursula", "Le Guin")]])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(0)))])]]);
+    let reply = request(db, 1, Method::Post, "/books", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["contributors"].is_array());
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn the_same_role_twice_is_rejected() {
+    let body = r#"{"author_id":10,"title":"Tehanu","cover":"https://example.com/tehanu.jpg",
+        "contributors":[{"author_id":12,"role":"editor"},{"author_id":12,"role":"editor"}]}"#;
+    let reply = request(db(), 1, Method::Post, "/books", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["contributors"][0], "must not give an author the same role twice");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn patch_replaces_the_contributors() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(1)))])]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![book::Model { version: 2, ..book() }]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 2 }, inserted(3)])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[], Some(r#"{"contributors":[{"author_id":12,"role":"editor"}]}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let writes: Vec<&str> = reply.statements.iter().map(|s| s.sql.as_str()).filter(|s| !s.starts_with("SELECT")).collect();
+    assert!(writes[1].starts_with("DELETE FROM `book_contributor` WHERE `book_contributor`.`book_id` = ?"), "{}", writes[1]);
+    assert!(writes[2].starts_with("INSERT INTO `book_contributor`"), "{}", writes[2]);
+}
+
+#[rocket::async_test]
+async fn a_new_author_keeps_the_other_contributors() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![author(11, "Vonda", "McIntyre")]])
+        .append_query_results([vec![contributor(12, "editor", 1)]])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(1)))])]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![book::Model { author_id: 11, version: 2, ..book() }]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 2 }, inserted(3)])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[], Some(r#"{"author_id":11}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `book_contributor`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert_eq!(values[1], Value::Int(Some(11)));
+    assert_eq!(values[5], Value::Int(Some(12)));
+    assert_eq!(values[6], Value::from("editor"));
+}
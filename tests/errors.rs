@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use rocket::{
+    http::{ContentType, Header, Status},
+    local::asynchronous::{Client, LocalResponse},
+    serde::json::Value,
+};
+use jsonwebtoken::{encode, EncodingKey, Header as JwtHeader};
+use sea_orm::{DatabaseBackend, MockDatabase};
+use udemy_mini_book::{
+    application::service::init_service_setup::init_service_setup,
+    config::jwt_config::JwtSecret,
+    domain::entities::{book, refresh_token},
+    infrastructure::rocket_http::{jwt::jwt_claim::Claims, routes::init_route_setup::init_routes_setup},
+};
+
+async fn client(db: MockDatabase) -> Client {
+    let db = Arc::new(db.into_connection());
+    let rocket = rocket::build()
+        .attach(init_service_setup(db))
+        .attach(init_routes_setup());
+    Client::tracked(rocket).await.unwrap()
+}
+
+async fn body(res: LocalResponse<'_>) -> Value {
+    assert_eq!(res.content_type(), Some(ContentType::JSON));
+    res.into_json::<Value>().await.expect("error body is not JSON")
+}
+
+#[rocket::async_test]
+async fn missing_token_is_a_json_401() {
+    let client = client(MockDatabase::new(DatabaseBackend::MySql)).await;
+    let res = client.get("/books").dispatch().await;
+    assert_eq!(res.status(), Status::Unauthorized);
+    let body = body(res).await;
+    assert_eq!(body["code"], "unauthorized");
+    assert_eq!(body["message"], "Token absent");
+}
+
+#[rocket::async_test]
+async fn malformed_token_is_a_json_401() {
+    let client = client(MockDatabase::new(DatabaseBackend::MySql)).await;
+    let res = client.get("/books").header(Header::new("token", "garbage")).dispatch().await;
+    assert_eq!(res.status(), Status::Unauthorized);
+    assert_eq!(body(res).await["code"], "unauthorized");
+}
+
+#[rocket::async_test]
+async fn unknown_route_is_a_json_404() {
+    let client = client(MockDatabase::new(DatabaseBackend::MySql)).await;
+    let res = client.get("/nowhere").dispatch().await;
+    assert_eq!(res.status(), Status::NotFound);
+    assert_eq!(body(res).await["code"], "not_found");
+}
+
+#[rocket::async_test]
+async fn malformed_json_is_a_json_error() {
+    let client = client(MockDatabase::new(DatabaseBackend::MySql)).await;
+    let res = client.post("/auth/sign-in").header(ContentType::JSON).body("{").dispatch().await;
+    assert_eq!(res.status(), Status::BadRequest);
+    assert_eq!(body(res).await["code"], "bad_request");
+}
+
+#[rocket::async_test]
+async fn missing_record_uses_stable_code() {
+    let db = MockDatabase::new(DatabaseBackend::MySql)
+        .append_query_results([Vec::<refresh_token::Model>::new()])
+        .append_query_results([Vec::<book::Model>::new()]);
+    let client = client(db).await;
+    let claims = Claims {
+        sub: 1,
+        role: "user".to_string(),
+        sid: "test-session".to_string(),
+        exp: u64::MAX / 2,
+    };
+    let token = encode(
+        &JwtHeader::default(),
+        &claims,
+        &EncodingKey::from_secret(JwtSecret::default().jwt_secret.as_bytes()),
+    )
+    .unwrap();
+    let res = client.get("/books/20").header(Header::new("token", token)).dispatch().await;
+    assert_eq!(res.status(), Status::NotFound);
+    let body = body(res).await;
+    assert_eq!(body["code"], "not_found");
+    assert_eq!(body["message"], "Book not found");
+}
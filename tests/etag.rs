@@ -0,0 +1,266 @@
+mod common;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::MockExecResult;
+use udemy_mini_book::domain::entities::{author, book};
+
+const CURRENT: &str = r#""20-3""#;
+const STALE: &str = r#""20-2""#;
+const AUTHOR_CURRENT: &str = r#""10-3""#;
+const AUTHOR_STALE: &str = r#""10-2""#;
+const AUTHOR_BODY: &str = r#"{"firstname":"Ursula","lastname":"Le Guin","bio":"Author of Tehanu"}"#;
+const BOOK_BODY: &str = r#"{"author_id":10,"title":"Tehanu","published_on":{"year":1990},"cover":"https://example.com/tehanu.jpg"}"#;
+
+fn book(version: i32) -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "https://example.com/wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn author(version: i32) -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version,
+        deleted_at: None,
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected }
+}
+
+#[rocket::async_test]
+async fn show_sends_etag() {
+    let db = db()
+        .append_query_results([vec![book(3)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.header("ETag"), Some(CURRENT));
+    assert!(reply.body.is_some());
+}
+
+#[rocket::async_test]
+async fn show_is_not_modified_for_current_etag() {
+    let db = db().append_query_results([vec![book(3)]]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[("If-None-Match", CURRENT)], None).await;
+
+    assert_eq!(reply.status, Status::NotModified);
+    assert_eq!(reply.header("ETag"), Some(CURRENT));
+    assert!(reply.body.is_none());
+}
+
+#[rocket::async_test]
+async fn show_sends_book_for_stale_etag() {
+    let db = db()
+        .append_query_results([vec![book(3)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[("If-None-Match", STALE)], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+}
+
+#[rocket::async_test]
+async fn update_bumps_version_and_checks_it() {
+    let db = db()
+        .append_query_results([vec![book(3)]])
+        .append_query_results([vec![author(1)]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![book(4)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Put, "/books/20", &[("If-Match", CURRENT)], Some(BOOK_BODY)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.header("ETag"), Some(r#""20-4""#));
+    let update = &reply.statements[2].sql;
+    assert!(update.contains("`version` = ?"), "{}", update);
+    assert!(update.ends_with("AND `book`.`version` = ?"), "{}", update);
+}
+
+#[rocket::async_test]
+async fn update_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![book(3)]]);
+    let reply = request(db, 1, Method::Put, "/books/20", &[("If-Match", STALE)], Some(BOOK_BODY)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.body.unwrap()["code"], "precondition_failed");
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn patch_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![book(3)]]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[("If-Match", STALE)], Some(r#"{"title":"Tehanu"}"#)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn patch_racing_another_write_fails() {
+    let db = db()
+        .append_query_results([vec![book(3)]])
+        .append_exec_results([rows(0)]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[], Some(r#"{"title":"Tehanu"}"#)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+}
+
+#[rocket::async_test]
+async fn delete_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![book(3)]]);
+    let reply = request(db, 1, Method::Delete, "/books/20", &[("If-Match", STALE)], None).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn delete_with_current_etag_succeeds() {
+    let db = db()
+        .append_query_results([vec![book(3)]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![book(4)]]);
+    let reply = request(db, 1, Method::Delete, "/books/20", &[("If-Match", CURRENT)], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements[1].sql.ends_with("AND `book`.`version` = ?"));
+}
+
+#[rocket::async_test]
+async fn author_show_sends_etag() {
+    let db = db().append_query_results([vec![author(3)]]);
+    let reply = request(db, 1, Method::Get, "/authors/10", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.header("ETag"), Some(AUTHOR_CURRENT));
+    assert!(reply.body.is_some());
+}
+
+#[rocket::async_test]
+async fn author_show_is_not_modified_for_current_etag() {
+    let db = db().append_query_results([vec![author(3)]]);
+    let reply = request(db, 1, Method::Get, "/authors/10", &[("If-None-Match", AUTHOR_CURRENT)], None).await;
+
+    assert_eq!(reply.status, Status::NotModified);
+    assert_eq!(reply.header("ETag"), Some(AUTHOR_CURRENT));
+    assert!(reply.body.is_none());
+}
+
+#[rocket::async_test]
+async fn author_update_bumps_version_and_checks_it() {
+    let db = db()
+        .append_query_results([vec![author(3)]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![author(4)]]);
+    let reply = request(db, 1, Method::Put, "/authors/10", &[("If-Match", AUTHOR_CURRENT)], Some(AUTHOR_BODY)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.header("ETag"), Some(r#""10-4""#));
+    let update = &reply.statements[1].sql;
+    assert!(update.contains("`version` = ?"), "{}", update);
+    assert!(update.ends_with("AND `author`.`version` = ?"), "{}", update);
+    // the books only embed the names
+    assert!(!reply.statements.iter().any(|s| s.sql.starts_with("UPDATE `book`")));
+}
+
+#[rocket::async_test]
+async fn author_rename_bumps_the_books_naming_it() {
+    let db = db()
+        .append_query_results([vec![author(3)]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![author::Model { lastname: "K. Le Guin".to_string(), ..author(4) }]])
+        .append_exec_results([rows(2)]);
+    let reply = request(db, 1, Method::Patch, "/authors/10", &[("If-Match", AUTHOR_CURRENT)], Some(r#"{"lastname":"K. Le Guin"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(
+        reply.statements.last().unwrap().sql,
+        "UPDATE `book` SET `version` = `version` + ? WHERE `book`.`id` IN (SELECT `book_id` FROM `book_contributor` WHERE `book_contributor`.`author_id` = ?)"
+    );
+}
+
+#[rocket::async_test]
+async fn author_update_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![author(3)]]);
+    let reply = request(db, 1, Method::Put, "/authors/10", &[("If-Match", AUTHOR_STALE)], Some(AUTHOR_BODY)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.body.unwrap()["code"], "precondition_failed");
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn author_patch_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![author(3)]]);
+    let reply = request(db, 1, Method::Patch, "/authors/10", &[("If-Match", AUTHOR_STALE)], Some(r#"{"bio":"Earthsea"}"#)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn author_patch_racing_another_write_fails() {
+    let db = db()
+        .append_query_results([vec![author(3)]])
+        .append_exec_results([rows(0)]);
+    let reply = request(db, 1, Method::Patch, "/authors/10", &[], Some(r#"{"bio":"Earthsea"}"#)).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+}
+
+#[rocket::async_test]
+async fn author_delete_with_stale_etag_fails() {
+    let db = db().append_query_results([vec![author(3)]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10", &[("If-Match", AUTHOR_STALE)], None).await;
+
+    assert_eq!(reply.status, Status::PreconditionFailed);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn author_delete_with_current_etag_succeeds() {
+    let db = db()
+        .append_query_results([vec![author(3)]])
+        .append_query_results([Vec::<book::Model>::new()])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![author(4)]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10", &[("If-Match", AUTHOR_CURRENT)], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements[2].sql.ends_with("AND `author`.`version` = ?"));
+}
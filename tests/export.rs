@@ -0,0 +1,173 @@
+mod common;
+
+use common::{db, request};
+use rocket::http::{Method, Status};
+use rocket::futures::{stream, StreamExt};
+use sea_orm::{DbErr, MockDatabase};
+use udemy_mini_book::{
+    domain::{entities::{author, book}, value_object::export::{ExportFormat, ResExportRow}},
+    infrastructure::{format::export::render, rocket_http::response_type::app_error::AppError},
+};
+
+fn author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Wrote \"Earthsea\", among others".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book(id: i32, title: &str) -> book::Model {
+    book::Model {
+        id,
+        user_id: 1,
+        author_id: 10,
+        title: title.to_string(),
+        cover: "https://example.com/cover.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn library() -> MockDatabase {
+    db().append_query_results([vec![
+        (book(20, "A Wizard of Earthsea"), author()),
+        (book(21, "The Tombs of Atuan"), author()),
+    ]])
+}
+
+#[rocket::async_test]
+async fn csv_export_has_a_header_and_one_line_per_book() {
+    let reply = request(library(), 1, Method::Get, "/export?format=csv", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.header("Content-Type").unwrap().starts_with("text/csv"));
+    assert_eq!(reply.header("Content-Disposition"), Some("attachment; filename=\"library.csv\""));
+
+    let text = reply.text.unwrap();
+    let lines: Vec<&str> = text.split("\r\n").collect();
+    assert_eq!(lines[0], "id,title,published_on,cover,isbn,author_id,author_firstname,author_lastname,author_bio");
+    assert_eq!(
+        lines[1],
+        "20,A Wizard of Earthsea,1968,https://example.com/cover.jpg,,10,Ursula,Le Guin,\"Wrote \"\"Earthsea\"\", among others\""
+    );
+    assert_eq!(lines.len(), 4);
+
+    // only the caller's live books, joined with their authors
+    let sql = &reply.statements[0].sql;
+    assert!(sql.contains("LEFT JOIN `author`"));
+    assert!(sql.contains("`book`.`user_id` = ?"));
+    assert!(sql.contains("`book`.`deleted_at` IS NULL"));
+}
+
+#[rocket::async_test]
+async fn jsonl_export_writes_one_object_per_line() {
+    let reply = request(library(), 1, Method::Get, "/export?format=jsonl", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let text = reply.text.unwrap();
+    let rows: Vec<rocket::serde::json::Value> = text
+        .lines()
+        .map(|line| rocket::serde::json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["title"], "The Tombs of Atuan");
+    assert_eq!(rows[1]["author_lastname"], "Le Guin");
+}
+
+#[rocket::async_test]
+async fn json_export_is_one_array() {
+    let reply = request(library(), 1, Method::Get, "/export?format=json", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body.as_array().unwrap().len(), 2);
+    assert_eq!(body[0]["id"], 20);
+}
+
+#[rocket::async_test]
+async fn empty_library_exports_an_empty_array() {
+    let db = db().append_query_results([Vec::<(book::Model, author::Model)>::new()]);
+    let reply = request(db, 1, Method::Get, "/export?format=json", &[], None).await;
+
+    assert_eq!(reply.body.unwrap(), rocket::serde::json::json!([]));
+}
+
+#[rocket::async_test]
+async fn unknown_format_is_rejected() {
+    let reply = request(db(), 1, Method::Get, "/export?format=xml", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
+
+fn failing() -> MockDatabase {
+    db().append_query_errors([DbErr::Custom("connection lost".to_string())])
+}
+
+#[rocket::async_test]
+async fn failed_csv_export_ends_with_an_error_record() {
+    let reply = request(failing(), 1, Method::Get, "/export?format=csv", &[], None).await;
+
+    let text = reply.text.unwrap();
+    let lines: Vec<&str> = text.split("\r\n").collect();
+    assert!(lines[0].starts_with("id,title"));
+    assert_eq!(lines[1], "#error,database_error,A database error occurred");
+}
+
+#[rocket::async_test]
+async fn failed_jsonl_export_ends_with_an_error_record() {
+    let reply = request(failing(), 1, Method::Get, "/export?format=jsonl", &[], None).await;
+
+    let text = reply.text.unwrap();
+    let last: rocket::serde::json::Value = rocket::serde::json::from_str(text.lines().last().unwrap()).unwrap();
+    assert_eq!(last["error"]["code"], "database_error");
+}
+
+#[rocket::async_test]
+async fn failed_json_export_closes_the_array_before_the_error_record() {
+    let reply = request(failing(), 1, Method::Get, "/export?format=json", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let text = reply.text.unwrap();
+    let (array, trailer) = text.split_once("]\n").unwrap();
+    assert_eq!(array, "[\n");
+    let last: rocket::serde::json::Value = rocket::serde::json::from_str(trailer.trim_end()).unwrap();
+    assert_eq!(last["error"]["code"], "database_error");
+}
+
+#[rocket::async_test]
+async fn export_failing_partway_keeps_what_was_written() {
+    let rows = stream::iter(vec![
+        Ok(ResExportRow::from((book(20, "A Wizard of Earthsea"), author()))),
+        Err(AppError::from(DbErr::Custom("connection lost".to_string()))),
+    ])
+    .boxed();
+    let chunks: Vec<String> = render(ExportFormat::Json, rows).collect().await;
+
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks[0], "[");
+    assert!(chunks[1].contains("\"title\":\"A Wizard of Earthsea\""));
+    assert_eq!(chunks[2], "\n]\n");
+    assert!(chunks[3].starts_with("{\"error\":"));
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use common::{db, request, Reply};
+use rocket::http::{Method, Status};
+use sea_orm::{MockDatabase, MockExecResult};
+use udemy_mini_book::domain::entities::{author, book};
+
+const BOUNDARY: &str = "X-IMPORT-BOUNDARY";
+
+fn author(id: i32, firstname: &str, lastname: &str) -> author::Model {
+    author::Model {
+        id,
+        user_id: 1,
+        firstname: firstname.to_string(),
+        lastname: lastname.to_string(),
+        bio: String::new(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book(id: i32, author_id: i32, title: &str, year: i32) -> book::Model {
+    book::Model {
+        id,
+        user_id: 1,
+        author_id,
+        title: title.to_string(),
+        cover: String::new(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(year),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+/// The caller owns Ursula Le Guin and her "A Wizard of Earthsea".
+fn library() -> MockDatabase {
+    db()
+        .append_query_results([vec![author(10, "Ursula", "Le Guin")]])
+        .append_query_results([vec![book(20, 10, "A Wizard of Earthsea", 1968)]])
+}
+
+async fn upload(db: MockDatabase, uri: &str, content_type: &str, file: &str) -> Reply {
+    let body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"books\"\r\nContent-Type: {ct}\r\n\r\n{file}\r\n--{b}--\r\n",
+        b = BOUNDARY,
+        ct = content_type,
+        file = file,
+    );
+    let content_type = format!("multipart/form-data; boundary={}", BOUNDARY);
+    request(db, 1, Method::Post, uri, &[("Content-Type", &content_type)], Some(&body)).await
+}
+
+const CSV: &str = "title,year,author_firstname,author_lastname,cover\r\n\
+a wizard of earthsea,1968,ursula,LE GUIN,\r\n\
+\"Kindred\",1979,Octavia,Butler,https://example.com/kindred.jpg\r\n\
+Dawn,not a year,Octavia,Butler,\r\n\
+Kindred,1979,Octavia,Butler,\r\n";
+
+#[rocket::async_test]
+async fn dry_run_reports_without_writing() {
+    let reply = upload(library(), "/import?dry_run=true", "text/csv", CSV).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+
+    let body = reply.body.unwrap();
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["inserted"], 1);
+    assert_eq!(body["skipped"], 2);
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["authors_created"], 1);
+
+    let rows = body["rows"].as_array().unwrap();
+    // matched by name ignoring case, and deduplicated against the library
+    assert_eq!(rows[0]["row"], 2);
+    assert_eq!(rows[0]["status"], "skipped");
+    assert_eq!(rows[0]["book_id"], 20);
+    assert_eq!(rows[1]["status"], "inserted");
+    assert!(rows[1].get("book_id").is_none());
+    assert_eq!(rows[2]["status"], "failed");
+    assert!(rows[2]["error"]["details"]["published_on"].is_array());
+    // a duplicate of an earlier row of the same file
+    assert_eq!(rows[3]["status"], "skipped");
+}
+
+#[rocket::async_test]
+async fn import_creates_missing_authors_under_the_caller() {
+    let db = library()
+        .append_exec_results([MockExecResult { last_insert_id: 11, rows_affected: 1 }])
+        .append_query_results([vec![author(11, "Octavia", "Butler")]])
+        .append_query_results([vec![author(11, "Octavia", "Butler")]])
+        .append_exec_results([MockExecResult { last_insert_id: 21, rows_affected: 1 }])
+        .append_query_results([vec![book(21, 11, "Kindred", 1979)]])
+        .append_exec_results([MockExecResult { last_insert_id: 1, rows_affected: 1 }]);
+    let reply = upload(db, "/import", "text/csv", CSV).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let inserts: Vec<_> = reply.statements.iter().filter(|s| s.sql.starts_with("INSERT")).collect();
+    assert_eq!(inserts.len(), 3);
+    assert!(inserts[0].sql.starts_with("INSERT INTO `author`"));
+    assert!(inserts[0].values.as_ref().unwrap().0.contains(&sea_orm::Value::Int(Some(1))));
+    assert!(inserts[1].sql.starts_with("INSERT INTO `book`"));
+    assert!(inserts[2].sql.starts_with("INSERT INTO `book_contributor`"));
+    // the cover as it came, checked like on a create
+    assert!(inserts[1].values.as_ref().unwrap().0.contains(&sea_orm::Value::String(Some(Box::new("https://example.com/kindred.jpg".to_string())))));
+
+    let body = reply.body.unwrap();
+    assert_eq!(body["rows"][1]["book_id"], 21);
+    assert_eq!(body["rows"][1]["author_id"], 11);
+}
+
+#[rocket::async_test]
+async fn json_rows_fail_one_by_one() {
+    let file = r#"[
+        {"title":"Kindred","year":1979,"author_firstname":"Octavia","author_lastname":"Butler","cover":"https://example.com/kindred.jpg"},
+        {"title":"No author","year":"2001"}
+    ]"#;
+    let reply = upload(library(), "/import?dry_run=true", "application/json", file).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["rows"][0]["status"], "inserted");
+    assert_eq!(body["rows"][1]["row"], 2);
+    assert_eq!(body["rows"][1]["status"], "failed");
+}
+
+#[rocket::async_test]
+async fn new_books_need_a_cover_like_on_a_create() {
+    let file = r#"[{"title":"Dawn","year":1987,"author_firstname":"Octavia","author_lastname":"Butler"}]"#;
+    let reply = upload(library(), "/import", "application/json", file).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["rows"][0]["status"], "failed");
+    assert!(body["rows"][0]["error"]["details"]["cover"].is_array());
+    assert_eq!(body["authors_created"], 0);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn csv_without_required_columns_is_rejected() {
+    let reply = upload(db(), "/import", "text/csv", "title,year\r\nKindred,1979\r\n").await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["file"].is_array());
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn unknown_file_type_needs_a_format() {
+    let reply = upload(db(), "/import", "application/octet-stream", CSV).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["format"].is_array());
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::{
+    entities::{author, book},
+    value_object::isbn::{Isbn, IsbnError},
+};
+
+fn author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book(id: i32) -> book::Model {
+    book::Model {
+        id,
+        user_id: 1,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "https://example.com/wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: Some("0306406152".to_string()),
+        isbn13: Some("9780306406157".to_string()),
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+const BOOK_BODY: &str = r#"{"author_id":10,"title":"A Wizard of Earthsea","published_on":{"year":1968},"cover":"https://example.com/wizard.jpg","isbn":"0-306-40615-2"}"#;
+
+#[test]
+fn isbn10_converts_to_isbn13() {
+    let isbn = Isbn::parse("0-306-40615-2").unwrap();
+    assert_eq!(isbn.isbn13(), "9780306406157");
+    assert_eq!(isbn.isbn10().as_deref(), Some("0306406152"));
+    assert_eq!(Isbn::parse("978 0 306 40615 7").unwrap(), isbn);
+}
+
+#[test]
+fn isbn10_check_digit_may_be_x() {
+    let isbn = Isbn::parse("0-8044-2957-x").unwrap();
+    assert_eq!(isbn.isbn10().as_deref(), Some("080442957X"));
+}
+
+#[test]
+fn isbn13_of_979_has_no_isbn10() {
+    let isbn = Isbn::parse("979-10-90636-07-1").unwrap();
+    assert_eq!(isbn.isbn10(), None);
+}
+
+#[test]
+fn invalid_isbns_are_rejected() {
+    assert_eq!(Isbn::parse("0-306-40615-3"), Err(IsbnError::Checksum));
+    assert_eq!(Isbn::parse("9780306406158"), Err(IsbnError::Checksum));
+    assert_eq!(Isbn::parse("9770306406157"), Err(IsbnError::Prefix));
+    assert_eq!(Isbn::parse("12345"), Err(IsbnError::Length));
+    assert_eq!(Isbn::parse("03064O6152"), Err(IsbnError::Character));
+}
+
+#[test]
+fn non_ascii_input_is_rejected() {
+    // 10 bytes, the last character takes two of them
+    assert_eq!(Isbn::parse("12345678é"), Err(IsbnError::Character));
+    assert_eq!(Isbn::parse("０３０６４０６１５２"), Err(IsbnError::Character));
+}
+
+#[rocket::async_test]
+async fn create_stores_both_forms() {
+    let db = db()
+        .append_query_results([vec![author()]])
+        .append_query_results([Vec::<book::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 20, rows_affected: 1 }])
+        .append_query_results([vec![book(20)]])
+        .append_exec_results([MockExecResult { last_insert_id: 1, rows_affected: 1 }])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Post, "/books", &[], Some(BOOK_BODY)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::String(Some(Box::new("0306406152".to_string())))));
+    assert!(values.contains(&Value::String(Some(Box::new("9780306406157".to_string())))));
+    assert_eq!(reply.body.unwrap()["isbn13"], "9780306406157");
+}
+
+#[rocket::async_test]
+async fn duplicate_isbn_is_a_conflict() {
+    let db = db()
+        .append_query_results([vec![author()]])
+        .append_query_results([vec![book(21)]]);
+    let reply = request(db, 1, Method::Post, "/books", &[], Some(BOOK_BODY)).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.body.unwrap()["details"]["book_id"], 21);
+    assert!(reply.statements[1].sql.contains("`book`.`user_id` = ?"));
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn bad_checksum_is_a_validation_error() {
+    let body = BOOK_BODY.replace("0-306-40615-2", "0-306-40615-3");
+    let reply = request(db(), 1, Method::Post, "/books", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["isbn"][0], "has an invalid check digit");
+}
+
+#[rocket::async_test]
+async fn lookup_finds_the_book_by_either_form() {
+    let db = db()
+        .append_query_results([vec![book(20)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/by-isbn/0-306-40615-2", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["id"], 20);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("`book`.`isbn13` = ?"));
+    assert!(stmt.sql.contains("`book`.`deleted_at` IS NULL"));
+    assert!(stmt.values.as_ref().unwrap().0.contains(&Value::String(Some(Box::new("9780306406157".to_string())))));
+}
+
+#[rocket::async_test]
+async fn lookup_rejects_an_invalid_isbn() {
+    let reply = request(db(), 1, Method::Get, "/books/by-isbn/12345", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn lookup_rejects_non_ascii_input() {
+    let reply = request(db(), 1, Method::Get, "/books/by-isbn/12345678%C3%A9", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, Utc};
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::{book, loan};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "The Word for World Is Forest".to_string(),
+        cover: "https://example.com/forest.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1972),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn loan(id: i32, due_on: Option<NaiveDate>, returned_on: Option<NaiveDate>) -> loan::Model {
+    loan::Model {
+        id,
+        user_id: 1,
+        book_id: 20,
+        direction: "lent".to_string(),
+        person: "Sam".to_string(),
+        contact: Some("sam@example.com".to_string()),
+        lent_on: date(9, 1),
+        due_on,
+        returned_on,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[rocket::async_test]
+async fn lending_locks_the_book_and_records_the_loan() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([Vec::<loan::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 4, rows_affected: 1 }])
+        .append_query_results([vec![loan(4, Some(date(10, 1)), None)]]);
+    let body = r#"{"person":" Sam ","contact":"sam@example.com","lent_on":"2026-09-01","due_on":"2026-10-01"}"#;
+    let reply = request(db, 1, Method::Post, "/books/20/loans", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    let body = reply.body.unwrap();
+    assert_eq!(body["book_title"], "The Word for World Is Forest");
+    assert_eq!(body["direction"], "lent");
+    assert!(reply.statements[0].sql.ends_with("FOR UPDATE"));
+    assert!(reply.statements[1].sql.contains("`loan`.`returned_on` IS NULL"));
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `loan`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::String(Some(Box::new("Sam".to_string())))));
+    assert!(values.contains(&Value::ChronoDate(Some(Box::new(date(10, 1))))));
+}
+
+#[rocket::async_test]
+async fn book_already_out_cannot_be_lent() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![loan(3, None, None)]]);
+    let reply = request(db, 1, Method::Post, "/books/20/loans", &[], Some(r#"{"person":"Alex"}"#)).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    let body = reply.body.unwrap();
+    assert_eq!(body["details"]["loan_id"], 3);
+    assert_eq!(body["details"]["person"], "Sam");
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn due_date_must_follow_the_lending() {
+    let body = r#"{"person":"Sam","lent_on":"2026-09-01","due_on":"2026-08-01"}"#;
+    let reply = request(db(), 1, Method::Post, "/books/20/loans", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["due_on"][0], "must not be before lent_on");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn lending_in_the_future_is_rejected() {
+    let tomorrow = Utc::now().date_naive() + Duration::days(1);
+    let body = format!(r#"{{"person":"Sam","lent_on":"{}"}}"#, tomorrow);
+    let reply = request(db(), 1, Method::Post, "/books/20/loans", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["lent_on"].is_array());
+}
+
+#[rocket::async_test]
+async fn overdue_loans_are_listed() {
+    let db = db()
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(1)))])]])
+        .append_query_results([vec![(loan(3, Some(date(9, 15)), None), book())]]);
+    let reply = request(db, 1, Method::Get, "/loans?overdue=true", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["loans"][0]["overdue"], true);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("`loan`.`user_id` = ?"));
+    assert!(stmt.sql.contains("`loan`.`returned_on` IS NULL AND `loan`.`due_on` IS NOT NULL AND `loan`.`due_on` < ?"));
+    assert!(stmt.sql.contains("`book`.`deleted_at` IS NULL"));
+}
+
+#[rocket::async_test]
+async fn returning_closes_the_loan() {
+    let db = db()
+        .append_query_results([vec![(loan(3, Some(date(9, 15)), None), book())]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![loan(3, Some(date(9, 15)), Some(date(9, 20)))]]);
+    let reply = request(db, 1, Method::Post, "/loans/3/return", &[], Some(r#"{"returned_on":"2026-09-20"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["returned_on"], "2026-09-20");
+    assert_eq!(body["overdue"], false);
+    let update = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `loan`")).unwrap();
+    assert!(update.values.as_ref().unwrap().0.contains(&Value::ChronoDate(Some(Box::new(date(9, 20))))));
+}
+
+#[rocket::async_test]
+async fn returning_twice_is_a_conflict() {
+    let db = db().append_query_results([vec![(loan(3, None, Some(date(9, 20))), book())]]);
+    let reply = request(db, 1, Method::Post, "/loans/3/return", &[], Some("{}")).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn history_lists_every_loan_of_the_book() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![loan(4, None, None), loan(3, None, Some(date(9, 20)))]]);
+    let reply = request(db, 1, Method::Get, "/books/20/loans", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["loans"][1]["returned_on"], "2026-09-20");
+    assert!(reply.statements[1].sql.contains("ORDER BY `loan`.`lent_on` DESC, `loan`.`id` DESC"));
+}
+
+#[rocket::async_test]
+async fn history_of_a_foreign_book_is_not_found() {
+    let db = db().append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 2, Method::Get, "/books/20/loans", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements[0].sql.contains("`book`.`user_id` = ?"));
+}
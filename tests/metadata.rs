@@ -0,0 +1,224 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{db, no_contributors, no_ratings, no_tags, request, Reply};
+use rocket::{
+    http::{Method, Status},
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    },
+};
+use sea_orm::{MockDatabase, MockExecResult};
+use udemy_mini_book::{
+    domain::{
+        entities::{author, book},
+        repositories::metadata_provider::MetadataProvider,
+        value_object::isbn::Isbn,
+    },
+    infrastructure::{metadata::open_library::OpenLibraryProvider, rocket_http::response_type::app_error::AppError},
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/open_library.json");
+
+/// Every request of this file is answered by the fixture provider.
+async fn call(db: MockDatabase, uri: &str, body: &str) -> Reply {
+    std::env::set_var("BOOKSTORE_METADATA_FIXTURES", FIXTURES);
+    request(db, 1, Method::Post, uri, &[], Some(body)).await
+}
+
+fn author() -> author::Model {
+    author::Model {
+        id: 11,
+        user_id: 1,
+        firstname: "Octavia".to_string(),
+        lastname: "Butler".to_string(),
+        bio: String::new(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 30,
+        user_id: 1,
+        author_id: 11,
+        title: "Kindred".to_string(),
+        cover: "https://covers.openlibrary.org/b/id/1-L.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: Some("0306406152".to_string()),
+        isbn13: Some("9780306406157".to_string()),
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1979),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+#[rocket::async_test]
+async fn lookup_returns_the_provider_metadata() {
+    let reply = call(db(), "/books/lookup", r#"{"isbn":"0-306-40615-2"}"#).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["title"], "Kindred");
+    assert_eq!(body["published_on"]["year"], 1979);
+    assert_eq!(body["published_on"]["month"], 6);
+    assert_eq!(body["published_on"]["precision"], "month");
+    assert_eq!(body["cover"], "https://covers.openlibrary.org/b/id/1-L.jpg");
+    assert_eq!(body["authors"][0]["firstname"], "Octavia");
+    assert_eq!(body["authors"][0]["lastname"], "Butler");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn lookup_of_an_unknown_isbn_is_not_found() {
+    let reply = call(db(), "/books/lookup", r#"{"isbn":"0-8044-2957-X"}"#).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn enriched_create_fills_the_book_and_creates_its_author() {
+    let db = db()
+        .append_query_results([Vec::<author::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 11, rows_affected: 1 }])
+        .append_query_results([vec![author()]])
+        .append_query_results([vec![author()]])
+        .append_query_results([Vec::<book::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 30, rows_affected: 1 }])
+        .append_query_results([vec![book()]])
+        .append_exec_results([MockExecResult { last_insert_id: 1, rows_affected: 1 }])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = call(db, "/books?enrich=true", r#"{"isbn":"9780306406157"}"#).await;
+
+    assert_eq!(reply.status, Status::Created);
+    assert_eq!(reply.body.unwrap()["title"], "Kindred");
+
+    let inserts: Vec<_> = reply.statements.iter().filter(|s| s.sql.starts_with("INSERT")).collect();
+    assert!(inserts[0].sql.starts_with("INSERT INTO `author`"));
+    assert!(inserts[1].sql.starts_with("INSERT INTO `book`"));
+    let values = &inserts[1].values.as_ref().unwrap().0;
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new("Kindred".to_string())))));
+    assert!(values.contains(&sea_orm::Value::Int(Some(1979))));
+    assert!(values.contains(&sea_orm::Value::String(Some(Box::new("month".to_string())))));
+}
+
+#[rocket::async_test]
+async fn enriched_create_reports_what_nobody_provided() {
+    let reply = call(db(), "/books?enrich=true", r#"{"isbn":"979-10-90636-07-1","published_on":{"year":2012}}"#).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    let details = &reply.body.unwrap()["details"];
+    assert!(details["cover"].is_array());
+    assert!(details["author_id"].is_array());
+    assert!(details.get("title").is_none());
+    assert!(reply.statements.is_empty());
+}
+
+/// A stand-in for Open Library taking one connection per answer, each
+/// answer a status, extra header lines and a body, and handing back the
+/// request lines it received.
+async fn stand_ins(answers: Vec<(&'static str, &'static str, &'static str)>) -> (String, rocket::tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = rocket::tokio::spawn(async move {
+        let mut request_lines = Vec::new();
+        for (status, headers, body) in answers {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                headers,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            request_lines.push(String::from_utf8_lossy(&request).lines().next().unwrap_or_default().to_string());
+        }
+        request_lines
+    });
+
+    (url, handle)
+}
+
+/// A one-shot stand-in answering with `status` and `body`.
+async fn stand_in(status: &'static str, body: &'static str) -> (String, rocket::tokio::task::JoinHandle<Vec<String>>) {
+    stand_ins(vec![(status, "", body)]).await
+}
+
+#[rocket::async_test]
+async fn http_provider_reads_an_open_library_answer() {
+    let (url, server) = stand_in(
+        "200 OK",
+        r#"{"ISBN:9780306406157":{"title":"Kindred","publish_date":"1979-06-01","authors":[{"name":"Octavia E. Butler"}]}}"#,
+    )
+    .await;
+    let provider = OpenLibraryProvider::new(&url, Duration::from_secs(5));
+
+    let metadata = provider.lookup(&Isbn::parse("0306406152").unwrap()).await.unwrap().unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Kindred"));
+    assert_eq!(metadata.published_on.map(|p| p.to_string()).as_deref(), Some("1979-06-01"));
+    assert_eq!(metadata.cover, None);
+    assert_eq!(metadata.authors[0].firstname, "Octavia E.");
+
+    let request_lines = server.await.unwrap();
+    assert!(request_lines[0].starts_with("GET /api/books?bibkeys=ISBN:9780306406157&format=json&jscmd=data "));
+}
+
+#[rocket::async_test]
+async fn http_provider_failures_are_upstream_errors() {
+    let (url, _server) = stand_in("503 Service Unavailable", "{}").await;
+    let provider = OpenLibraryProvider::new(&url, Duration::from_secs(5));
+
+    let error = provider.lookup(&Isbn::parse("0306406152").unwrap()).await.unwrap_err();
+    assert!(matches!(error, AppError::Upstream(_)));
+    assert_eq!(error.status(), Status::BadGateway);
+}
+
+#[rocket::async_test]
+async fn http_provider_follows_redirects() {
+    let (url, server) = stand_ins(vec![
+        ("301 Moved Permanently", "Location: /moved/api/books?bibkeys=ISBN:9780306406157\r\n", ""),
+        ("200 OK", "", r#"{"ISBN:9780306406157":{"title":"Kindred"}}"#),
+    ])
+    .await;
+    let provider = OpenLibraryProvider::new(&url, Duration::from_secs(5));
+
+    let metadata = provider.lookup(&Isbn::parse("0306406152").unwrap()).await.unwrap().unwrap();
+    assert_eq!(metadata.title.as_deref(), Some("Kindred"));
+
+    let request_lines = server.await.unwrap();
+    assert!(request_lines[1].starts_with("GET /moved/api/books?bibkeys=ISBN:9780306406157 "));
+}
+
+#[rocket::async_test]
+async fn http_provider_reports_a_redirect_without_location() {
+    let (url, _server) = stand_in("302 Found", "").await;
+    let provider = OpenLibraryProvider::new(&url, Duration::from_secs(5));
+
+    let error = provider.lookup(&Isbn::parse("0306406152").unwrap()).await.unwrap_err();
+    assert!(matches!(&error, AppError::Upstream(message) if message.contains("302")));
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockDatabase, MockExecResult, Statement, Value};
+use udemy_mini_book::domain::entities::{author, book};
+
+const OWNER: i32 = 1;
+const INTRUDER: i32 = 2;
+
+fn owned_author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: OWNER,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn owned_book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: OWNER,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+const BOOK_BODY: &str = r#"{"author_id":10,"title":"Stolen","published_on":{"year":2000},"cover":"https://example.com/x.jpg"}"#;
+const AUTHOR_BODY: &str = r#"{"firstname":"Stolen","lastname":"Name","bio":"-"}"#;
+
+fn empty_books() -> MockDatabase {
+    db().append_query_results([Vec::<book::Model>::new()])
+}
+
+fn empty_authors() -> MockDatabase {
+    db().append_query_results([Vec::<author::Model>::new()])
+}
+
+fn assert_scoped(stmt: &Statement, table: &str, user_id: i32) {
+    assert!(
+        stmt.sql.contains(&format!("`{}`.`user_id` = ?", table)),
+        "query is not scoped by user: {}",
+        stmt.sql
+    );
+    let values = stmt.values.as_ref().expect("query has no bound values");
+    assert!(
+        values.0.contains(&Value::Int(Some(user_id))),
+        "query is not bound to user {}: {:?}",
+        user_id,
+        stmt
+    );
+}
+
+fn assert_read_only(statements: &[Statement]) {
+    for stmt in statements {
+        assert!(stmt.sql.starts_with("SELECT"), "unexpected write: {}", stmt.sql);
+    }
+}
+
+#[rocket::async_test]
+async fn books_index_only_lists_callers_books() {
+    let reply = request(empty_books(), INTRUDER, Method::Get, "/books", &[], None).await;
+    assert_eq!(reply.status, Status::Ok);
+    assert_scoped(&reply.statements[0], "book", INTRUDER);
+}
+
+#[rocket::async_test]
+async fn books_show_returns_own_book() {
+    let db = db()
+        .append_query_results([vec![owned_book()]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, OWNER, Method::Get, "/books/20", &[], None).await;
+    assert_eq!(reply.status, Status::Ok);
+    assert_scoped(&reply.statements[0], "book", OWNER);
+}
+
+#[rocket::async_test]
+async fn books_show_hides_foreign_book() {
+    let reply = request(empty_books(), INTRUDER, Method::Get, "/books/20", &[], None).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "book", INTRUDER);
+}
+
+#[rocket::async_test]
+async fn books_create_rejects_foreign_author() {
+    let reply = request(empty_authors(), INTRUDER, Method::Post, "/books", &[], Some(BOOK_BODY)).await;
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn books_update_hides_foreign_book() {
+    let reply = request(empty_books(), INTRUDER, Method::Put, "/books/20", &[], Some(BOOK_BODY)).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "book", INTRUDER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn books_update_rejects_moving_to_foreign_author() {
+    let db = db()
+        .append_query_results([vec![owned_book()]])
+        .append_query_results([Vec::<author::Model>::new()]);
+    let reply = request(db, OWNER, Method::Put, "/books/20", &[], Some(BOOK_BODY)).await;
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_scoped(&reply.statements[1], "author", OWNER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn books_delete_hides_foreign_book() {
+    let reply = request(empty_books(), INTRUDER, Method::Delete, "/books/20", &[], None).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "book", INTRUDER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn authors_index_only_lists_callers_authors() {
+    let reply = request(empty_authors(), INTRUDER, Method::Get, "/authors", &[], None).await;
+    assert_eq!(reply.status, Status::Ok);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+}
+
+#[rocket::async_test]
+async fn authors_create_assigns_caller() {
+    let db = db()
+        .append_exec_results([MockExecResult { last_insert_id: 10, rows_affected: 1 }])
+        .append_query_results([vec![owned_author()]]);
+    let reply = request(db, OWNER, Method::Post, "/authors", &[], Some(AUTHOR_BODY)).await;
+    assert_eq!(reply.status, Status::Created);
+    assert!(reply.statements[0].sql.starts_with("INSERT INTO `author` (`user_id`"));
+    assert!(reply.statements[0].values.as_ref().unwrap().0.contains(&Value::Int(Some(OWNER))));
+}
+
+#[rocket::async_test]
+async fn authors_show_hides_foreign_author() {
+    let reply = request(empty_authors(), INTRUDER, Method::Get, "/authors/10", &[], None).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+}
+
+#[rocket::async_test]
+async fn authors_update_hides_foreign_author() {
+    let reply = request(empty_authors(), INTRUDER, Method::Put, "/authors/10", &[], Some(AUTHOR_BODY)).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn authors_delete_hides_foreign_author() {
+    let reply = request(empty_authors(), INTRUDER, Method::Delete, "/authors/10", &[], None).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+    assert_read_only(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn authors_books_hides_foreign_author() {
+    let reply = request(empty_authors(), INTRUDER, Method::Get, "/authors/10/books", &[], None).await;
+    assert_eq!(reply.status, Status::NotFound);
+    assert_scoped(&reply.statements[0], "author", INTRUDER);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn authors_books_only_lists_callers_books() {
+    let db = db()
+        .append_query_results([vec![owned_author()]])
+        .append_query_results([vec![owned_book()]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, OWNER, Method::Get, "/authors/10/books", &[], None).await;
+    assert_eq!(reply.status, Status::Ok);
+    assert_scoped(&reply.statements[1], "book", OWNER);
+    // any role counts, not only the primary author
+    assert!(reply.statements[1].sql.contains("`book`.`id` IN (SELECT `book_id` FROM `book_contributor`"), "{}", reply.statements[1].sql);
+}
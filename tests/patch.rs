@@ -0,0 +1,140 @@
+mod common;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::{author, book, user};
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "https://example.com/wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn me() -> user::Model {
+    user::Model {
+        id: 1,
+        email: "ursula@example.com".to_string(),
+        password: "hash".to_string(),
+        firstname: Some("Ursula".to_string()),
+        lastname: Some("Le Guin".to_string()),
+        role: "user".to_string(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn updated() -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected: 1 }
+}
+
+/// The column list of an `UPDATE ... SET ... WHERE` statement.
+fn set_clause(sql: &str) -> &str {
+    let start = sql.find(" SET ").expect("not an update") + 5;
+    let end = sql.find(" WHERE ").unwrap();
+    &sql[start..end]
+}
+
+#[rocket::async_test]
+async fn book_patch_writes_only_given_fields() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_exec_results([updated()])
+        .append_query_results([vec![book::Model { title: "Tehanu".to_string(), ..book() }]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[], Some(r#"{"title":"Tehanu"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["title"], "Tehanu");
+    assert_eq!(set_clause(&reply.statements[1].sql), "`title` = ?, `updated_at` = ?, `version` = ?");
+}
+
+#[rocket::async_test]
+async fn book_patch_checks_new_author() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([Vec::<author::Model>::new()]);
+    let reply = request(db, 1, Method::Patch, "/books/20", &[], Some(r#"{"author_id":99}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.statements.len(), 2);
+}
+
+#[rocket::async_test]
+async fn book_patch_validates_given_fields() {
+    let reply = request(db(), 1, Method::Patch, "/books/20", &[], Some(r#"{"published_on":{"year":1968,"day":3}}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn author_patch_writes_only_given_fields() {
+    let db = db()
+        .append_query_results([vec![author()]])
+        .append_exec_results([updated()])
+        .append_query_results([vec![author()]]);
+    let reply = request(db, 1, Method::Patch, "/authors/10", &[], Some(r#"{"bio":"Earthsea"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(set_clause(&reply.statements[1].sql), "`bio` = ?, `updated_at` = ?, `version` = ?");
+}
+
+#[rocket::async_test]
+async fn me_patch_null_clears_and_absent_keeps() {
+    let db = db()
+        .append_query_results([vec![me()]])
+        .append_exec_results([updated()])
+        .append_query_results([vec![user::Model { lastname: None, ..me() }]]);
+    let reply = request(db, 1, Method::Patch, "/auth/me", &[], Some(r#"{"lastname":null}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["lastname"], rocket::serde::json::Value::Null);
+    let update = &reply.statements[1];
+    assert_eq!(set_clause(&update.sql), "`lastname` = ?, `updated_at` = ?");
+    assert_eq!(update.values.as_ref().unwrap().0[0], Value::String(None));
+}
+
+#[rocket::async_test]
+async fn me_patch_rejects_blank_name() {
+    let reply = request(db(), 1, Method::Patch, "/auth/me", &[], Some(r#"{"firstname":" "}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::{
+    entities::{author, book},
+    value_object::published_on::{DatePrecision, PublishedOn, PublishedOnError},
+};
+
+fn book(published_on: Option<PublishedOn>) -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "https://example.com/wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: published_on.map(|p| p.year),
+        published_month: published_on.and_then(|p| p.month),
+        published_day: published_on.and_then(|p| p.day),
+        published_precision: published_on.map(|p| p.precision.as_str().to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+#[test]
+fn parses_the_years_clients_used_to_send() {
+    let parsed = |value: &str| PublishedOn::parse(value).map(|p| (p.year, p.month, p.day, p.precision));
+
+    assert_eq!(parsed("2019"), Some((2019, None, None, DatePrecision::Year)));
+    assert_eq!(parsed(" 2019-05 "), Some((2019, Some(5), None, DatePrecision::Month)));
+    assert_eq!(parsed("2019/05/12"), Some((2019, Some(5), Some(12), DatePrecision::Day)));
+    assert_eq!(parsed("c. 1900"), Some((1900, None, None, DatePrecision::Circa)));
+    assert_eq!(parsed("circa 1900"), Some((1900, None, None, DatePrecision::Circa)));
+    assert_eq!(parsed("1900?"), Some((1900, None, None, DatePrecision::Circa)));
+}
+
+#[test]
+fn rejects_text_that_is_not_a_date() {
+    for value in ["unknown", "", "soon", "2019-13", "2019-02-30", "c. 1900-05", "99999", "0", "2019-05-12-01"] {
+        assert_eq!(PublishedOn::parse(value), None, "{:?}", value);
+    }
+}
+
+#[test]
+fn text_form_reads_back() {
+    for value in ["1968", "1968-05", "1968-05-12", "c. 1900", "868"] {
+        assert_eq!(PublishedOn::parse(value).unwrap().to_string(), value);
+    }
+}
+
+#[test]
+fn precision_must_match_the_given_parts() {
+    assert_eq!(PublishedOn::new(1968, None, Some(3), None), Err(PublishedOnError::Day));
+    assert_eq!(PublishedOn::new(1968, Some(5), None, Some(DatePrecision::Circa)), Err(PublishedOnError::Precision));
+    assert_eq!(PublishedOn::new(1968, None, None, Some(DatePrecision::Day)), Err(PublishedOnError::Precision));
+    assert_eq!(PublishedOn::new(1968, Some(5), None, None).unwrap().precision, DatePrecision::Month);
+}
+
+#[rocket::async_test]
+async fn book_shows_its_date_and_the_derived_year() {
+    let date = PublishedOn::new(1968, Some(11), None, None).ok();
+    let db = db()
+        .append_query_results([vec![book(date)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["year"], "1968");
+    assert_eq!(body["published_on"]["year"], 1968);
+    assert_eq!(body["published_on"]["month"], 11);
+    assert_eq!(body["published_on"]["day"], rocket::serde::json::Value::Null);
+    assert_eq!(body["published_on"]["precision"], "month");
+}
+
+fn author() -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }
+}
+
+fn db_for_update(before: Option<PublishedOn>, after: Option<PublishedOn>) -> sea_orm::MockDatabase {
+    db()
+        .append_query_results([vec![book(before)]])
+        .append_query_results([vec![author()]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![book(after)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()])
+}
+
+#[rocket::async_test]
+async fn published_on_wins_over_the_year_of_older_clients() {
+    let date = PublishedOn::new(1968, Some(11), None, None).ok();
+    // what GET answered, `year` included and even disagreeing with the date
+    let body = r#"{"author_id":10,"title":"A Wizard of Earthsea","year":"1970","published_on":{"year":1968,"month":11},"cover":"https://example.com/wizard.jpg"}"#;
+    let reply = request(db_for_update(date, date), 1, Method::Put, "/books/20", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["year"], "1968");
+    let update = &reply.statements[2];
+    assert!(!update.sql.contains("`year`"), "{}", update.sql);
+    let values = &update.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::Int(Some(1968))));
+    assert!(!values.contains(&Value::Int(Some(1970))));
+}
+
+#[rocket::async_test]
+async fn year_alone_sets_the_date() {
+    let date = PublishedOn::new(1968, None, None, None).ok();
+    // an older client that knows nothing of `published_on`
+    let body = r#"{"author_id":10,"title":"A Wizard of Earthsea","year":"1968","cover":"https://example.com/wizard.jpg"}"#;
+    let reply = request(db_for_update(None, date), 1, Method::Put, "/books/20", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let values = &reply.statements[2].values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::Int(Some(1968))));
+    assert!(values.contains(&Value::String(Some(Box::new("year".to_string())))));
+}
+
+#[rocket::async_test]
+async fn year_that_is_not_a_date_is_rejected() {
+    let body = r#"{"author_id":10,"title":"A Wizard of Earthsea","year":"unknown","cover":"https://example.com/wizard.jpg"}"#;
+    let reply = request(db(), 1, Method::Put, "/books/20", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn setting_a_date_drops_the_year_that_was_not_one() {
+    let date = PublishedOn::new(1968, None, None, None).ok();
+    let before = book::Model { published_text: Some("around the sixties".to_string()), ..book(None) };
+    let shown = db()
+        .append_query_results([vec![before.clone()]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(shown, 1, Method::Get, "/books/20", &[], None).await;
+    assert_eq!(reply.body.unwrap()["published_text"], "around the sixties");
+
+    let updated = db()
+        .append_query_results([vec![before]])
+        .append_query_results([vec![author()]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![book(date)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let body = r#"{"author_id":10,"title":"A Wizard of Earthsea","published_on":{"year":1968},"cover":"https://example.com/wizard.jpg"}"#;
+    let reply = request(updated, 1, Method::Put, "/books/20", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["published_text"], rocket::serde::json::Value::Null);
+    let update = &reply.statements[2];
+    assert!(update.sql.contains("`published_text` = ?"), "{}", update.sql);
+    assert!(update.values.as_ref().unwrap().0.contains(&Value::String(None)));
+}
+
+#[rocket::async_test]
+async fn unknown_date_has_an_empty_year() {
+    let db = db()
+        .append_query_results([vec![book(None)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[], None).await;
+
+    let body = reply.body.unwrap();
+    assert_eq!(body["year"], "");
+    assert_eq!(body["published_on"], rocket::serde::json::Value::Null);
+}
+
+#[rocket::async_test]
+async fn year_range_and_sort_use_the_date_columns() {
+    let count = BTreeMap::from([("num_items", Value::Int(Some(1)))]);
+    let db = db()
+        .append_query_results([vec![count]])
+        .append_query_results([vec![book(PublishedOn::parse("1968"))]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books?year_from=1960&year_to=1970&sort=year&order=asc", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let sql = &reply.statements[1].sql;
+    assert!(sql.contains("`book`.`published_year` >= ?"));
+    assert!(sql.contains("`book`.`published_year` <= ?"));
+    assert!(sql.contains(
+        "ORDER BY `book`.`published_year` ASC, `book`.`published_month` ASC, `book`.`published_day` ASC, `book`.`id` ASC"
+    ));
+}
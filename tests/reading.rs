@@ -0,0 +1,244 @@
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{prelude::DateTimeUtc, MockExecResult, Value};
+use udemy_mini_book::domain::{
+    entities::{book, reading_session, reading_state},
+    value_object::reading::{Progress, ReadingStatus},
+};
+
+fn at(day: u32) -> DateTimeUtc {
+    Utc.with_ymd_and_hms(2026, 9, day, 20, 0, 0).unwrap()
+}
+
+fn book(id: i32, total_pages: Option<i32>) -> book::Model {
+    book::Model {
+        id,
+        user_id: 1,
+        author_id: 10,
+        title: "The Dispossessed".to_string(),
+        cover: "https://example.com/dispossessed.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1974),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages,
+    }
+}
+
+fn state(book_id: i32, status: ReadingStatus, current_page: Option<i32>) -> reading_state::Model {
+    reading_state::Model {
+        id: 7,
+        user_id: 1,
+        book_id,
+        status: status.as_str().to_string(),
+        current_page,
+        percent: None,
+        started_at: (status != ReadingStatus::WantToRead).then(|| at(1)),
+        finished_at: None,
+        updated_at: Some(at(2)),
+    }
+}
+
+fn session(start_page: i32, end_page: i32) -> reading_session::Model {
+    reading_session::Model {
+        id: 3,
+        user_id: 1,
+        book_id: 20,
+        started_at: at(5),
+        ended_at: at(5) + Duration::minutes(45),
+        start_page: Some(start_page),
+        end_page: Some(end_page),
+        created_at: None,
+    }
+}
+
+fn has_string(values: &[Value], value: &str) -> bool {
+    values.contains(&Value::String(Some(Box::new(value.to_string()))))
+}
+
+#[test]
+fn dates_follow_the_transitions() {
+    let reading = state(20, ReadingStatus::Reading, Some(40));
+
+    assert_eq!(ReadingStatus::Reading.dates(None, at(3)), (Some(at(3)), None));
+    assert_eq!(ReadingStatus::Reading.dates(Some(&reading), at(3)), (Some(at(1)), None));
+    assert_eq!(ReadingStatus::Finished.dates(Some(&reading), at(3)), (Some(at(1)), Some(at(3))));
+    assert_eq!(ReadingStatus::Abandoned.dates(Some(&reading), at(3)), (Some(at(1)), None));
+    assert_eq!(ReadingStatus::WantToRead.dates(Some(&reading), at(3)), (None, None));
+
+    let mut finished = state(20, ReadingStatus::Finished, Some(200));
+    finished.finished_at = Some(at(2));
+    assert_eq!(ReadingStatus::Finished.dates(Some(&finished), at(3)), (Some(at(1)), Some(at(2))));
+    assert_eq!(ReadingStatus::Reading.dates(Some(&finished), at(3)), (Some(at(3)), None));
+}
+
+#[test]
+fn progress_converts_between_page_and_percent() {
+    assert_eq!(Progress::new(Some(50), None, Some(200)).unwrap(), Progress { current_page: Some(50), percent: Some(25) });
+    assert_eq!(Progress::new(None, Some(10), Some(200)).unwrap(), Progress { current_page: Some(20), percent: Some(10) });
+    assert_eq!(Progress::new(Some(50), Some(90), None).unwrap(), Progress { current_page: Some(50), percent: Some(90) });
+    assert!(Progress::new(Some(201), None, Some(200)).is_err());
+    assert_eq!(
+        Progress::new(Some(50), None, Some(200)).unwrap().for_status(ReadingStatus::Finished, Some(200)),
+        Progress { current_page: Some(200), percent: Some(100) }
+    );
+}
+
+#[rocket::async_test]
+async fn first_status_inserts_the_state() {
+    let db = db()
+        .append_query_results([vec![book(20, Some(200))]])
+        .append_query_results([Vec::<reading_state::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 7, rows_affected: 1 }])
+        .append_query_results([vec![state(20, ReadingStatus::Reading, Some(50))]]);
+    let reply = request(db, 1, Method::Put, "/books/20/status", &[], Some(r#"{"status":"reading","current_page":50}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["status"], "reading");
+    assert_eq!(body["total_pages"], 200);
+    assert!(reply.statements[0].sql.contains("`book`.`user_id` = ?"));
+    assert!(reply.statements[0].sql.contains("`book`.`deleted_at` IS NULL"));
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `reading_state`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(has_string(values, "reading"));
+    assert!(values.contains(&Value::TinyUnsigned(Some(25))));
+}
+
+#[rocket::async_test]
+async fn finishing_keeps_the_start_and_completes_the_progress() {
+    let db = db()
+        .append_query_results([vec![book(20, Some(200))]])
+        .append_query_results([vec![state(20, ReadingStatus::Reading, Some(150))]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![state(20, ReadingStatus::Finished, Some(200))]]);
+    let reply = request(db, 1, Method::Put, "/books/20/status", &[], Some(r#"{"status":"finished"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let update = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `reading_state`")).unwrap();
+    let values = &update.values.as_ref().unwrap().0;
+    assert!(has_string(values, "finished"));
+    assert!(values.contains(&Value::Int(Some(200))));
+    assert!(values.contains(&Value::TinyUnsigned(Some(100))));
+    assert!(values.contains(&Value::ChronoDateTimeUtc(Some(Box::new(at(1))))));
+}
+
+#[rocket::async_test]
+async fn status_of_a_missing_book_is_not_found() {
+    let db = db().append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 1, Method::Put, "/books/99/status", &[], Some(r#"{"status":"reading"}"#)).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn page_past_the_end_is_rejected() {
+    let db = db()
+        .append_query_results([vec![book(20, Some(200))]])
+        .append_query_results([Vec::<reading_state::Model>::new()]);
+    let reply = request(db, 1, Method::Put, "/books/20/status", &[], Some(r#"{"status":"reading","current_page":250}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["current_page"].is_array());
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn session_starts_reading_a_wished_book() {
+    let db = db()
+        .append_query_results([vec![book(20, Some(200))]])
+        .append_exec_results([MockExecResult { last_insert_id: 3, rows_affected: 1 }])
+        .append_query_results([vec![session(0, 30)]])
+        .append_query_results([vec![state(20, ReadingStatus::WantToRead, None)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![state(20, ReadingStatus::Reading, Some(30))]]);
+    let body = format!(
+        r#"{{"started_at":"{}","ended_at":"{}","start_page":0,"end_page":30}}"#,
+        at(5).to_rfc3339(),
+        (at(5) + Duration::minutes(45)).to_rfc3339()
+    );
+    let reply = request(db, 1, Method::Post, "/books/20/sessions", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    let body = reply.body.unwrap();
+    assert_eq!(body["session"]["minutes"], 45);
+    assert_eq!(body["session"]["pages"], 30);
+    let update = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `reading_state`")).unwrap();
+    let values = &update.values.as_ref().unwrap().0;
+    assert!(has_string(values, "reading"));
+    assert!(values.contains(&Value::Int(Some(30))));
+    assert!(values.contains(&Value::ChronoDateTimeUtc(Some(Box::new(at(5))))));
+}
+
+#[rocket::async_test]
+async fn session_on_the_last_page_finishes_the_book() {
+    let db = db()
+        .append_query_results([vec![book(20, Some(200))]])
+        .append_exec_results([MockExecResult { last_insert_id: 3, rows_affected: 1 }])
+        .append_query_results([vec![session(150, 200)]])
+        .append_query_results([vec![state(20, ReadingStatus::Reading, Some(150))]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![state(20, ReadingStatus::Finished, Some(200))]]);
+    let body = format!(
+        r#"{{"started_at":"{}","ended_at":"{}","start_page":150,"end_page":200}}"#,
+        at(5).to_rfc3339(),
+        (at(5) + Duration::minutes(45)).to_rfc3339()
+    );
+    let reply = request(db, 1, Method::Post, "/books/20/sessions", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    let update = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `reading_state`")).unwrap();
+    let values = &update.values.as_ref().unwrap().0;
+    assert!(has_string(values, "finished"));
+    assert!(values.contains(&Value::ChronoDateTimeUtc(Some(Box::new(at(1))))));
+    assert!(values.contains(&Value::ChronoDateTimeUtc(Some(Box::new(at(5) + Duration::minutes(45))))));
+}
+
+#[rocket::async_test]
+async fn session_must_end_after_it_starts() {
+    let body = format!(
+        r#"{{"started_at":"{}","ended_at":"{}"}}"#,
+        at(5).to_rfc3339(),
+        at(4).to_rfc3339()
+    );
+    let reply = request(db(), 1, Method::Post, "/books/20/sessions", &[], Some(&body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["ended_at"][0], "must be after started_at");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn current_lists_the_books_being_read() {
+    let db = db()
+        .append_query_results([vec![(state(20, ReadingStatus::Reading, Some(50)), book(20, Some(200)))]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/reading/current", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["books"][0]["book"]["id"], 20);
+    assert_eq!(body["books"][0]["state"]["current_page"], 50);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("`reading_state`.`status` = ?"));
+    assert!(stmt.sql.contains("`book`.`deleted_at` IS NULL"));
+    assert!(stmt.sql.contains("ORDER BY `reading_state`.`updated_at` DESC"));
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, no_contributors, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Statement, Value};
+use udemy_mini_book::domain::{
+    entities::{book, review},
+    value_object::{review::ResRating, validation::half_star_rating},
+};
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "The Tombs of Atuan".to_string(),
+        cover: "https://example.com/tombs.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1970),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn review(id: i32, user_id: i32, half_stars: u8) -> review::Model {
+    review::Model {
+        id,
+        user_id,
+        book_id: 20,
+        half_stars,
+        text: "Darker than the first one.".to_string(),
+        spoiler: false,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn rating(half_stars_sum: i64, count: i64) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("book_id", Value::Int(Some(20))),
+        ("half_stars_sum", Value::BigInt(Some(half_stars_sum))),
+        ("count", Value::BigInt(Some(count))),
+    ])
+}
+
+// the book answers with its rating, a conditional GET must not find it fresh
+fn assert_bumps_the_book(statements: &[Statement]) {
+    let bump = statements.last().unwrap();
+    assert_eq!(bump.sql, "UPDATE `book` SET `version` = `version` + ? WHERE `book`.`id` = ?");
+    assert_eq!(bump.values.as_ref().unwrap().0, vec![Value::Int(Some(1)), Value::Int(Some(20))]);
+}
+
+#[test]
+fn ratings_go_by_half_stars() {
+    assert!(half_star_rating(1.0).is_ok());
+    assert!(half_star_rating(4.5).is_ok());
+    assert!(half_star_rating(5.0).is_ok());
+    assert!(half_star_rating(0.5).is_err());
+    assert!(half_star_rating(4.3).is_err());
+    assert!(half_star_rating(5.5).is_err());
+}
+
+#[test]
+fn average_is_in_stars() {
+    assert_eq!(ResRating::new(17, 2), ResRating { average: Some(4.25), count: 2 });
+    assert_eq!(ResRating::new(20, 3), ResRating { average: Some(3.33), count: 3 });
+    assert_eq!(ResRating::new(0, 0), ResRating { average: None, count: 0 });
+}
+
+#[rocket::async_test]
+async fn create_stores_the_rating_in_half_stars() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([Vec::<review::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 5, rows_affected: 1 }])
+        .append_query_results([vec![review(5, 1, 9)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Post, "/books/20/reviews", &[], Some(r#"{"rating":4.5,"text":"Darker than the first one."}"#)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    assert_eq!(reply.body.unwrap()["rating"], 4.5);
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `review`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::TinyUnsigned(Some(9))));
+    assert!(values.contains(&Value::Bool(Some(false))));
+    assert_bumps_the_book(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn second_review_of_a_book_is_a_conflict() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![review(5, 1, 9)]]);
+    let reply = request(db, 1, Method::Post, "/books/20/reviews", &[], Some(r#"{"rating":3}"#)).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.body.unwrap()["details"]["review_id"], 5);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn rating_off_the_half_stars_is_rejected() {
+    let reply = request(db(), 1, Method::Post, "/books/20/reviews", &[], Some(r#"{"rating":4.3}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["rating"][0], "must be between 1 and 5 in steps of 0.5");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn index_lists_the_reviews_with_the_average() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(2)))])]])
+        .append_query_results([vec![review(5, 1, 9), review(6, 1, 8)]])
+        .append_query_results([vec![rating(17, 2)]]);
+    let reply = request(db, 1, Method::Get, "/books/20/reviews", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["rating"]["average"], 4.25);
+    assert_eq!(body["reviews"][1]["rating"], 4.0);
+    let aggregate = reply.statements.last().unwrap();
+    assert!(aggregate.sql.contains("GROUP BY `review`.`book_id`"));
+}
+
+#[rocket::async_test]
+async fn book_shows_its_rating() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([no_contributors()])
+        .append_query_results([vec![rating(7, 1)]])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["rating"]["average"], 3.5);
+    assert_eq!(body["rating"]["count"], 1);
+}
+
+#[rocket::async_test]
+async fn reviews_of_a_trashed_book_are_hidden() {
+    let db = db().append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 1, Method::Get, "/books/20/reviews", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements[0].sql.contains("`book`.`deleted_at` IS NULL"));
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn update_replaces_the_review() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![review(5, 1, 9)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![review::Model { spoiler: true, ..review(5, 1, 4) }]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Put, "/books/20/reviews/5", &[], Some(r#"{"rating":2,"text":"Ends badly","spoiler":true}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["spoiler"], true);
+    let update = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `review`")).unwrap();
+    let values = &update.values.as_ref().unwrap().0;
+    assert!(values.contains(&Value::TinyUnsigned(Some(4))));
+    assert!(values.contains(&Value::Bool(Some(true))));
+    assert_bumps_the_book(&reply.statements);
+}
+
+#[rocket::async_test]
+async fn someone_elses_review_cannot_be_deleted() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![review(5, 2, 9)]]);
+    let reply = request(db, 1, Method::Delete, "/books/20/reviews/5", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn delete_removes_the_review() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![review(5, 1, 9)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Delete, "/books/20/reviews/5", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements.iter().any(|s| s.sql.starts_with("DELETE FROM `review`")));
+    assert_bumps_the_book(&reply.statements);
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::{
+    entities::{author, book, series},
+    value_object::series::{duplicates, gaps, ResGap},
+};
+
+fn series() -> series::Model {
+    series::Model {
+        id: 5,
+        user_id: 1,
+        title: "Earthsea".to_string(),
+        description: "The Earthsea cycle".to_string(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn book(id: i32, series_index: Option<i32>) -> book::Model {
+    book::Model {
+        id,
+        user_id: 1,
+        author_id: 10,
+        title: format!("Volume {}", id),
+        cover: "https://example.com/earthsea.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: None,
+        published_month: None,
+        published_day: None,
+        published_precision: None,
+        published_text: None,
+        series_id: Some(5),
+        series_index,
+        total_pages: None,
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected }
+}
+
+#[test]
+fn gaps_are_runs_of_missing_volumes() {
+    assert_eq!(gaps(&[]), vec![]);
+    assert_eq!(gaps(&[1, 2, 3]), vec![]);
+    assert_eq!(gaps(&[2, 3]), vec![ResGap { from: 1, to: 1 }]);
+    assert_eq!(gaps(&[6, 1, 3, 3]), vec![ResGap { from: 2, to: 2 }, ResGap { from: 4, to: 5 }]);
+    assert_eq!(duplicates(&[3, 1, 3, 2, 3, 2]), vec![2, 3]);
+}
+
+#[rocket::async_test]
+async fn create_belongs_to_the_caller() {
+    let db = db()
+        .append_exec_results([MockExecResult { last_insert_id: 5, rows_affected: 1 }])
+        .append_query_results([vec![series()]]);
+    let reply = request(db, 1, Method::Post, "/series", &[], Some(r#"{"title":"Earthsea"}"#)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    assert_eq!(reply.body.unwrap()["title"], "Earthsea");
+    let insert = &reply.statements[0];
+    assert!(insert.sql.starts_with("INSERT INTO `series`"));
+    assert!(insert.values.as_ref().unwrap().0.contains(&Value::Int(Some(1))));
+}
+
+#[rocket::async_test]
+async fn create_rejects_a_blank_title() {
+    let reply = request(db(), 1, Method::Post, "/series", &[], Some(r#"{"title":" "}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn foreign_series_is_not_found() {
+    let db = db().append_query_results([Vec::<series::Model>::new()]);
+    let reply = request(db, 2, Method::Get, "/series/5", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements[0].sql.contains("`series`.`user_id` = ?"));
+}
+
+#[rocket::async_test]
+async fn books_come_in_volume_order_with_the_gaps() {
+    let db = db()
+        .append_query_results([vec![series()]])
+        .append_query_results([vec![book(21, Some(1)), book(23, Some(4)), book(24, Some(4)), book(22, None)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/series/5/books", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["series"]["id"], 5);
+    assert_eq!(body["total"], 4);
+    assert_eq!(body["books"][1]["series_index"], 4);
+    assert_eq!(body["gaps"][0]["from"], 2);
+    assert_eq!(body["gaps"][0]["to"], 3);
+    assert_eq!(body["duplicates"][0], 4);
+
+    let sql = &reply.statements[1].sql;
+    assert!(sql.contains("`book`.`deleted_at` IS NULL"), "{}", sql);
+    assert!(sql.contains("ORDER BY `book`.`series_index` IS NULL ASC, `book`.`series_index` ASC, `book`.`id` ASC"), "{}", sql);
+}
+
+#[rocket::async_test]
+async fn delete_takes_the_books_out_of_the_series() {
+    let db = db()
+        .append_query_results([vec![series()]])
+        .append_exec_results([rows(3), rows(1)]);
+    let reply = request(db, 1, Method::Delete, "/series/5", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let detach = &reply.statements[1].sql;
+    assert!(detach.starts_with("UPDATE `book` SET `series_id` = ?, `series_index` = ?, `version` = `version` + ?"), "{}", detach);
+    assert!(reply.statements[2].sql.starts_with("DELETE FROM `series`"));
+}
+
+#[rocket::async_test]
+async fn a_volume_number_needs_a_series() {
+    let body = r#"{"author_id":10,"title":"Tehanu","cover":"https://example.com/tehanu.jpg","series_index":4}"#;
+    let db = db().append_query_results([vec![author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: String::new(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+    }]]);
+    let reply = request(db, 1, Method::Post, "/books", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["series_index"].is_array());
+}
+
+#[rocket::async_test]
+async fn patch_checks_the_series_belongs_to_the_caller() {
+    let db = db()
+        .append_query_results([vec![book(21, None)]])
+        .append_query_results([Vec::<series::Model>::new()]);
+    let reply = request(db, 2, Method::Patch, "/books/21", &[], Some(r#"{"series_id":6,"series_index":2}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["series_id"].is_array());
+    assert!(reply.statements[1].sql.contains("`series`.`user_id` = ?"));
+}
+
+#[rocket::async_test]
+async fn patch_leaving_the_series_drops_the_volume_number() {
+    let db = db()
+        .append_query_results([vec![book(21, Some(3))]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![book::Model { series_id: None, series_index: None, version: 2, ..book(21, None) }]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Patch, "/books/21", &[], Some(r#"{"series_id":null}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let update = &reply.statements[1];
+    assert!(update.sql.contains("`series_id` = ?, `series_index` = ?"), "{}", update.sql);
+    let cleared = update.values.as_ref().unwrap().0.iter().filter(|v| **v == Value::Int(None)).count();
+    assert_eq!(cleared, 2);
+}
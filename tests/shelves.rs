@@ -0,0 +1,149 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::entities::{book, book_shelf, shelf};
+
+fn shelf(id: i32, name: &str, position: i32) -> shelf::Model {
+    shelf::Model {
+        id,
+        user_id: 1,
+        name: name.to_string(),
+        description: None,
+        position,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "The Farthest Shore".to_string(),
+        cover: "https://example.com/shore.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1972),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn has_string(values: &[Value], value: &str) -> bool {
+    values.contains(&Value::String(Some(Box::new(value.to_string()))))
+}
+
+#[rocket::async_test]
+async fn new_shelf_goes_last() {
+    let db = db()
+        .append_query_results([Vec::<shelf::Model>::new()])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(2)))])]])
+        .append_exec_results([MockExecResult { last_insert_id: 3, rows_affected: 1 }])
+        .append_query_results([vec![shelf(3, "Favourites", 2)]]);
+    let reply = request(db, 1, Method::Post, "/shelves", &[], Some(r#"{"name":" Favourites "}"#)).await;
+
+    assert_eq!(reply.status, Status::Created);
+    assert_eq!(reply.body.unwrap()["position"], 2);
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `shelf`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(has_string(values, "Favourites"));
+    assert!(values.contains(&Value::Int(Some(2))));
+}
+
+#[rocket::async_test]
+async fn shelf_names_are_unique_per_user() {
+    let db = db().append_query_results([vec![shelf(3, "Favourites", 0)]]);
+    let reply = request(db, 1, Method::Post, "/shelves", &[], Some(r#"{"name":"Favourites"}"#)).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.body.unwrap()["details"]["shelf_id"], 3);
+    assert!(reply.statements[0].sql.contains("`shelf`.`user_id` = ?"));
+    assert_eq!(reply.statements.len(), 1);
+}
+
+#[rocket::async_test]
+async fn shelves_are_listed_in_their_order() {
+    let db = db().append_query_results([vec![shelf(4, "To read", 0), shelf(3, "Favourites", 1)]]);
+    let reply = request(db, 1, Method::Get, "/shelves", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["shelves"][0]["name"], "To read");
+    assert!(reply.statements[0].sql.contains("ORDER BY `shelf`.`position` ASC, `shelf`.`id` ASC"));
+}
+
+#[rocket::async_test]
+async fn adding_a_book_twice_changes_nothing() {
+    let db = db()
+        .append_query_results([vec![shelf(3, "Favourites", 0)]])
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![book_shelf::Model { shelf_id: 3, book_id: 20, created_at: None }]]);
+    let reply = request(db, 1, Method::Put, "/shelves/3/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn adding_a_book_links_it() {
+    let db = db()
+        .append_query_results([vec![shelf(3, "Favourites", 0)]])
+        .append_query_results([vec![book()]])
+        .append_query_results([Vec::<book_shelf::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Put, "/shelves/3/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements[1].sql.contains("`book`.`deleted_at` IS NULL"));
+    assert!(reply.statements.last().unwrap().sql.starts_with("INSERT INTO `book_shelf`"));
+}
+
+#[rocket::async_test]
+async fn foreign_book_cannot_be_shelved() {
+    let db = db()
+        .append_query_results([vec![shelf(3, "Favourites", 0)]])
+        .append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 2, Method::Put, "/shelves/3/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements.iter().all(|s| s.sql.starts_with("SELECT")));
+}
+
+#[rocket::async_test]
+async fn removing_a_book_not_on_the_shelf_is_not_found() {
+    let db = db()
+        .append_query_results([vec![shelf(3, "Favourites", 0)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 0 }]);
+    let reply = request(db, 1, Method::Delete, "/shelves/3/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn books_index_filters_by_shelf() {
+    let db = db()
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(0)))])]]);
+    let reply = request(db, 1, Method::Get, "/books?shelf=3", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("`book`.`id` IN (SELECT `book_id` FROM `book_shelf` WHERE `book_shelf`.`shelf_id` = ?)"));
+    assert!(stmt.sql.contains("`book`.`user_id` = ?"));
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::{entities::reading_goal, value_object::stats::ResGoalProgress};
+
+type Row = BTreeMap<&'static str, Value>;
+
+fn period(period: i32, books: i64) -> Row {
+    BTreeMap::from([("period", Value::Int(Some(period))), ("books", Value::BigInt(Some(books)))])
+}
+
+fn pages(pages: Option<i64>, sessions: i64) -> Vec<Row> {
+    vec![BTreeMap::from([("pages", Value::BigInt(pages)), ("sessions", Value::BigInt(Some(sessions)))])]
+}
+
+fn rating(half_stars_sum: Option<i64>, count: i64) -> Vec<Row> {
+    vec![BTreeMap::from([("half_stars_sum", Value::BigInt(half_stars_sum)), ("count", Value::BigInt(Some(count)))])]
+}
+
+fn author(author_id: i32, lastname: &str, books: i64) -> Row {
+    BTreeMap::from([
+        ("author_id", Value::Int(Some(author_id))),
+        ("firstname", Value::String(Some(Box::new("Ursula".to_string())))),
+        ("lastname", Value::String(Some(Box::new(lastname.to_string())))),
+        ("books", Value::BigInt(Some(books))),
+    ])
+}
+
+fn goal(year: i32, target_books: i32) -> reading_goal::Model {
+    reading_goal::Model {
+        id: 3,
+        user_id: 1,
+        year,
+        target_books,
+        target_pages: Some(5000),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[test]
+fn goal_progress_follows_the_year() {
+    let day = NaiveDate::from_ymd_opt(2023, 7, 2).unwrap();
+
+    // half of 2023 has gone by on the 2nd of July
+    let current = ResGoalProgress::new(&goal(2023, 24), 10, 1200, day);
+    assert_eq!(current.percent, 41);
+    assert_eq!(current.expected_books, 12);
+    assert!(!current.on_track);
+
+    let past = ResGoalProgress::new(&goal(2022, 10), 12, 0, day);
+    assert_eq!((past.percent, past.expected_books, past.on_track), (120, 10, true));
+
+    let future = ResGoalProgress::new(&goal(2024, 10), 0, 0, day);
+    assert_eq!((future.percent, future.expected_books, future.on_track), (0, 0, true));
+}
+
+#[rocket::async_test]
+async fn stats_are_counted_by_the_database() {
+    let db = db()
+        .append_query_results([vec![period(2022, 7), period(2023, 3)]])
+        .append_query_results([vec![period(1, 1), period(3, 2)]])
+        .append_query_results([pages(Some(840), 12)])
+        .append_query_results([rating(Some(17), 2)])
+        .append_query_results([vec![author(10, "Le Guin", 4), author(11, "Butler", 2)]])
+        .append_query_results([vec![goal(2023, 12)]]);
+    let reply = request(db, 1, Method::Get, "/stats?year=2023", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["year"], 2023);
+    assert_eq!(body["books_per_year"][1]["year"], 2023);
+    assert_eq!(body["books_per_year"][1]["books"], 3);
+    assert_eq!(body["books_per_month"].as_array().unwrap().len(), 12);
+    assert_eq!(body["books_per_month"][0]["books"], 1);
+    assert_eq!(body["books_per_month"][1]["books"], 0);
+    assert_eq!(body["books_per_month"][2]["books"], 2);
+    assert_eq!(body["pages_read"], 840);
+    assert_eq!(body["sessions"], 12);
+    assert_eq!(body["rating"]["average"], 4.25);
+    assert_eq!(body["top_authors"][0]["lastname"], "Le Guin");
+    assert_eq!(body["top_authors"][0]["books"], 4);
+    assert_eq!(body["goal"]["books_read"], 3);
+    assert_eq!(body["goal"]["percent"], 25);
+    // a past year is over, the whole goal was expected
+    assert_eq!(body["goal"]["expected_books"], 12);
+    assert_eq!(body["goal"]["on_track"], false);
+
+    // only aggregates are read, never the books themselves
+    assert_eq!(reply.statements.len(), 6);
+    assert!(reply.statements.iter().all(|s| !s.sql.contains("`book`.`title`")));
+    assert!(reply.statements[0].sql.contains("GROUP BY YEAR(`reading_state`.`finished_at`)"));
+    assert!(reply.statements[0].sql.contains("`book`.`deleted_at` IS NULL"));
+    assert!(reply.statements[1].sql.contains("GROUP BY MONTH(`reading_state`.`finished_at`)"));
+    assert!(reply.statements[1].sql.contains("`reading_state`.`finished_at` >= ?"));
+    assert!(reply.statements[2].sql.contains("SUM(`reading_session`.`end_page` - `reading_session`.`start_page`)"));
+    assert!(reply.statements[3].sql.contains("SUM(`review`.`half_stars`)"));
+    assert!(reply.statements[4].sql.contains("GROUP BY `author`.`id`"));
+    assert!(reply.statements[4].sql.contains("ORDER BY COUNT(`book`.`id`) DESC"));
+    assert!(reply.statements[4].sql.contains("LIMIT ?"));
+    assert!(reply.statements[5].sql.contains("`reading_goal`.`year` = ?"));
+}
+
+#[rocket::async_test]
+async fn stats_without_any_reading_are_empty() {
+    let db = db()
+        .append_query_results([Vec::<Row>::new()])
+        .append_query_results([Vec::<Row>::new()])
+        .append_query_results([pages(None, 0)])
+        .append_query_results([rating(None, 0)])
+        .append_query_results([Vec::<Row>::new()])
+        .append_query_results([Vec::<reading_goal::Model>::new()]);
+    let reply = request(db, 1, Method::Get, "/stats?year=2023", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["books_per_year"].as_array().unwrap().len(), 0);
+    assert!(body["books_per_month"].as_array().unwrap().iter().all(|m| m["books"] == 0));
+    assert_eq!(body["pages_read"], 0);
+    assert_eq!(body["rating"]["average"], rocket::serde::json::Value::Null);
+    assert_eq!(body["goal"], rocket::serde::json::Value::Null);
+}
+
+#[rocket::async_test]
+async fn stats_reject_an_impossible_year() {
+    let reply = request(db(), 1, Method::Get, "/stats?year=0", &[], None).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["year"][0].is_string());
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn goal_is_created_for_a_new_year() {
+    let db = db()
+        .append_query_results([Vec::<reading_goal::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 3, rows_affected: 1 }])
+        .append_query_results([vec![goal(2024, 30)]]);
+    let body = r#"{"target_books":30,"target_pages":5000}"#;
+    let reply = request(db, 1, Method::Put, "/stats/goals/2024", &[], Some(body)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["target_books"], 30);
+    assert!(reply.statements[1].sql.starts_with("INSERT INTO `reading_goal`"));
+}
+
+#[rocket::async_test]
+async fn goal_replaces_the_one_of_the_year() {
+    let db = db()
+        .append_query_results([vec![goal(2024, 30)]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![goal(2024, 40)]]);
+    let reply = request(db, 1, Method::Put, "/stats/goals/2024", &[], Some(r#"{"target_books":40}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["target_books"], 40);
+    assert!(reply.statements[0].sql.contains("`reading_goal`.`user_id` = ?"));
+    assert!(reply.statements[1].sql.starts_with("UPDATE `reading_goal`"));
+}
+
+#[rocket::async_test]
+async fn goal_needs_a_book_at_least() {
+    let reply = request(db(), 1, Method::Put, "/stats/goals/2024", &[], Some(r#"{"target_books":0}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert!(reply.body.unwrap()["details"]["target_books"][0].is_string());
+}
+
+#[rocket::async_test]
+async fn deleting_a_missing_goal_is_not_found() {
+    let db = db().append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 0 }]);
+    let reply = request(db, 1, Method::Delete, "/stats/goals/2024", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+    assert!(reply.statements[0].sql.starts_with("DELETE FROM `reading_goal`"));
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{db, request};
+use rocket::http::{Method, Status};
+use sea_orm::{MockExecResult, Value};
+use udemy_mini_book::domain::{
+    entities::{book, tag},
+    value_object::tag::{normalize_tag, normalize_tags},
+};
+
+fn tag(id: i32, name: &str) -> tag::Model {
+    tag::Model { id, user_id: 1, name: name.to_string() }
+}
+
+fn book() -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "The Lathe of Heaven".to_string(),
+        cover: "https://example.com/lathe.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at: None,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1971),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn usage(id: i32, name: &str, books: i64) -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("id", Value::Int(Some(id))),
+        ("name", Value::String(Some(Box::new(name.to_string())))),
+        ("books", Value::BigInt(Some(books))),
+    ])
+}
+
+fn has_string(values: &[Value], value: &str) -> bool {
+    values.contains(&Value::String(Some(Box::new(value.to_string()))))
+}
+
+#[test]
+fn tags_are_normalized() {
+    assert_eq!(normalize_tag("  Science   Fiction "), "science fiction");
+    assert_eq!(
+        normalize_tags(&["Classic".to_string(), "sci-fi".to_string(), "CLASSIC".to_string()]),
+        vec!["classic".to_string(), "sci-fi".to_string()]
+    );
+}
+
+#[rocket::async_test]
+async fn autocomplete_puts_the_most_used_tags_first() {
+    let db = db().append_query_results([vec![usage(1, "sci-fi", 12), usage(2, "science", 3)]]);
+    let reply = request(db, 1, Method::Get, "/tags?q=Sci&limit=5", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["tags"][0]["name"], "sci-fi");
+    assert_eq!(body["tags"][0]["books"], 12);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("`tag`.`user_id` = ?"));
+    assert!(stmt.sql.contains("LIKE ? ESCAPE '\\\\'"));
+    assert!(stmt.sql.contains("`book`.`deleted_at` IS NULL"));
+    assert!(stmt.sql.contains("ORDER BY COUNT(`book`.`id`) DESC, `tag`.`name` ASC"));
+    let values = &stmt.values.as_ref().unwrap().0;
+    assert!(has_string(values, "sci%"));
+}
+
+#[rocket::async_test]
+async fn autocomplete_escapes_wildcards() {
+    let db = db().append_query_results([Vec::<BTreeMap<&str, Value>>::new()]);
+    let reply = request(db, 1, Method::Get, "/tags?q=100%25_", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let values = &reply.statements[0].values.as_ref().unwrap().0;
+    assert!(has_string(values, "100\\%\\_%"));
+}
+
+#[rocket::async_test]
+async fn setting_tags_creates_the_missing_ones() {
+    let db = db()
+        .append_query_results([vec![book()]])
+        .append_query_results([vec![tag(1, "sci-fi")]])
+        .append_exec_results([MockExecResult { last_insert_id: 2, rows_affected: 1 }])
+        .append_query_results([vec![tag(2, "classic"), tag(1, "sci-fi")]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 2 }])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Put, "/books/20/tags", &[], Some(r#"{"tags":["Sci-Fi","Classic","sci-fi"]}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["tags"][0], "classic");
+    assert_eq!(body["tags"][1], "sci-fi");
+    let insert = reply.statements.iter().find(|s| s.sql.starts_with("INSERT INTO `tag`")).unwrap();
+    let values = &insert.values.as_ref().unwrap().0;
+    assert!(has_string(values, "classic"));
+    assert!(!has_string(values, "sci-fi"));
+    assert!(reply.statements.iter().any(|s| s.sql.starts_with("DELETE FROM `book_tag`")));
+    assert!(reply.statements.iter().any(|s| s.sql.starts_with("INSERT INTO `book_tag`")));
+    let bump = reply.statements.last().unwrap();
+    assert_eq!(bump.sql, "UPDATE `book` SET `version` = `version` + ? WHERE `book`.`id` = ?");
+}
+
+#[rocket::async_test]
+async fn blank_tag_is_rejected() {
+    let reply = request(db(), 1, Method::Put, "/books/20/tags", &[], Some(r#"{"tags":["classic","  "]}"#)).await;
+
+    assert_eq!(reply.status, Status::UnprocessableEntity);
+    assert_eq!(reply.body.unwrap()["details"]["tags"][0], "must not be blank");
+    assert!(reply.statements.is_empty());
+}
+
+#[rocket::async_test]
+async fn renaming_onto_another_tag_is_a_conflict() {
+    let db = db()
+        .append_query_results([vec![tag(1, "scifi")]])
+        .append_query_results([vec![tag(2, "sci-fi")]]);
+    let reply = request(db, 1, Method::Put, "/tags/1", &[], Some(r#"{"name":"Sci-Fi"}"#)).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.body.unwrap()["details"]["tag_id"], 2);
+}
+
+#[rocket::async_test]
+async fn renaming_a_tag_bumps_its_books() {
+    let db = db()
+        .append_query_results([vec![tag(1, "scifi")]])
+        .append_query_results([Vec::<tag::Model>::new()])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }])
+        .append_query_results([vec![tag(1, "sci-fi")]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 2 }])
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(2)))])]]);
+    let reply = request(db, 1, Method::Put, "/tags/1", &[], Some(r#"{"name":"Sci-Fi"}"#)).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert_eq!(reply.body.unwrap()["books"], 2);
+    let bump = reply.statements.iter().find(|s| s.sql.starts_with("UPDATE `book`")).unwrap();
+    assert_eq!(
+        bump.sql,
+        "UPDATE `book` SET `version` = `version` + ? WHERE `book`.`id` IN (SELECT `book_id` FROM `book_tag` WHERE `book_tag`.`tag_id` = ?)"
+    );
+}
+
+#[rocket::async_test]
+async fn deleting_a_tag_bumps_its_books_first() {
+    let db = db()
+        .append_query_results([vec![tag(1, "sci-fi")]])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 2 }])
+        .append_exec_results([MockExecResult { last_insert_id: 0, rows_affected: 1 }]);
+    let reply = request(db, 1, Method::Delete, "/tags/1", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let writes: Vec<&str> = reply.statements.iter().skip(1).map(|s| s.sql.as_str()).collect();
+    assert!(writes[0].starts_with("UPDATE `book` SET `version` = `version` + ?"));
+    assert!(writes[1].starts_with("DELETE FROM `tag`"));
+}
+
+#[rocket::async_test]
+async fn books_index_filters_by_tag() {
+    let db = db()
+        .append_query_results([vec![BTreeMap::from([("num_items", Value::Int(Some(0)))])]]);
+    let reply = request(db, 1, Method::Get, "/books?tag=Sci-Fi", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let stmt = &reply.statements[0];
+    assert!(stmt.sql.contains("INNER JOIN `tag` ON `tag`.`id` = `book_tag`.`tag_id`"));
+    assert!(has_string(&stmt.values.as_ref().unwrap().0, "sci-fi"));
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use common::{db, no_contributors, no_ratings, no_tags, request};
+use rocket::http::{Method, Status};
+use sea_orm::{prelude::DateTimeUtc, DatabaseBackend, MockDatabase, MockExecResult};
+use udemy_mini_book::{
+    application::service::trash_service::TrashService,
+    domain::{
+        entities::{author, book},
+        repositories::blob_store::BlobStore,
+        value_object::{cover::{Blob, CoverRef, CoverSize}, trash::PurgeCount},
+    },
+    infrastructure::{blob::fs_store::FsBlobStore, db::repositories::trash_repository::TrashRepositoryImplSql},
+};
+
+fn trashed_at() -> DateTimeUtc {
+    Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap()
+}
+
+fn book(deleted_at: Option<DateTimeUtc>) -> book::Model {
+    book::Model {
+        id: 20,
+        user_id: 1,
+        author_id: 10,
+        title: "A Wizard of Earthsea".to_string(),
+        cover: "https://example.com/wizard.jpg".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at,
+        isbn10: None,
+        isbn13: None,
+        cover_blob: None,
+        cover_type: None,
+        published_year: Some(1968),
+        published_month: None,
+        published_day: None,
+        published_precision: Some("year".to_string()),
+        published_text: None,
+        series_id: None,
+        series_index: None,
+        total_pages: None,
+    }
+}
+
+fn author(deleted_at: Option<DateTimeUtc>) -> author::Model {
+    author::Model {
+        id: 10,
+        user_id: 1,
+        firstname: "Ursula".to_string(),
+        lastname: "Le Guin".to_string(),
+        bio: "Author of Earthsea".to_string(),
+        created_at: None,
+        updated_at: None,
+        version: 1,
+        deleted_at,
+    }
+}
+
+fn rows(rows_affected: u64) -> MockExecResult {
+    MockExecResult { last_insert_id: 0, rows_affected }
+}
+
+#[rocket::async_test]
+async fn listing_hides_trashed_books() {
+    let db = db().append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 1, Method::Get, "/books", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements[0].sql.contains("`book`.`deleted_at` IS NULL"));
+}
+
+#[rocket::async_test]
+async fn book_delete_moves_to_trash() {
+    let db = db()
+        .append_query_results([vec![book(None)]])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![book(Some(trashed_at()))]]);
+    let reply = request(db, 1, Method::Delete, "/books/20", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements.iter().all(|s| !s.sql.starts_with("DELETE")));
+    assert!(reply.statements[1].sql.contains("`deleted_at` = ?"));
+}
+
+#[rocket::async_test]
+async fn trash_lists_books_and_authors_with_purge_date() {
+    let db = db()
+        .append_query_results([vec![book(Some(trashed_at()))]])
+        .append_query_results([vec![author(Some(trashed_at()))]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Get, "/trash", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let body = reply.body.unwrap();
+    assert_eq!(body["books"][0]["id"], 20);
+    assert_eq!(body["books"][0]["title"], "A Wizard of Earthsea");
+    assert_eq!(body["authors"][0]["id"], 10);
+    assert_eq!(body["books"][0]["purge_at"], "2026-10-31T12:00:00Z");
+    assert!(reply.statements[0].sql.contains("`book`.`deleted_at` IS NOT NULL"));
+}
+
+#[rocket::async_test]
+async fn book_restore_requires_live_author() {
+    let db = db()
+        .append_query_results([vec![book(Some(trashed_at()))]])
+        .append_query_results([vec![author(Some(trashed_at()))]]);
+    let reply = request(db, 1, Method::Post, "/books/20/restore", &[], None).await;
+
+    assert_eq!(reply.status, Status::Conflict);
+    assert_eq!(reply.body.unwrap()["details"]["author_id"], 10);
+    assert_eq!(reply.statements.len(), 2);
+}
+
+#[rocket::async_test]
+async fn book_restore_clears_deleted_at() {
+    let db = db()
+        .append_query_results([vec![book(Some(trashed_at()))]])
+        .append_query_results([Vec::<author::Model>::new()])
+        .append_exec_results([rows(1)])
+        .append_query_results([vec![book(None)]])
+        .append_query_results([no_contributors()])
+        .append_query_results([no_ratings()])
+        .append_query_results([no_tags()]);
+    let reply = request(db, 1, Method::Post, "/books/20/restore", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let update = &reply.statements[2];
+    assert!(update.sql.contains("`deleted_at` = ?"));
+    assert!(update.values.as_ref().unwrap().0.contains(&sea_orm::Value::ChronoDateTimeUtc(None)));
+}
+
+#[rocket::async_test]
+async fn restore_unknown_book_is_not_found() {
+    let db = db().append_query_results([Vec::<book::Model>::new()]);
+    let reply = request(db, 1, Method::Post, "/books/20/restore", &[], None).await;
+
+    assert_eq!(reply.status, Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn author_delete_trashes_its_books_too() {
+    let db = db()
+        .append_query_results([vec![author(None)]])
+        .append_exec_results([rows(2), rows(1)])
+        .append_query_results([vec![author(Some(trashed_at()))]]);
+    let reply = request(db, 1, Method::Delete, "/authors/10?on_books=cascade", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    assert!(reply.statements[1].sql.starts_with("UPDATE `book` SET"));
+    assert!(reply.statements[1].sql.contains("`deleted_at` = ?"));
+    assert!(reply.statements[2].sql.starts_with("UPDATE `author` SET"));
+    assert!(reply.statements.iter().all(|s| !s.sql.starts_with("DELETE")));
+}
+
+#[rocket::async_test]
+async fn author_restore_brings_back_books_trashed_with_it() {
+    let db = db()
+        .append_query_results([vec![author(Some(trashed_at()))]])
+        .append_exec_results([rows(2), rows(1)])
+        .append_query_results([vec![author(None)]]);
+    let reply = request(db, 1, Method::Post, "/authors/10/restore", &[], None).await;
+
+    assert_eq!(reply.status, Status::Ok);
+    let books = &reply.statements[1];
+    assert!(books.sql.contains("`book`.`deleted_at` = ?"), "{}", books.sql);
+    assert!(books.values.as_ref().unwrap().0.contains(&sea_orm::Value::ChronoDateTimeUtc(Some(Box::new(trashed_at())))));
+}
+
+#[rocket::async_test]
+async fn purge_removes_expired_books_then_orphaned_authors() {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results([Vec::<book::Model>::new()])
+            .append_exec_results([rows(3), rows(1)])
+            .into_connection(),
+    );
+    let service = TrashService::new(Arc::new(TrashRepositoryImplSql::new(Arc::clone(&db))), Arc::new(FsBlobStore::new("unused")));
+
+    let count = service.purge_expired().await.unwrap();
+    assert_eq!(count, PurgeCount { books: 3, authors: 1 });
+
+    drop(service);
+    let log = Arc::try_unwrap(db).ok().unwrap().into_transaction_log();
+    let statements: Vec<_> = log
+        .iter()
+        .flat_map(|t| t.statements().to_vec())
+        .filter(|s| !matches!(s.sql.as_str(), "BEGIN" | "COMMIT"))
+        .collect();
+    assert!(statements[0].sql.starts_with("SELECT"));
+    assert!(statements[0].sql.contains("`book`.`cover_blob` IS NOT NULL"), "{}", statements[0].sql);
+    assert!(statements[1].sql.starts_with("DELETE FROM `book` WHERE `book`.`deleted_at` < ?"));
+    assert!(statements[2].sql.starts_with("DELETE FROM `author` WHERE `author`.`deleted_at` < ?"));
+    assert!(statements[2].sql.contains("NOT IN (SELECT `author_id` FROM `book`)"), "{}", statements[2].sql);
+}
+
+#[rocket::async_test]
+async fn purge_deletes_the_covers_of_purged_books() {
+    let dir = std::env::temp_dir().join(format!("bookstore-purge-{}", std::process::id()));
+    let blobs = Arc::new(FsBlobStore::new(dir.to_str().unwrap()));
+    let cover = CoverRef { hash: "c0ffee".to_string(), content_type: "image/png".to_string() };
+    for size in CoverSize::ALL {
+        let blob = Blob { content_type: cover.content_type.to_owned(), bytes: b"png".to_vec() };
+        blobs.put(&cover.key(20, size), blob).await.unwrap();
+    }
+
+    let trashed = book::Model {
+        cover_blob: Some(cover.hash.to_owned()),
+        cover_type: Some(cover.content_type.to_owned()),
+        ..book(Some(trashed_at()))
+    };
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::MySql)
+            .append_query_results([vec![trashed]])
+            .append_exec_results([rows(1), rows(0)])
+            .into_connection(),
+    );
+    let service = TrashService::new(Arc::new(TrashRepositoryImplSql::new(db)), blobs.clone());
+
+    let count = service.purge_expired().await.unwrap();
+    assert_eq!(count.books, 1);
+    for size in CoverSize::ALL {
+        assert!(blobs.get(&cover.key(20, size)).await.unwrap().is_none());
+    }
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,136 @@
+use std::{sync::Arc, time::SystemTime};
+
+use jsonwebtoken::{encode, EncodingKey, Header as JwtHeader};
+use rocket::{
+    http::{ContentType, Header, Status},
+    local::asynchronous::Client,
+    serde::json::Value,
+};
+use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+use udemy_mini_book::{
+    application::service::init_service_setup::init_service_setup,
+    config::jwt_config::JwtSecret,
+    domain::entities::{author, refresh_token},
+    infrastructure::rocket_http::{jwt::jwt_claim::Claims, routes::init_route_setup::init_routes_setup},
+};
+
+fn token() -> String {
+    let claims = Claims {
+        sub: 1,
+        role: "user".to_string(),
+        sid: "test-session".to_string(),
+        exp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 60 * 60,
+    };
+    encode(
+        &JwtHeader::default(),
+        &claims,
+        &EncodingKey::from_secret(JwtSecret::default().jwt_secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Posts `body` to `uri` and returns the status, the JSON error body and
+/// how many statements reached the database besides the session lookup.
+async fn post(db: MockDatabase, uri: &str, body: &str) -> (Status, Value, usize) {
+    let db = Arc::new(db.into_connection());
+    let rocket = rocket::build()
+        .attach(init_service_setup(Arc::clone(&db)))
+        .attach(init_routes_setup());
+    let client = Client::tracked(rocket).await.unwrap();
+
+    let res = client
+        .post(uri)
+        .header(Header::new("token", token()))
+        .header(ContentType::JSON)
+        .body(body)
+        .dispatch()
+        .await;
+    let status = res.status();
+    let json = res.into_json::<Value>().await.unwrap();
+
+    drop(client);
+    let conn: DatabaseConnection = Arc::try_unwrap(db).ok().unwrap();
+    let queries = conn
+        .into_transaction_log()
+        .iter()
+        .flat_map(|t| t.statements().to_vec())
+        .filter(|stmt| !stmt.sql.contains("FROM `refresh_token`"))
+        .count();
+    (status, json, queries)
+}
+
+fn db() -> MockDatabase {
+    MockDatabase::new(DatabaseBackend::MySql).append_query_results([Vec::<refresh_token::Model>::new()])
+}
+
+fn fields(body: &Value) -> Vec<&str> {
+    let mut fields: Vec<&str> = body["details"].as_object().unwrap().keys().map(String::as_str).collect();
+    fields.sort();
+    fields
+}
+
+#[rocket::async_test]
+async fn sign_up_rejects_bad_email_and_weak_password() {
+    let body = r#"{"email":"not-an-email","password":"short"}"#;
+    let (status, json, queries) = post(MockDatabase::new(DatabaseBackend::MySql), "/auth/sign-up", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(json["code"], "validation_failed");
+    assert_eq!(fields(&json), ["email", "password"]);
+    assert_eq!(queries, 0);
+}
+
+#[rocket::async_test]
+async fn sign_up_rejects_blank_names() {
+    let body = r#"{"email":"a@example.com","password":"secret123","firstname":"   "}"#;
+    let (status, json, _) = post(MockDatabase::new(DatabaseBackend::MySql), "/auth/sign-up", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(fields(&json), ["firstname"]);
+}
+
+#[rocket::async_test]
+async fn book_rejects_blank_title_bad_date_and_cover() {
+    let body = r#"{"author_id":10,"title":" ","published_on":{"year":1965,"month":13},"cover":"cover.jpg"}"#;
+    let (status, json, queries) = post(db(), "/books", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(fields(&json), ["cover", "published_on", "title"]);
+    assert_eq!(json["details"]["published_on"][0], "month must be between 1 and 12");
+    assert_eq!(queries, 0);
+}
+
+#[rocket::async_test]
+async fn book_rejects_year_out_of_range() {
+    let body = r#"{"author_id":10,"title":"Dune","published_on":{"year":99999},"cover":"https://example.com/dune.jpg"}"#;
+    let (status, json, _) = post(db(), "/books", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(fields(&json), ["published_on"]);
+}
+
+#[rocket::async_test]
+async fn book_reports_missing_author_on_its_field() {
+    let body = r#"{"author_id":10,"title":"Dune","published_on":{"year":1965},"cover":"https://example.com/dune.jpg"}"#;
+    let db = db().append_query_results([Vec::<author::Model>::new()]);
+    let (status, json, _) = post(db, "/books", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(json["details"]["author_id"][0], "Author not found");
+}
+
+#[rocket::async_test]
+async fn author_rejects_blank_names() {
+    let body = r#"{"firstname":"","lastname":"  ","bio":"-"}"#;
+    let (status, json, queries) = post(db(), "/authors", body).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(fields(&json), ["firstname", "lastname"]);
+    assert_eq!(queries, 0);
+}
+
+#[rocket::async_test]
+async fn missing_field_is_a_validation_error() {
+    let (status, json, _) = post(db(), "/authors", r#"{"firstname":"Frank"}"#).await;
+    assert_eq!(status, Status::UnprocessableEntity);
+    assert_eq!(json["code"], "validation_failed");
+    assert!(json["message"].as_str().unwrap().contains("missing field"));
+}